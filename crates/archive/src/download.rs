@@ -0,0 +1,353 @@
+//! Provides downloads to a file that are retried after transient failures, and that resume
+//! a partial file with an HTTP `Range` request instead of starting over.
+
+use std::ffi::OsString;
+use std::fs::{read_to_string, remove_file, write, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::Duration;
+
+use super::ArchiveError;
+use attohttpc::header::{
+    HeaderMap, CONTENT_LENGTH, CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE,
+};
+use attohttpc::{RequestBuilder, StatusCode};
+
+/// The number of times a download is attempted before giving up
+const MAX_ATTEMPTS: u32 = 4;
+
+/// The delay before the first retry, which doubles with each further retry
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Downloads to `dest`, calling `progress` with the number of bytes downloaded so far and the
+/// total size (or 0 if the server didn't report it).
+///
+/// If `dest` already holds part of the file, such as after an interrupted download, only the
+/// rest of it is requested, on the condition that the file hasn't changed since. To check that,
+/// the `ETag` or `Last-Modified` header of the file is kept beside `dest` until the download
+/// completes; a partial file without one is downloaded again in full. Connection failures and 5xx responses that are likely to be
+/// transient are retried with exponential backoff, resuming from wherever the previous attempt
+/// stopped. `request` is called to build the request for each attempt.
+pub fn download<F>(
+    request: F,
+    dest: &Path,
+    progress: &mut dyn FnMut(u64, u64),
+) -> Result<(), ArchiveError>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match download_once(&request, dest, progress) {
+            Err(ref error) if attempt < MAX_ATTEMPTS && is_transient(error) => {
+                sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn download_once<F>(
+    request: &F,
+    dest: &Path,
+    progress: &mut dyn FnMut(u64, u64),
+) -> Result<(), ArchiveError>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let validator_file = validator_file(dest);
+    let partial_len = dest.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    let validator = if partial_len > 0 {
+        read_to_string(&validator_file).ok()
+    } else {
+        None
+    };
+
+    let builder = match validator {
+        Some(ref validator) => request()
+            .header(RANGE, format!("bytes={}-", partial_len))
+            .header(IF_RANGE, validator.trim()),
+        None => request(),
+    };
+    let (status, headers, mut response) = builder.send()?.split();
+
+    let (mut file, mut downloaded) = match status {
+        StatusCode::PARTIAL_CONTENT
+            if validator.is_some() && range_start(&headers) == Some(partial_len) =>
+        {
+            (OpenOptions::new().append(true).open(dest)?, partial_len)
+        }
+        // The partial file doesn't belong to the file being served, so start over
+        StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE if validator.is_some() => {
+            remove_file(dest)?;
+            remove_file_if_exists(&validator_file)?;
+            return download_once(request, dest, progress);
+        }
+        // The whole file is served, either because nothing was downloaded yet or because it
+        // changed since the partial file was downloaded
+        status if status.is_success() && status != StatusCode::PARTIAL_CONTENT => {
+            save_validator(&validator_file, &headers)?;
+            (File::create(dest)?, 0)
+        }
+        status => return Err(ArchiveError::HttpError(status)),
+    };
+
+    let total = content_length(&headers).map_or(0, |length| downloaded + length);
+    progress(downloaded, total);
+
+    let mut buffer = [0; 8 * 1024];
+    loop {
+        let read = response.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read])?;
+        downloaded += read as u64;
+        progress(downloaded, total);
+    }
+    file.flush()?;
+
+    if downloaded < total {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "download ended early").into());
+    }
+
+    remove_file_if_exists(&validator_file)?;
+    Ok(())
+}
+
+/// The file beside a partial download that holds the validator of the file it is part of
+fn validator_file(dest: &Path) -> PathBuf {
+    let mut name = OsString::from(dest.as_os_str());
+    name.push(".validator");
+    PathBuf::from(name)
+}
+
+/// Keep the validator of the file being downloaded, so that the download can be resumed
+///
+/// `If-Range` only accepts a strong `ETag`, so `Last-Modified` is used for a weak one. If the
+/// server sends neither, any old validator is removed so that the download will start over.
+fn save_validator(validator_file: &Path, headers: &HeaderMap) -> io::Result<()> {
+    let etag = headers
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .filter(|etag| !etag.starts_with("W/"));
+    let last_modified = headers
+        .get(LAST_MODIFIED)
+        .and_then(|value| value.to_str().ok());
+
+    match etag.or(last_modified) {
+        Some(validator) => write(validator_file, validator),
+        None => remove_file_if_exists(validator_file),
+    }
+}
+
+fn remove_file_if_exists(path: &Path) -> io::Result<()> {
+    match remove_file(path) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// The first byte of a partial response, from its `Content-Range` header, e.g. `bytes 100-199/200`
+fn range_start(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|range| range.strip_prefix("bytes "))
+        .and_then(|range| range.split('-').next())
+        .and_then(|start| start.trim().parse().ok())
+}
+
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+/// Determines whether a failed download is worth retrying
+fn is_transient(error: &ArchiveError) -> bool {
+    match error {
+        ArchiveError::HttpError(status) => matches!(
+            *status,
+            StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        ),
+        ArchiveError::IoError(error) => is_transient_io(error),
+        ArchiveError::AttohttpcError(error) => match error.kind() {
+            attohttpc::ErrorKind::Io(error) => is_transient_io(error),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn is_transient_io(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::env;
+    use std::fs::read;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::process;
+    use std::thread;
+
+    /// Serve each of `responses` to one connection, returning the URL of the server and the
+    /// lowercased request headers that it received
+    fn serve(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/archive.tgz", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line.to_lowercase());
+                }
+                requests.push(request);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        (url, server)
+    }
+
+    fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let mut response = format!("HTTP/1.1 {}\r\n", status);
+        for header in headers {
+            response.push_str(header);
+            response.push_str("\r\n");
+        }
+        response.push_str(&format!(
+            "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        response
+    }
+
+    /// A partial download with the validator of the file that it is part of
+    fn partial_file(name: &str, contents: &str, validator: &str) -> PathBuf {
+        let dest = env::temp_dir().join(format!("archive-{}-{}.partial", name, process::id()));
+        write(&dest, contents).unwrap();
+        write(validator_file(&dest), validator).unwrap();
+        dest
+    }
+
+    #[test]
+    fn resumes_partial_file_with_partial_content() {
+        let dest = partial_file("resume", "hello ", "\"v1\"");
+        let (url, server) = serve(vec![response(
+            "206 Partial Content",
+            &["Content-Range: bytes 6-10/11"],
+            "world",
+        )]);
+
+        download(|| attohttpc::get(&url), &dest, &mut |_, _| {}).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("range: bytes=6-\r\n"));
+        assert!(requests[0].contains("if-range: \"v1\"\r\n"));
+        assert_eq!(read(&dest).unwrap(), b"hello world");
+        assert!(!validator_file(&dest).exists());
+        remove_file(dest).unwrap();
+    }
+
+    #[test]
+    fn starts_over_when_whole_file_is_served() {
+        let dest = partial_file("changed", "stale ", "\"v1\"");
+        let (url, server) = serve(vec![response("200 OK", &["ETag: \"v2\""], "changed file")]);
+
+        download(|| attohttpc::get(&url), &dest, &mut |_, _| {}).unwrap();
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(read(&dest).unwrap(), b"changed file");
+        remove_file(dest).unwrap();
+    }
+
+    #[test]
+    fn starts_over_when_range_is_not_satisfiable() {
+        let dest = partial_file("unsatisfiable", "left over", "\"v1\"");
+        let (url, server) = serve(vec![
+            response("416 Range Not Satisfiable", &[], ""),
+            response("200 OK", &[], "whole file"),
+        ]);
+
+        download(|| attohttpc::get(&url), &dest, &mut |_, _| {}).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("range: "));
+        assert!(!requests[1].contains("range: "));
+        assert_eq!(read(&dest).unwrap(), b"whole file");
+        remove_file(dest).unwrap();
+    }
+
+    #[test]
+    fn starts_over_without_validator() {
+        let dest = partial_file("unvalidated", "unknown", "");
+        remove_file(validator_file(&dest)).unwrap();
+        let (url, server) = serve(vec![response("200 OK", &[], "whole file")]);
+
+        download(|| attohttpc::get(&url), &dest, &mut |_, _| {}).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(!requests[0].contains("range: "));
+        assert_eq!(read(&dest).unwrap(), b"whole file");
+        remove_file(dest).unwrap();
+    }
+
+    #[test]
+    fn test_transient_statuses() {
+        assert!(is_transient(&ArchiveError::HttpError(
+            StatusCode::SERVICE_UNAVAILABLE
+        )));
+        assert!(is_transient(&ArchiveError::HttpError(
+            StatusCode::BAD_GATEWAY
+        )));
+        assert!(!is_transient(&ArchiveError::HttpError(
+            StatusCode::NOT_IMPLEMENTED
+        )));
+        assert!(!is_transient(&ArchiveError::HttpError(
+            StatusCode::NOT_FOUND
+        )));
+    }
+
+    #[test]
+    fn test_transient_io_errors() {
+        let reset = io::Error::new(io::ErrorKind::ConnectionReset, "reset");
+        assert!(is_transient(&ArchiveError::IoError(reset)));
+
+        let denied = io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        assert!(!is_transient(&ArchiveError::IoError(denied)));
+    }
+}
@@ -5,9 +5,11 @@ use std::path::Path;
 
 use thiserror::Error;
 
+mod download;
 mod tarball;
 mod zip;
 
+pub use crate::download::download;
 pub use crate::tarball::Tarball;
 pub use crate::zip::Zip;
 
@@ -17,12 +19,6 @@ pub enum ArchiveError {
     #[error("HTTP failure ({0})")]
     HttpError(attohttpc::StatusCode),
 
-    #[error("HTTP header '{0}' not found")]
-    MissingHeaderError(String),
-
-    #[error("unexpected content length in HTTP response: {0}")]
-    UnexpectedContentLengthError(u64),
-
     #[error("{0}")]
     IoError(#[from] std::io::Error),
 
@@ -63,15 +59,6 @@ cfg_if::cfg_if! {
         pub fn load_native(source: File) -> Result<Box<dyn Archive>, ArchiveError> {
             Tarball::load(source)
         }
-
-        /// Fetch a remote archive in the native OS-preferred format from the specified
-        /// URL and store its results at the specified file path.
-        ///
-        /// On Windows, the preferred format is zip. On Unixes, the preferred format
-        /// is tarball.
-        pub fn fetch_native(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-            Tarball::fetch(url, cache_file)
-        }
     } else if #[cfg(windows)] {
         /// Load an archive in the native OS-preferred format from the specified file.
         ///
@@ -80,15 +67,6 @@ cfg_if::cfg_if! {
         pub fn load_native(source: File) -> Result<Box<dyn Archive>, ArchiveError> {
             Zip::load(source)
         }
-
-        /// Fetch a remote archive in the native OS-preferred format from the specified
-        /// URL and store its results at the specified file path.
-        ///
-        /// On Windows, the preferred format is zip. On Unixes, the preferred format
-        /// is tarball.
-        pub fn fetch_native(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-            Zip::fetch(url, cache_file)
-        }
     } else {
         compile_error!("Unsupported OS (expected 'unix' or 'windows').");
     }
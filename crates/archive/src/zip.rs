@@ -30,30 +30,6 @@ impl Zip {
             origin: Origin::Local,
         }))
     }
-
-    /// Initiate fetching of a Node zip archive from the given URL, returning
-    /// a `Remote` data source.
-    pub fn fetch(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-        let (status, _, mut response) = attohttpc::get(url).send()?.split();
-
-        if !status.is_success() {
-            return Err(ArchiveError::HttpError(status));
-        }
-
-        {
-            let mut file = File::create(cache_file)?;
-            copy(&mut response, &mut file)?;
-        }
-
-        let file = File::open(cache_file)?;
-        let compressed_size = file.metadata()?.len();
-
-        Ok(Box::new(Zip {
-            compressed_size,
-            data: file,
-            origin: Origin::Remote,
-        }))
-    }
 }
 
 impl Archive for Zip {
@@ -0,0 +1,467 @@
+//! Provides types for working with the Volta configuration file, `config.json`.
+
+use std::env;
+use std::fs::{write, File};
+use std::iter::once;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::read_file;
+use crate::layout::volta_home;
+use crate::policy::ResolutionPolicy;
+use crate::project::find_workspace_roots;
+use crate::tool::package::PackageManager;
+use double_checked_cell::DoubleCheckedCell;
+use fs_utils::ensure_containing_dir_exists;
+use lazy_static::lazy_static;
+use log::{debug, warn, LevelFilter};
+use serde_json::{Map, Value};
+
+mod serial;
+
+/// The settings that can be read with `volta config get` and written with `volta config set`
+pub const CONFIG_KEYS: [&str; 9] = [
+    "registry",
+    "nodeMirror",
+    "offline",
+    "indexCacheTtl",
+    "logLevel",
+    "defaultPackageManager",
+    "resolution",
+    "usageHistory",
+    "allowUnverified",
+];
+
+/// Environment variable that overrides the npm registry URL, taking precedence over the
+/// `registry` setting
+pub const REGISTRY_VAR: &str = "VOLTA_REGISTRY";
+
+/// Environment variable that overrides the Node distribution server, taking precedence over the
+/// `nodeMirror` setting
+pub const NODE_MIRROR_VAR: &str = "VOLTA_NODE_MIRROR";
+
+/// Environment variable that overrides how long the Node index is cached, in seconds, taking
+/// precedence over the `indexCacheTtl` setting
+pub const INDEX_CACHE_TTL_VAR: &str = "VOLTA_INDEX_CACHE_TTL";
+
+/// Environment variable that allows installing registry tarballs without a published integrity,
+/// in addition to the `allowUnverified` setting
+pub const ALLOW_UNVERIFIED_VAR: &str = "VOLTA_ALLOW_UNVERIFIED";
+
+lazy_static! {
+    static ref CONFIG: DoubleCheckedCell<Config> = DoubleCheckedCell::new();
+}
+
+/// Returns the merged settings from the project and user configuration files, loading them the
+/// first time they are needed
+///
+/// Only the configuration files are read, so loading the settings never resolves the project
+/// platform or accesses the network.
+pub fn current() -> Fallible<&'static Config> {
+    CONFIG.get_or_try_init(Config::current)
+}
+
+/// Volta configuration
+///
+/// Settings that aren't specified in any configuration file are `None`, so that the built-in
+/// defaults (or the equivalent environment variables) apply.
+#[derive(Default)]
+pub struct Config {
+    registry: Option<String>,
+    node_mirror: Option<String>,
+    offline: Option<bool>,
+    index_cache_ttl: Option<u64>,
+    log_level: Option<LevelFilter>,
+    default_package_manager: Option<PackageManager>,
+    resolution: Option<ResolutionPolicy>,
+    usage_history: Option<bool>,
+    allow_unverified: Option<bool>,
+}
+
+impl Config {
+    /// The package manager that `volta install` uses for global packages
+    pub fn default_package_manager(&self) -> PackageManager {
+        self.default_package_manager.unwrap_or(PackageManager::Npm)
+    }
+
+    /// Returns the value of a setting as a string, if it is set
+    pub fn get(&self, key: &str) -> Fallible<Option<String>> {
+        let value = match key {
+            "registry" => self.registry.clone(),
+            "nodeMirror" => self.node_mirror.clone(),
+            "offline" => self.offline.map(|offline| offline.to_string()),
+            "indexCacheTtl" => self.index_cache_ttl.map(|ttl| ttl.to_string()),
+            "logLevel" => self.log_level.map(|level| level.to_string().to_lowercase()),
+            "defaultPackageManager" => self.default_package_manager.map(|manager| {
+                match manager {
+                    PackageManager::Npm => "npm",
+                    PackageManager::Pnpm => "pnpm",
+                    PackageManager::Yarn => "yarn",
+                }
+                .to_string()
+            }),
+            "resolution" => self.resolution.map(|policy| policy.to_string()),
+            "usageHistory" => self.usage_history.map(|enabled| enabled.to_string()),
+            "allowUnverified" => self.allow_unverified.map(|allowed| allowed.to_string()),
+            _ => return Err(ErrorKind::UnknownConfigKey { key: key.into() }.into()),
+        };
+
+        Ok(value)
+    }
+
+    /// Returns the current configuration, which is a merge between the user configuration and
+    /// the project configuration (if any).
+    ///
+    /// A project configuration that can't be loaded is reported and skipped, so that the user's
+    /// own settings still apply.
+    fn current() -> Fallible<Self> {
+        let user_config = Self::from_paths(once(volta_home()?.config_file()))?;
+        let project_config = Self::from_project().unwrap_or_else(|error| {
+            warn!("Ignoring the project configuration: {}", error);
+            Self::default()
+        });
+
+        Ok(project_config.merge(user_config))
+    }
+
+    /// Returns the merged configuration of the workspaces containing the current directory
+    fn from_project() -> Fallible<Self> {
+        let workspace_roots = match env::current_dir() {
+            Ok(dir) => find_workspace_roots(dir)?,
+            Err(_) => Vec::new(),
+        };
+
+        // Inner workspaces take precedence over outer ones, in the same order as hooks (see
+        // `HookConfig::current`)
+        let paths = workspace_roots.into_iter().map(|root| {
+            let mut path = root.join(".volta");
+            path.push("config.json");
+            path
+        });
+
+        Self::from_paths(paths).map(Self::without_user_settings)
+    }
+
+    /// Removes the settings that only the user configuration may set
+    ///
+    /// A cloned repository shouldn't be able to redirect downloads to another server or turn off
+    /// their verification, so `registry`, `nodeMirror`, and `allowUnverified` are ignored in
+    /// project configuration files.
+    fn without_user_settings(self) -> Self {
+        if self.registry.is_some() || self.node_mirror.is_some() || self.allow_unverified.is_some()
+        {
+            warn!("Ignoring `registry`, `nodeMirror`, and `allowUnverified` in the project configuration, since they can only be set in the user configuration");
+        }
+
+        Self {
+            registry: None,
+            node_mirror: None,
+            allow_unverified: None,
+            ..self
+        }
+    }
+
+    /// Returns the merged configuration loaded from an iterator of potential config files
+    ///
+    /// `paths` should be sorted in order of descending precedence.
+    fn from_paths<P, I>(paths: I) -> Fallible<Self>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = P>,
+    {
+        paths.into_iter().try_fold(
+            Self::default(),
+            |loaded, config_file| match Self::from_file(config_file.as_ref())? {
+                Some(config) => {
+                    debug!(
+                        "Loaded configuration file: {}",
+                        config_file.as_ref().display()
+                    );
+                    Ok(loaded.merge(config))
+                }
+                None => Ok(loaded),
+            },
+        )
+    }
+
+    fn from_file(file_path: &Path) -> Fallible<Option<Self>> {
+        if !file_path.is_file() {
+            return Ok(None);
+        }
+
+        let file = File::open(file_path).with_context(|| ErrorKind::ReadConfigError {
+            file: file_path.to_path_buf(),
+        })?;
+
+        let raw: serial::RawConfig =
+            serde_json::de::from_reader(file).with_context(|| ErrorKind::ParseConfigError {
+                file: file_path.to_path_buf(),
+            })?;
+
+        raw.into_config(file_path).map(Some)
+    }
+
+    /// Merges this Config with another, giving precedence to the current instance
+    fn merge(self, other: Self) -> Self {
+        Self {
+            registry: self.registry.or(other.registry),
+            node_mirror: self.node_mirror.or(other.node_mirror),
+            offline: self.offline.or(other.offline),
+            index_cache_ttl: self.index_cache_ttl.or(other.index_cache_ttl),
+            log_level: self.log_level.or(other.log_level),
+            default_package_manager: self
+                .default_package_manager
+                .or(other.default_package_manager),
+            resolution: self.resolution.or(other.resolution),
+            usage_history: self.usage_history.or(other.usage_history),
+            allow_unverified: self.allow_unverified.or(other.allow_unverified),
+        }
+    }
+}
+
+/// Writes a setting to the user configuration file, preserving any other settings in the file
+pub fn set(key: &str, value: &str) -> Fallible<()> {
+    let value = parse_value(key, value)?;
+    let config_file = volta_home()?.config_file();
+
+    let mut settings = match read_file(config_file).with_context(|| ErrorKind::ReadConfigError {
+        file: config_file.to_path_buf(),
+    })? {
+        Some(contents) => {
+            serde_json::from_str::<Map<String, Value>>(&contents).with_context(|| {
+                ErrorKind::ParseConfigError {
+                    file: config_file.to_path_buf(),
+                }
+            })?
+        }
+        None => Map::new(),
+    };
+    settings.insert(key.into(), value);
+
+    // Serializing a map of JSON values can't fail
+    let contents = serde_json::to_string_pretty(&settings).expect("settings are valid JSON");
+
+    ensure_containing_dir_exists(&config_file)
+        .and_then(|_| write(config_file, contents))
+        .with_context(|| ErrorKind::WriteConfigError {
+            file: config_file.to_path_buf(),
+        })
+}
+
+/// Validates the value of a setting from the command line, converting it to JSON
+fn parse_value(key: &str, value: &str) -> Fallible<Value> {
+    let invalid = |expected: &str| ErrorKind::InvalidConfigValue {
+        key: key.into(),
+        value: value.into(),
+        expected: expected.into(),
+    };
+
+    match key {
+        "registry" | "nodeMirror" => {
+            if value.starts_with("http://") || value.starts_with("https://") {
+                Ok(Value::String(value.into()))
+            } else {
+                Err(invalid("an http:// or https:// URL").into())
+            }
+        }
+        "offline" | "usageHistory" | "allowUnverified" => value
+            .parse()
+            .map(Value::Bool)
+            .map_err(|_| invalid("`true` or `false`").into()),
+        "indexCacheTtl" => value
+            .parse::<u64>()
+            .map(Value::from)
+            .map_err(|_| invalid("a number of seconds").into()),
+        "logLevel" => match parse_log_level(value) {
+            Some(level) => Ok(Value::String(level.to_string().to_lowercase())),
+            None => {
+                Err(invalid("one of `off`, `error`, `warn`, `info`, `debug`, or `trace`").into())
+            }
+        },
+        "defaultPackageManager" => match parse_package_manager(value) {
+            Some(_) => Ok(Value::String(value.to_lowercase())),
+            None => Err(invalid("`npm`, `pnpm`, or `yarn`").into()),
+        },
+        "resolution" => match serde_json::from_value::<ResolutionPolicy>(value.into()) {
+            Ok(policy) => Ok(Value::String(policy.to_string())),
+            Err(_) => Err(invalid("`newest` or `prefer-local`").into()),
+        },
+        _ => Err(ErrorKind::UnknownConfigKey { key: key.into() }.into()),
+    }
+}
+
+fn parse_log_level(level: &str) -> Option<LevelFilter> {
+    level.to_uppercase().parse().ok()
+}
+
+fn parse_package_manager(manager: &str) -> Option<PackageManager> {
+    match manager.to_lowercase().as_str() {
+        "npm" => Some(PackageManager::Npm),
+        "pnpm" => Some(PackageManager::Pnpm),
+        "yarn" => Some(PackageManager::Yarn),
+        _ => None,
+    }
+}
+
+/// Returns the value of a setting from the configuration files, if it is set
+///
+/// The settings are read by code that runs without a `Session`, so a configuration file that
+/// can't be loaded is treated as if it were absent (`volta` reports the error on startup).
+fn setting<T, F>(value: F) -> Option<T>
+where
+    F: FnOnce(&Config) -> Option<T>,
+{
+    current().ok().and_then(value)
+}
+
+/// The npm registry URL, if one is configured
+///
+/// The `VOLTA_REGISTRY` environment variable takes precedence over the `registry` setting.
+pub(crate) fn registry_override() -> Option<String> {
+    env::var(REGISTRY_VAR)
+        .ok()
+        .or_else(|| setting(|config| config.registry.clone()))
+        .map(|registry| registry.trim_end_matches('/').to_string())
+}
+
+/// The root URL of the Node distribution server, if one is configured
+///
+/// The `VOLTA_NODE_MIRROR` environment variable takes precedence over the `nodeMirror` setting.
+pub(crate) fn node_mirror_override() -> Option<String> {
+    env::var(NODE_MIRROR_VAR)
+        .ok()
+        .or_else(|| setting(|config| config.node_mirror.clone()))
+        .map(|mirror| mirror.trim_end_matches('/').to_string())
+}
+
+/// How long a fetched copy of the Node index should be cached, if configured
+///
+/// The `VOLTA_INDEX_CACHE_TTL` environment variable takes precedence over the `indexCacheTtl`
+/// setting.
+pub(crate) fn index_cache_ttl() -> Option<Duration> {
+    env::var(INDEX_CACHE_TTL_VAR)
+        .ok()
+        .and_then(|ttl| ttl.parse().ok())
+        .or_else(|| setting(|config| config.index_cache_ttl))
+        .map(Duration::from_secs)
+}
+
+/// Whether offline mode is enabled by the `offline` setting
+pub(crate) fn offline() -> bool {
+    setting(|config| config.offline).unwrap_or(false)
+}
+
+/// The log level set by the `logLevel` setting, if any
+pub(crate) fn log_level() -> Option<LevelFilter> {
+    setting(|config| config.log_level)
+}
+
+/// The resolution policy set by the `resolution` setting, if any
+pub(crate) fn resolution() -> Option<ResolutionPolicy> {
+    setting(|config| config.resolution)
+}
+
+/// Whether the usage history is enabled by the `usageHistory` setting
+pub(crate) fn usage_history() -> bool {
+    setting(|config| config.usage_history).unwrap_or(false)
+}
+
+/// Whether registry tarballs without a published integrity may be installed
+///
+/// This is allowed by either the `VOLTA_ALLOW_UNVERIFIED` environment variable or the
+/// `allowUnverified` setting.
+pub(crate) fn allow_unverified() -> bool {
+    env::var_os(ALLOW_UNVERIFIED_VAR).is_some()
+        || setting(|config| config.allow_unverified).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture_path(fixture_dir: &str) -> PathBuf {
+        let mut cargo_manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cargo_manifest_dir.push("fixtures");
+        cargo_manifest_dir.push(fixture_dir);
+        cargo_manifest_dir
+    }
+
+    #[test]
+    fn test_from_file() {
+        let config = Config::from_file(&fixture_path("config/user.json"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            config.get("registry").unwrap(),
+            Some("https://registry.example.com".into())
+        );
+        assert_eq!(config.get("logLevel").unwrap(), Some("warn".into()));
+        assert_eq!(config.default_package_manager(), PackageManager::Yarn);
+        assert_eq!(config.get("offline").unwrap(), None);
+    }
+
+    #[test]
+    fn test_project_overrides_user() {
+        let config = Config::from_paths(&[
+            fixture_path("config/project.json"),
+            fixture_path("config/user.json"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            config.get("registry").unwrap(),
+            Some("https://registry.example.com".into())
+        );
+        assert_eq!(config.get("offline").unwrap(), Some("true".into()));
+        assert_eq!(config.default_package_manager(), PackageManager::Pnpm);
+    }
+
+    #[test]
+    fn test_project_cannot_set_user_settings() {
+        let config = Config::from_paths(&[fixture_path("config/project-untrusted.json")])
+            .unwrap()
+            .without_user_settings();
+
+        assert_eq!(config.get("registry").unwrap(), None);
+        assert_eq!(config.get("nodeMirror").unwrap(), None);
+        assert_eq!(config.get("allowUnverified").unwrap(), None);
+        assert_eq!(config.get("offline").unwrap(), Some("true".into()));
+    }
+
+    #[test]
+    fn test_invalid_value_in_file() {
+        assert!(Config::from_file(&fixture_path("config/invalid.json")).is_err());
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        assert!(Config::default().get("colour").is_err());
+        assert!(parse_value("colour", "red").is_err());
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value("offline", "true").unwrap(), Value::Bool(true));
+        assert_eq!(
+            parse_value("allowUnverified", "false").unwrap(),
+            Value::Bool(false)
+        );
+        assert_eq!(parse_value("indexCacheTtl", "60").unwrap(), Value::from(60));
+        assert_eq!(
+            parse_value("logLevel", "DEBUG").unwrap(),
+            Value::String("debug".into())
+        );
+        assert_eq!(
+            parse_value("resolution", "prefer-local").unwrap(),
+            Value::String("prefer-local".into())
+        );
+
+        assert!(parse_value("registry", "registry.example.com").is_err());
+        assert!(parse_value("indexCacheTtl", "soon").is_err());
+        assert!(parse_value("allowUnverified", "yes").is_err());
+        assert!(parse_value("defaultPackageManager", "bun").is_err());
+    }
+}
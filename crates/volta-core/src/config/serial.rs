@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use super::{parse_log_level, parse_package_manager, Config};
+use crate::error::{ErrorKind, Fallible};
+use crate::policy::ResolutionPolicy;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawConfig {
+    registry: Option<String>,
+    node_mirror: Option<String>,
+    offline: Option<bool>,
+    index_cache_ttl: Option<u64>,
+    log_level: Option<String>,
+    default_package_manager: Option<String>,
+    resolution: Option<ResolutionPolicy>,
+    usage_history: Option<bool>,
+    allow_unverified: Option<bool>,
+}
+
+impl RawConfig {
+    pub fn into_config(self, file: &Path) -> Fallible<Config> {
+        let parse_error = || ErrorKind::ParseConfigError {
+            file: file.to_path_buf(),
+        };
+
+        let log_level = match self.log_level {
+            Some(level) => Some(parse_log_level(&level).ok_or_else(parse_error)?),
+            None => None,
+        };
+        let default_package_manager = match self.default_package_manager {
+            Some(manager) => Some(parse_package_manager(&manager).ok_or_else(parse_error)?),
+            None => None,
+        };
+
+        Ok(Config {
+            registry: self.registry,
+            node_mirror: self.node_mirror,
+            offline: self.offline,
+            index_cache_ttl: self.index_cache_ttl,
+            log_level,
+            default_package_manager,
+            resolution: self.resolution,
+            usage_history: self.usage_history,
+            allow_unverified: self.allow_unverified,
+        })
+    }
+}
@@ -2,6 +2,7 @@ use std::fmt;
 use std::path::PathBuf;
 
 use super::ExitCode;
+use crate::config::CONFIG_KEYS;
 use crate::style::{text_width, tool_version};
 use crate::tool;
 use crate::tool::package::PackageManager;
@@ -50,6 +51,20 @@ pub enum ErrorKind {
         package: String,
     },
 
+    /// Thrown when the checksum of a downloaded archive doesn't match the published checksum
+    ChecksumMismatch {
+        tool: String,
+        version: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// Thrown when the published checksums don't include an entry for a downloaded archive
+    ChecksumNotFound {
+        file: String,
+        from_url: String,
+    },
+
     /// Thrown when the Completions out-dir is not a directory
     CompletionsOutFileError {
         path: PathBuf,
@@ -106,11 +121,25 @@ pub enum ErrorKind {
         advice: String,
     },
 
+    /// Thrown when the published checksums, or their signature, could not be downloaded
+    DownloadChecksumsError {
+        from_url: String,
+    },
+
     DownloadToolNetworkError {
         tool: tool::Spec,
         from_url: String,
     },
 
+    /// Thrown when strict engines checking is enabled and the active platform doesn't satisfy the
+    /// `engines` of the project
+    EnginesMismatch {
+        tool: String,
+        version: String,
+        range: String,
+        file: PathBuf,
+    },
+
     /// Thrown when unable to execute a hook command
     ExecuteHookError {
         command: String,
@@ -146,6 +175,19 @@ pub enum ErrorKind {
     /// Thrown when determining the name of a newly-installed package fails
     InstalledPackageNameError,
 
+    /// Thrown when the registry doesn't publish an integrity for a tarball that is downloaded from it
+    IntegrityNotFound {
+        tool: String,
+        version: String,
+    },
+
+    /// Thrown when `volta config set` is given a value that isn't valid for the setting
+    InvalidConfigValue {
+        key: String,
+        value: String,
+        expected: String,
+    },
+
     InvalidHookCommand {
         command: String,
     },
@@ -170,6 +212,11 @@ pub enum ErrorKind {
         version: String,
     },
 
+    /// Thrown when VOLTA_SHASUMS_SIGNATURE is set to an unsupported verifier
+    InvalidSignatureVerifier {
+        value: String,
+    },
+
     /// Thrown when a tool name is invalid per npm's rules.
     InvalidToolName {
         name: String,
@@ -179,11 +226,17 @@ pub enum ErrorKind {
     /// Thrown when unable to acquire a lock on the Volta directory
     LockAcquireError,
 
+    /// Thrown when minisign verification is requested without a public key
+    MissingMinisignPublicKey,
+
     /// Thrown when pinning or installing npm@bundled and couldn't detect the bundled version
     NoBundledNpm {
         command: String,
     },
 
+    /// Thrown when pnpm is not set at the command-line
+    NoCommandLinePnpm,
+
     /// Thrown when Yarn is not set at the command-line
     NoCommandLineYarn,
 
@@ -215,6 +268,9 @@ pub enum ErrorKind {
     /// Thrown when parsing the project manifest and there is a `"volta"` key without Node
     NoProjectNodeInManifest,
 
+    /// Thrown when pnpm is not set in a project
+    NoProjectPnpm,
+
     /// Thrown when Yarn is not set in a project
     NoProjectYarn,
 
@@ -227,6 +283,9 @@ pub enum ErrorKind {
     /// Thrown when the user tries to pin Node or Yarn versions outside of a package.
     NotInPackage,
 
+    /// Thrown when default pnpm is not set
+    NoDefaultPnpm,
+
     /// Thrown when default Yarn is not set
     NoDefaultYarn,
 
@@ -249,6 +308,17 @@ pub enum ErrorKind {
         version: String,
     },
 
+    /// Thrown when offline mode prevents a request to the network
+    OfflineFetchError {
+        from_url: String,
+    },
+
+    /// Thrown when offline mode can't find a fetched version matching a requested version
+    OfflineVersionNotFound {
+        tool: String,
+        matching: String,
+    },
+
     /// Thrown when the command to install a global package is not successful
     PackageInstallFailed {
         package: String,
@@ -290,6 +360,16 @@ pub enum ErrorKind {
     /// Thrown when unable to parse a bin config file
     ParseBinConfigError,
 
+    /// Thrown when a file of extra certificate authorities doesn't contain valid certificates
+    ParseCertificatesError {
+        file: PathBuf,
+    },
+
+    /// Thrown when unable to parse the Volta configuration file
+    ParseConfigError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to parse a hooks.json file
     ParseHooksError {
         file: PathBuf,
@@ -306,6 +386,11 @@ pub enum ErrorKind {
     /// Thrown when unable to parse the node index cache expiration
     ParseNodeIndexExpiryError,
 
+    /// Thrown when a `.nvmrc` or `.node-version` file doesn't contain a version
+    ParseNodeVersionFileError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to parse the npm manifest file from a node install
     ParseNpmManifestError,
 
@@ -320,11 +405,30 @@ pub enum ErrorKind {
         tool_spec: String,
     },
 
+    /// Thrown when an exported toolchain file is not valid, or was written by a newer version of
+    /// Volta
+    ParseToolchainExportError {
+        file: PathBuf,
+    },
+
     /// Thrown when persisting an archive to the inventory fails
     PersistInventoryError {
         tool: String,
     },
 
+    /// Thrown when pinning a version that doesn't satisfy the `engines` of the project
+    PinEnginesMismatch {
+        tool: String,
+        version: String,
+        range: String,
+        file: PathBuf,
+    },
+
+    /// Thrown when there is no pnpm version matching a requested semver specifier.
+    PnpmVersionNotFound {
+        matching: String,
+    },
+
     /// Thrown when executing a project-local binary fails
     ProjectLocalBinaryExecError {
         command: String,
@@ -335,12 +439,22 @@ pub enum ErrorKind {
         command: String,
     },
 
+    /// Thrown when a request could not be made through the configured HTTP proxy
+    ProxyConnectError {
+        from_url: String,
+    },
+
     /// Thrown when a publish hook contains both the url and bin fields
     PublishHookBothUrlAndBin,
 
     /// Thrown when a publish hook contains neither url nor bin fields
     PublishHookNeitherUrlNorBin,
 
+    /// Thrown when a downloaded archive could not be read to compute its checksum
+    ReadArchiveError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error reading the user bin directory
     ReadBinConfigDirError {
         dir: PathBuf,
@@ -351,6 +465,16 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when a file of extra certificate authorities could not be read
+    ReadCertificatesError {
+        file: PathBuf,
+    },
+
+    /// Thrown when there was an error opening the Volta configuration file
+    ReadConfigError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to read the default npm version file
     ReadDefaultNpmError {
         file: PathBuf,
@@ -376,9 +500,19 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when there was an error reading a `.nvmrc` or `.node-version` file
+    ReadNodeVersionFileError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error reading the npm manifest file
     ReadNpmManifestError,
 
+    /// Thrown when there was an error reading an `.npmrc` file
+    ReadNpmrcError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error reading a package configuration file
     ReadPackageConfigError {
         file: PathBuf,
@@ -389,6 +523,16 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when there was an error reading an exported toolchain file
+    ReadToolchainExportError {
+        file: PathBuf,
+    },
+
+    /// Thrown when there was an error reading the usage history
+    ReadUsageHistoryError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to read the user Path environment variable from the registry
     #[cfg(windows)]
     ReadUserPathError,
@@ -409,6 +553,12 @@ pub enum ErrorKind {
         dir: PathBuf,
     },
 
+    /// Thrown when the signature of the published checksums could not be verified
+    ShasumsSignatureError {
+        from_url: String,
+        verifier: String,
+    },
+
     /// Thrown when Volta is unable to create a shim
     ShimCreateError {
         name: String,
@@ -428,11 +578,41 @@ pub enum ErrorKind {
     /// Thrown when serializing the platform to JSON fails
     StringifyPlatformError,
 
+    /// Thrown when serializing the list of recently used projects to JSON fails
+    StringifyRecentProjectsError,
+
+    /// Thrown when serializing the toolchain for export fails
+    StringifyToolchainExportError,
+
+    /// Thrown when a secure connection could not be established, such as when the server's
+    /// certificate isn't trusted
+    TlsConnectError {
+        from_url: String,
+    },
+
     /// Thrown when a given feature has not yet been implemented
     Unimplemented {
         feature: String,
     },
 
+    /// Thrown when uninstalling a tool version that is still used by the default platform or
+    /// an installed package, without `--force`
+    UninstallVersionInUse {
+        tool: String,
+        version: String,
+        users: Vec<String>,
+    },
+
+    /// Thrown when uninstalling a runtime or package manager without specifying a version
+    UninstallVersionRequired {
+        tool: String,
+    },
+
+    /// Thrown when a `volta config` command names a setting that doesn't exist
+    UnknownConfigKey {
+        key: String,
+    },
+
     /// Thrown when unpacking an archive (tarball or zip) fails
     UnpackArchiveError {
         tool: String,
@@ -460,11 +640,21 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when writing the Volta configuration file fails
+    WriteConfigError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error writing the default npm to file
     WriteDefaultNpmError {
         file: PathBuf,
     },
 
+    /// Thrown when the verified integrity of a cached archive could not be written
+    WriteIntegrityError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error writing the npm launcher
     WriteLauncherError {
         tool: String,
@@ -490,6 +680,11 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when writing the list of recently used projects fails
+    WriteRecentProjectsError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to write the user PATH environment variable
     #[cfg(windows)]
     WriteUserPathError,
@@ -554,11 +749,34 @@ Use `volta install {}` to update the default version.",
             ),
             ErrorKind::CannotPinPackage { package } => write!(
                 f,
-                "Only node and yarn can be pinned in a project
+                "Only node, npm, pnpm, and yarn can be pinned in a project
 
-Use `npm install` or `yarn add` to select a version of {} for this project.",
+Use `npm install`, `pnpm add`, or `yarn add` to select a version of {} for this project.",
                 package
             ),
+            ErrorKind::ChecksumMismatch {
+                tool,
+                version,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "The downloaded archive for {}@{} does not match its published checksum.
+
+Expected: {}
+Actual:   {}
+
+The download may have been corrupted or tampered with. Please try again.",
+                tool, version, expected, actual
+            ),
+            ErrorKind::ChecksumNotFound { file, from_url } => write!(
+                f,
+                "Could not find a checksum for {}
+in {}
+
+Please ensure the distro server publishes checksums for every archive.",
+                file, from_url
+            ),
             ErrorKind::CompletionsOutFileError { path } => write!(
                 f,
                 "Completions file `{}` already exists.
@@ -653,6 +871,14 @@ at {}
             ErrorKind::DeprecatedCommandError { command, advice } => {
                 write!(f, "The subcommand `{}` is deprecated.\n{}", command, advice)
             }
+            ErrorKind::DownloadChecksumsError { from_url } => write!(
+                f,
+                "Could not download checksums from {}
+
+Please verify your internet connection, or run `volta config set allowUnverified true`
+to install without verification if the server doesn't publish checksums.",
+                from_url
+            ),
             ErrorKind::DownloadToolNetworkError { tool, from_url } => write!(
                 f,
                 "Could not download {}
@@ -661,6 +887,23 @@ from {}
 Please verify your internet connection and ensure the correct version is specified.",
                 tool, from_url
             ),
+            ErrorKind::EnginesMismatch {
+                tool,
+                version,
+                range,
+                file,
+            } => write!(
+                f,
+                r#"{}@{} does not satisfy the engines constraint "{}"
+from {}
+
+Use `volta pin {}` to select a version that does (see `volta help pin` for more info)."#,
+                tool,
+                version,
+                range,
+                file.display(),
+                tool
+            ),
             ErrorKind::ExecuteHookError { command } => write!(
                 f,
                 "Could not execute hook command: '{}'
@@ -733,6 +976,25 @@ Please ensure that the correct command is specified.",
 {}",
                 REPORT_BUG_CTA
             ),
+            ErrorKind::IntegrityNotFound { tool, version } => write!(
+                f,
+                "Could not find a published checksum for {}@{}, so the download can't be verified.
+
+Please ensure the registry publishes an integrity for every version, or run
+`volta config set allowUnverified true` to install it without verification.",
+                tool, version
+            ),
+            ErrorKind::InvalidConfigValue {
+                key,
+                value,
+                expected,
+            } => write!(
+                f,
+                "Invalid value '{}' for setting '{}'
+
+Please provide {}.",
+                value, key, expected
+            ),
             ErrorKind::InvalidHookCommand { command } => write!(
                 f,
                 "Invalid hook command: '{}'
@@ -803,6 +1065,13 @@ To {action} the package '{version}', please use an explicit version such as '{ve
                 write!(f, "{}\n\n{}", error, wrapped_cta)
             }
 
+            ErrorKind::InvalidSignatureVerifier { value } => write!(
+                f,
+                "Unrecognized value for VOLTA_SHASUMS_SIGNATURE: '{}'
+
+Please set it to `gpg` or `minisign`.",
+                value
+            ),
             ErrorKind::InvalidToolName { name, errors } => {
                 let indentation = "    ";
                 let wrapped = match text_width() {
@@ -828,6 +1097,12 @@ To {action} the package '{version}', please use an explicit version such as '{ve
                 f,
                 "Unable to acquire lock on Volta directory"
             ),
+            ErrorKind::MissingMinisignPublicKey => write!(
+                f,
+                "VOLTA_SHASUMS_SIGNATURE is set to `minisign`, but no public key was provided.
+
+Please set VOLTA_MINISIGN_PUBLIC_KEY to the public key used to sign the checksums."
+            ),
             ErrorKind::NoBundledNpm { command } => write!(
                 f,
                 "Could not detect bundled npm version.
@@ -835,6 +1110,12 @@ To {action} the package '{version}', please use an explicit version such as '{ve
 Please ensure you have a Node version selected with `volta {} node` (see `volta help {0}` for more info).",
                 command
             ),
+            ErrorKind::NoCommandLinePnpm => write!(
+                f,
+                "No pnpm version specified.
+
+Use `volta run --pnpm` to select a version (see `volta help run` for more info)."
+            ),
             ErrorKind::NoCommandLineYarn => write!(
                 f,
                 "No Yarn version specified.
@@ -891,6 +1172,12 @@ To run any Node command, first set a default version using `volta install node`"
                 "No Node version found in this project.
 
 Use `volta pin node` to select a version (see `volta help pin` for more info)."
+            ),
+            ErrorKind::NoProjectPnpm => write!(
+                f,
+                "No pnpm version found in this project.
+
+Use `volta pin pnpm` to select a version (see `volta help pin` for more info)."
             ),
             ErrorKind::NoProjectYarn => write!(
                 f,
@@ -911,6 +1198,12 @@ Please create one of these and try again; or you can edit your profile manually
                 "Not in a node package.
 
 Use `volta install` to select a default version of a tool."
+            ),
+            ErrorKind::NoDefaultPnpm => write!(
+                f,
+                "pnpm is not available.
+
+Use `volta install pnpm` to select a default version (see `volta help install` for more info)."
             ),
             ErrorKind::NoDefaultYarn => write!(
                 f,
@@ -946,6 +1239,22 @@ Please verify that the version is correct."#,
 This project is configured to use version {} of npm.",
                 version
             ),
+            ErrorKind::OfflineFetchError { from_url } => write!(
+                f,
+                "Could not download from {}
+because Volta is in offline mode.
+
+Unset VOLTA_OFFLINE (or remove the `--offline` flag) to allow network access.",
+                from_url
+            ),
+            ErrorKind::OfflineVersionNotFound { tool, matching } => write!(
+                f,
+                r#"Could not find a fetched {} version matching "{}".
+
+Volta is in offline mode, so only versions in the local inventory can be used.
+Fetch the version while online with `volta fetch {0}@{1}`."#,
+                tool, matching
+            ),
             ErrorKind::PackageInstallFailed { package } => write!(
                 f,
                 "Could not install package '{}'
@@ -1011,6 +1320,22 @@ Please ensure you have correct permissions.",
 {}",
                 REPORT_BUG_CTA
             ),
+            ErrorKind::ParseCertificatesError { file } => write!(
+                f,
+                "Could not parse certificate authorities
+from {}
+
+Please ensure the file contains PEM-encoded certificates.",
+                file.display()
+            ),
+            ErrorKind::ParseConfigError { file } => write!(
+                f,
+                "Could not parse Volta configuration file.
+from {}
+
+Please ensure the file is correctly formatted.",
+                file.display()
+            ),
             ErrorKind::ParseHooksError { file } => write!(
                 f,
                 "Could not parse hooks configuration file.
@@ -1041,6 +1366,13 @@ Please verify your internet connection.",
 {}",
                 REPORT_BUG_CTA
             ),
+            ErrorKind::ParseNodeVersionFileError { file } => write!(
+                f,
+                "Could not find a Node version in {}
+
+Please ensure the file contains a version, for example `16.14.0`.",
+                file.display()
+            ),
             ErrorKind::ParseNpmManifestError => write!(
                 f,
                 "Could not parse package.json file for bundled npm.
@@ -1068,6 +1400,14 @@ Please ensure the version of Node is correct."
 Please supply a spec in the format `<tool name>[@<version>]`.",
                 tool_spec
             ),
+            ErrorKind::ParseToolchainExportError { file } => write!(
+                f,
+                "Could not parse exported toolchain
+from {}
+
+Please ensure the file was created with `volta export` by this or an older version of Volta.",
+                file.display()
+            ),
             ErrorKind::PersistInventoryError { tool } => write!(
                 f,
                 "Could not store {} archive in inventory cache
@@ -1075,6 +1415,29 @@ Please supply a spec in the format `<tool name>[@<version>]`.",
 {}",
                 tool, PERMISSIONS_CTA
             ),
+            ErrorKind::PinEnginesMismatch {
+                tool,
+                version,
+                range,
+                file,
+            } => write!(
+                f,
+                r#"Could not pin {}@{}, since it does not satisfy the engines constraint "{}"
+from {}
+
+Please choose a version that satisfies the constraint, or use `--force` to pin it anyway."#,
+                tool,
+                version,
+                range,
+                file.display()
+            ),
+            ErrorKind::PnpmVersionNotFound { matching } => write!(
+                f,
+                r#"Could not find pnpm version matching "{}" in the version registry.
+
+Please verify that the version is correct."#,
+                matching
+            ),
             ErrorKind::ProjectLocalBinaryExecError { command } => write!(
                 f,
                 "Could not execute `{}`
@@ -1089,6 +1452,14 @@ Please ensure you have correct permissions to access the file.",
 Please ensure that all project dependencies are installed with `npm install` or `yarn install`",
                 command
             ),
+            ErrorKind::ProxyConnectError { from_url } => write!(
+                f,
+                "Could not connect through the proxy to
+{}
+
+Please verify the proxy in your HTTPS_PROXY environment variable, or add the host to NO_PROXY.",
+                from_url
+            ),
             ErrorKind::PublishHookBothUrlAndBin => write!(
                 f,
                 "Publish hook configuration includes both hook types.
@@ -1101,6 +1472,15 @@ Please include only one of 'bin' or 'url'"
 
 Please include one of 'bin' or 'url'"
             ),
+            ErrorKind::ReadArchiveError { file } => write!(
+                f,
+                "Could not read downloaded archive
+from {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
             ErrorKind::ReadBinConfigDirError { dir } => write!(
                 f,
                 "Could not read executable metadata directory
@@ -1115,6 +1495,23 @@ at {}
                 "Could not read executable configuration
 from {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadCertificatesError { file } => write!(
+                f,
+                "Could not read certificate authorities
+from {}
+
+Please ensure the file exists and you have correct permissions to it.",
+                file.display()
+            ),
+            ErrorKind::ReadConfigError { file } => write!(
+                f,
+                "Could not read Volta configuration file
+from {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1158,6 +1555,15 @@ from {}
                 "Could not read Node index cache expiration
 from {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadNodeVersionFileError { file } => write!(
+                f,
+                "Could not read Node version file
+from {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1168,6 +1574,14 @@ from {}
 
 Please ensure the version of Node is correct."
             ),
+            ErrorKind::ReadNpmrcError { file } => write!(
+                f,
+                "Could not read npm configuration
+from {}
+
+Please ensure you have correct permissions to the file.",
+                file.display()
+            ),
             ErrorKind::ReadPackageConfigError { file } => write!(
                 f,
                 "Could not read package configuration file
@@ -1182,6 +1596,23 @@ from {}
                 "Could not read default platform file
 from {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadToolchainExportError { file } => write!(
+                f,
+                "Could not read exported toolchain
+from {}
+
+Please ensure the file exists and is readable.",
+                file.display()
+            ),
+            ErrorKind::ReadUsageHistoryError { file } => write!(
+                f,
+                "Could not read usage history
+from {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1218,6 +1649,14 @@ at {}
                 dir.display(),
                 PERMISSIONS_CTA
             ),
+            ErrorKind::ShasumsSignatureError { from_url, verifier } => write!(
+                f,
+                "Could not verify the signature of {}
+using `{}`
+
+Please ensure `{}` is installed and trusts the key used to sign the checksums.",
+                from_url, verifier, verifier
+            ),
             ErrorKind::ShimCreateError { name } => write!(
                 f,
                 r#"Could not create shim for "{}"
@@ -1253,9 +1692,69 @@ at {}
 {}",
                 REPORT_BUG_CTA
             ),
+            ErrorKind::StringifyRecentProjectsError => write!(
+                f,
+                "Could not serialize the list of recently used projects.
+
+{}",
+                REPORT_BUG_CTA
+            ),
+            ErrorKind::StringifyToolchainExportError => write!(
+                f,
+                "Could not serialize the toolchain for export.
+
+{}",
+                REPORT_BUG_CTA
+            ),
+            ErrorKind::TlsConnectError { from_url } => write!(
+                f,
+                "Could not establish a secure connection to
+{}
+
+If your network uses its own certificate authority, add it with the NODE_EXTRA_CA_CERTS
+environment variable or the `cafile` setting in your .npmrc.",
+                from_url
+            ),
             ErrorKind::Unimplemented { feature } => {
                 write!(f, "{} is not supported yet.", feature)
             }
+            ErrorKind::UninstallVersionInUse {
+                tool,
+                version,
+                users,
+            } => {
+                let error = format!(
+                    "Could not uninstall {} because it is used by {}.",
+                    tool_version(tool, version),
+                    users.join(", ")
+                );
+                let wrapped_error = match text_width() {
+                    Some(width) => fill(&error, width),
+                    None => error,
+                };
+
+                write!(
+                    f,
+                    "{}\n\nUse `volta uninstall --force {}` to remove it anyway.",
+                    wrapped_error,
+                    tool_version(tool, version)
+                )
+            }
+            ErrorKind::UninstallVersionRequired { tool } => write!(
+                f,
+                "Please specify the version of {0} to uninstall.
+
+Use `volta list {0}` to see the fetched versions, then run `volta uninstall {0}@<version>`.",
+                tool
+            ),
+            ErrorKind::UnknownConfigKey { key } => write!(
+                f,
+                "Unknown setting '{}'
+
+Please use one of: {}",
+                key,
+                CONFIG_KEYS.join(", ")
+            ),
             ErrorKind::UnpackArchiveError { tool, version } => write!(
                 f,
                 "Could not unpack {} v{}
@@ -1271,12 +1770,14 @@ Please ensure it is installed with `{} {0}`"#,
                 package,
                 match manager {
                     PackageManager::Npm => "npm i -g",
+                    PackageManager::Pnpm => "pnpm add -g",
                     PackageManager::Yarn => "yarn global add",
                 }
             ),
             ErrorKind::UpgradePackageWrongManager { package, manager } => {
                 let (name, command) = match manager {
                     PackageManager::Npm => ("npm", "npm update -g"),
+                    PackageManager::Pnpm => ("pnpm", "pnpm update -g"),
                     PackageManager::Yarn => ("Yarn", "yarn global upgrade"),
                 };
                 write!(
@@ -1299,6 +1800,15 @@ Please verify the intended version."#,
                 "Could not write executable configuration
 to {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WriteConfigError { file } => write!(
+                f,
+                "Could not save Volta configuration
+to {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1308,6 +1818,15 @@ to {}
                 "Could not write bundled npm version
 to {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WriteIntegrityError { file } => write!(
+                f,
+                "Could not write archive integrity
+to {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1351,6 +1870,15 @@ to {}
                 "Could not save platform settings
 to {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WriteRecentProjectsError { file } => write!(
+                f,
+                "Could not save the list of recently used projects
+to {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1391,6 +1919,8 @@ impl ErrorKind {
             ErrorKind::BypassError { .. } => ExitCode::ExecutionFailure,
             ErrorKind::CannotFetchPackage { .. } => ExitCode::InvalidArguments,
             ErrorKind::CannotPinPackage { .. } => ExitCode::InvalidArguments,
+            ErrorKind::ChecksumMismatch { .. } => ExitCode::NetworkError,
+            ErrorKind::ChecksumNotFound { .. } => ExitCode::NetworkError,
             ErrorKind::CompletionsOutFileError { .. } => ExitCode::InvalidArguments,
             ErrorKind::ContainingDirError { .. } => ExitCode::FileSystemError,
             ErrorKind::CouldNotDetermineTool => ExitCode::UnknownError,
@@ -1404,7 +1934,9 @@ impl ErrorKind {
             ErrorKind::DeleteDirectoryError { .. } => ExitCode::FileSystemError,
             ErrorKind::DeleteFileError { .. } => ExitCode::FileSystemError,
             ErrorKind::DeprecatedCommandError { .. } => ExitCode::InvalidArguments,
+            ErrorKind::DownloadChecksumsError { .. } => ExitCode::NetworkError,
             ErrorKind::DownloadToolNetworkError { .. } => ExitCode::NetworkError,
+            ErrorKind::EnginesMismatch { .. } => ExitCode::EnginesMismatch,
             ErrorKind::ExecuteHookError { .. } => ExitCode::ExecutionFailure,
             ErrorKind::ExtensionCycleError { .. } => ExitCode::ConfigurationError,
             ErrorKind::ExtensionPathError { .. } => ExitCode::FileSystemError,
@@ -1413,13 +1945,18 @@ impl ErrorKind {
             ErrorKind::HookNoFieldsSpecified => ExitCode::ConfigurationError,
             ErrorKind::HookPathError { .. } => ExitCode::ConfigurationError,
             ErrorKind::InstalledPackageNameError => ExitCode::UnknownError,
+            ErrorKind::IntegrityNotFound { .. } => ExitCode::NetworkError,
+            ErrorKind::InvalidConfigValue { .. } => ExitCode::InvalidArguments,
             ErrorKind::InvalidHookCommand { .. } => ExitCode::ExecutableNotFound,
             ErrorKind::InvalidHookOutput { .. } => ExitCode::ExecutionFailure,
             ErrorKind::InvalidInvocation { .. } => ExitCode::InvalidArguments,
             ErrorKind::InvalidInvocationOfBareVersion { .. } => ExitCode::InvalidArguments,
+            ErrorKind::InvalidSignatureVerifier { .. } => ExitCode::EnvironmentError,
             ErrorKind::InvalidToolName { .. } => ExitCode::InvalidArguments,
             ErrorKind::LockAcquireError => ExitCode::FileSystemError,
+            ErrorKind::MissingMinisignPublicKey => ExitCode::EnvironmentError,
             ErrorKind::NoBundledNpm { .. } => ExitCode::ConfigurationError,
+            ErrorKind::NoCommandLinePnpm => ExitCode::ConfigurationError,
             ErrorKind::NoCommandLineYarn => ExitCode::ConfigurationError,
             ErrorKind::NoDefaultNodeVersion { .. } => ExitCode::ConfigurationError,
             ErrorKind::NodeVersionNotFound { .. } => ExitCode::NoVersionMatch,
@@ -1429,14 +1966,18 @@ impl ErrorKind {
             ErrorKind::NoPinnedNodeVersion { .. } => ExitCode::ConfigurationError,
             ErrorKind::NoPlatform => ExitCode::ConfigurationError,
             ErrorKind::NoProjectNodeInManifest => ExitCode::ConfigurationError,
+            ErrorKind::NoProjectPnpm => ExitCode::ConfigurationError,
             ErrorKind::NoProjectYarn => ExitCode::ConfigurationError,
             ErrorKind::NoShellProfile { .. } => ExitCode::EnvironmentError,
             ErrorKind::NotInPackage => ExitCode::ConfigurationError,
+            ErrorKind::NoDefaultPnpm => ExitCode::ConfigurationError,
             ErrorKind::NoDefaultYarn => ExitCode::ConfigurationError,
             ErrorKind::NpmLinkMissingPackage { .. } => ExitCode::ConfigurationError,
             ErrorKind::NpmLinkWrongManager { .. } => ExitCode::ConfigurationError,
             ErrorKind::NpmVersionNotFound { .. } => ExitCode::NoVersionMatch,
             ErrorKind::NpxNotAvailable { .. } => ExitCode::ExecutableNotFound,
+            ErrorKind::OfflineFetchError { .. } => ExitCode::NetworkError,
+            ErrorKind::OfflineVersionNotFound { .. } => ExitCode::NoVersionMatch,
             ErrorKind::PackageInstallFailed { .. } => ExitCode::UnknownError,
             ErrorKind::PackageManifestParseError { .. } => ExitCode::ConfigurationError,
             ErrorKind::PackageManifestReadError { .. } => ExitCode::FileSystemError,
@@ -1446,51 +1987,75 @@ impl ErrorKind {
             ErrorKind::PackageUnpackError => ExitCode::ConfigurationError,
             ErrorKind::PackageWriteError { .. } => ExitCode::FileSystemError,
             ErrorKind::ParseBinConfigError => ExitCode::UnknownError,
+            ErrorKind::ParseCertificatesError { .. } => ExitCode::ConfigurationError,
+            ErrorKind::ParseConfigError { .. } => ExitCode::ConfigurationError,
             ErrorKind::ParseHooksError { .. } => ExitCode::ConfigurationError,
             ErrorKind::ParseToolSpecError { .. } => ExitCode::InvalidArguments,
             ErrorKind::ParseNodeIndexCacheError => ExitCode::UnknownError,
             ErrorKind::ParseNodeIndexError { .. } => ExitCode::NetworkError,
             ErrorKind::ParseNodeIndexExpiryError => ExitCode::UnknownError,
+            ErrorKind::ParseNodeVersionFileError { .. } => ExitCode::ConfigurationError,
             ErrorKind::ParseNpmManifestError => ExitCode::UnknownError,
             ErrorKind::ParsePackageConfigError => ExitCode::UnknownError,
             ErrorKind::ParsePlatformError => ExitCode::ConfigurationError,
+            ErrorKind::ParseToolchainExportError { .. } => ExitCode::ConfigurationError,
             ErrorKind::PersistInventoryError { .. } => ExitCode::FileSystemError,
+            ErrorKind::PinEnginesMismatch { .. } => ExitCode::EnginesMismatch,
+            ErrorKind::PnpmVersionNotFound { .. } => ExitCode::NoVersionMatch,
             ErrorKind::ProjectLocalBinaryExecError { .. } => ExitCode::ExecutionFailure,
             ErrorKind::ProjectLocalBinaryNotFound { .. } => ExitCode::FileSystemError,
+            ErrorKind::ProxyConnectError { .. } => ExitCode::NetworkError,
             ErrorKind::PublishHookBothUrlAndBin => ExitCode::ConfigurationError,
             ErrorKind::PublishHookNeitherUrlNorBin => ExitCode::ConfigurationError,
+            ErrorKind::ReadArchiveError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadBinConfigDirError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadBinConfigError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadCertificatesError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadDefaultNpmError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadDirError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadHooksError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNodeIndexCacheError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNodeIndexExpiryError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadNodeVersionFileError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNpmManifestError => ExitCode::UnknownError,
+            ErrorKind::ReadNpmrcError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadPackageConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadPlatformError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadToolchainExportError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadUsageHistoryError { .. } => ExitCode::FileSystemError,
             #[cfg(windows)]
             ErrorKind::ReadUserPathError => ExitCode::EnvironmentError,
             ErrorKind::RegistryFetchError { .. } => ExitCode::NetworkError,
             ErrorKind::RunShimDirectly => ExitCode::InvalidArguments,
             ErrorKind::SetupToolImageError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ShasumsSignatureError { .. } => ExitCode::EnvironmentError,
             ErrorKind::ShimCreateError { .. } => ExitCode::FileSystemError,
             ErrorKind::ShimRemoveError { .. } => ExitCode::FileSystemError,
             ErrorKind::StringifyBinConfigError => ExitCode::UnknownError,
             ErrorKind::StringifyPackageConfigError => ExitCode::UnknownError,
             ErrorKind::StringifyPlatformError => ExitCode::UnknownError,
+            ErrorKind::StringifyRecentProjectsError => ExitCode::UnknownError,
+            ErrorKind::StringifyToolchainExportError => ExitCode::UnknownError,
+            ErrorKind::TlsConnectError { .. } => ExitCode::NetworkError,
             ErrorKind::Unimplemented { .. } => ExitCode::UnknownError,
+            ErrorKind::UninstallVersionInUse { .. } => ExitCode::ConfigurationError,
+            ErrorKind::UninstallVersionRequired { .. } => ExitCode::InvalidArguments,
+            ErrorKind::UnknownConfigKey { .. } => ExitCode::InvalidArguments,
             ErrorKind::UnpackArchiveError { .. } => ExitCode::UnknownError,
             ErrorKind::UpgradePackageNotFound { .. } => ExitCode::ConfigurationError,
             ErrorKind::UpgradePackageWrongManager { .. } => ExitCode::ConfigurationError,
             ErrorKind::VersionParseError { .. } => ExitCode::NoVersionMatch,
             ErrorKind::WriteBinConfigError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WriteConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteDefaultNpmError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WriteIntegrityError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteLauncherError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteNodeIndexCacheError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteNodeIndexExpiryError { .. } => ExitCode::FileSystemError,
             ErrorKind::WritePackageConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::WritePlatformError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WriteRecentProjectsError { .. } => ExitCode::FileSystemError,
             #[cfg(windows)]
             ErrorKind::WriteUserPathError => ExitCode::EnvironmentError,
             ErrorKind::YarnLatestFetchError { .. } => ExitCode::NetworkError,
@@ -116,6 +116,9 @@ pub enum ExitCode {
     /// The command or feature is not yet implemented.
     NotYetImplemented = 9,
 
+    /// A tool version doesn't satisfy the `engines` constraints of the project.
+    EnginesMismatch = 10,
+
     /// The requested executable could not be run.
     ExecutionFailure = 126,
 
@@ -1,19 +1,41 @@
 //! Events for the sessions in executables and shims and everything
 
 use std::env;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use lazy_static::lazy_static;
+use semver::Version;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{ExitCode, VoltaError};
 use crate::hook::Publish;
-use crate::monitor::send_events;
+use crate::monitor::{post_events, send_events};
+use crate::platform::{Platform, Sourced};
 use crate::session::ActivityKind;
+use crate::tool::Spec;
+use crate::usage::{self, UsageRecord};
+
+/// The version of the event format, which is increased whenever the fields of an event change
+///
+/// Events from before the format was versioned don't have a `schema_version`, and are version 1.
+pub const SCHEMA_VERSION: u32 = 2;
+
+lazy_static! {
+    /// Fetch events that haven't been added to the event log yet
+    ///
+    /// Tools can be downloaded on the threads of `tool::prefetch`, which can't reach the session,
+    /// so downloads are recorded here and moved into the log when the next event is added.
+    static ref PENDING_FETCHES: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+}
 
 // the Event data that is serialized to JSON and sent the plugin
 #[derive(Deserialize, Serialize)]
 pub struct Event {
     timestamp: u64,
+    #[serde(default = "unversioned_schema")]
+    pub schema_version: u32,
     pub name: String,
     pub event: EventKind,
 }
@@ -33,6 +55,9 @@ pub enum EventKind {
     Start,
     End {
         exit_code: i32,
+        duration_ms: u64,
+        #[serde(flatten)]
+        context: RunContext,
     },
     Error {
         exit_code: i32,
@@ -41,16 +66,59 @@ pub enum EventKind {
     },
     ToolEnd {
         exit_code: i32,
+        duration_ms: u64,
+        #[serde(flatten)]
+        context: RunContext,
     },
     Args {
         argv: String,
     },
+    Fetch {
+        tool: String,
+        bytes: u64,
+        duration_ms: u64,
+    },
+}
+
+/// What Volta resolved to run the command, which is included in the events that end a session
+#[derive(Deserialize, Serialize, PartialEq, Debug, Default, Clone)]
+pub struct RunContext {
+    /// The tool that was run, if any
+    pub tool: Option<String>,
+    /// The manifest of the project that the command was run in, if any
+    pub manifest: Option<PathBuf>,
+    pub node: Option<ResolvedVersion>,
+    pub npm: Option<ResolvedVersion>,
+    pub pnpm: Option<ResolvedVersion>,
+    pub yarn: Option<ResolvedVersion>,
+}
+
+/// A version of a tool in the platform, along with where it was resolved from
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub struct ResolvedVersion {
+    pub version: String,
+    /// One of `project`, `default`, `binary`, or `command-line`
+    pub source: String,
+}
+
+impl From<&Sourced<Version>> for ResolvedVersion {
+    fn from(sourced: &Sourced<Version>) -> Self {
+        ResolvedVersion {
+            version: sourced.value.to_string(),
+            source: sourced.source.to_string(),
+        }
+    }
+}
+
+fn unversioned_schema() -> u32 {
+    1
 }
 
 impl EventKind {
     pub fn into_event(self, activity_kind: ActivityKind) -> Event {
         Event {
             timestamp: unix_timestamp(),
+            schema_version: SCHEMA_VERSION,
             name: activity_kind.to_string(),
             event: self,
         }
@@ -58,7 +126,7 @@ impl EventKind {
 }
 
 // returns the current number of milliseconds since the epoch
-fn unix_timestamp() -> u64 {
+pub(crate) fn unix_timestamp() -> u64 {
     let start = SystemTime::now();
     let duration = start
         .duration_since(UNIX_EPOCH)
@@ -91,14 +159,52 @@ fn get_error_env() -> ErrorEnv {
     }
 }
 
+/// Record that `tool` was downloaded, adding a fetch event to the log of this process
+pub(crate) fn record_fetch(tool: &Spec, bytes: u64, duration: Duration) {
+    let event = EventKind::Fetch {
+        tool: tool.to_string(),
+        bytes,
+        duration_ms: duration.as_millis() as u64,
+    }
+    .into_event(ActivityKind::Fetch);
+
+    if let Ok(mut pending) = PENDING_FETCHES.lock() {
+        pending.push(event);
+    }
+}
+
 pub struct EventLog {
     events: Vec<Event>,
+    started: Instant,
+    context: RunContext,
 }
 
 impl EventLog {
     /// Constructs a new 'EventLog'
     pub fn init() -> Self {
-        EventLog { events: Vec::new() }
+        EventLog {
+            events: Vec::new(),
+            started: Instant::now(),
+            context: RunContext::default(),
+        }
+    }
+
+    /// Sets the tool that is being run, to be included in the end event
+    pub fn set_tool(&mut self, tool: &str) {
+        self.context.tool = Some(tool.to_string());
+    }
+
+    /// Sets the platform that the tool is run with, to be included in the end event
+    pub fn set_platform(&mut self, platform: &Platform) {
+        self.context.node = Some(ResolvedVersion::from(&platform.node));
+        self.context.npm = platform.npm.as_ref().map(ResolvedVersion::from);
+        self.context.pnpm = platform.pnpm.as_ref().map(ResolvedVersion::from);
+        self.context.yarn = platform.yarn.as_ref().map(ResolvedVersion::from);
+    }
+
+    /// Sets the manifest of the current project, to be included in the end event
+    pub fn set_manifest(&mut self, manifest: Option<&Path>) {
+        self.context.manifest = manifest.map(Path::to_path_buf);
     }
 
     pub fn add_event_start(&mut self, activity_kind: ActivityKind) {
@@ -108,12 +214,21 @@ impl EventLog {
         self.add_event(
             EventKind::End {
                 exit_code: exit_code as i32,
+                duration_ms: self.elapsed_ms(),
+                context: self.context.clone(),
             },
             activity_kind,
         )
     }
     pub fn add_event_tool_end(&mut self, activity_kind: ActivityKind, exit_code: i32) {
-        self.add_event(EventKind::ToolEnd { exit_code }, activity_kind)
+        self.add_event(
+            EventKind::ToolEnd {
+                exit_code,
+                duration_ms: self.elapsed_ms(),
+                context: self.context.clone(),
+            },
+            activity_kind,
+        )
     }
     pub fn add_event_error(&mut self, activity_kind: ActivityKind, error: &VoltaError) {
         self.add_event(
@@ -139,14 +254,43 @@ impl EventLog {
     }
 
     fn add_event(&mut self, event_kind: EventKind, activity_kind: ActivityKind) {
+        // Downloads happened before this event, so they are added first to keep the log in order
+        if let Ok(mut pending) = PENDING_FETCHES.lock() {
+            self.events.append(&mut pending);
+        }
+
         let event = event_kind.into_event(activity_kind);
         self.events.push(event);
     }
 
+    /// Adds the tool that was run to the usage history, if a tool was run
+    pub fn record_usage(&self, exit_code: i32) {
+        if let Some(tool) = &self.context.tool {
+            usage::append(&UsageRecord {
+                timestamp: unix_timestamp(),
+                tool: tool.clone(),
+                node: self.context.node.clone(),
+                project: self
+                    .context
+                    .manifest
+                    .as_deref()
+                    .and_then(Path::parent)
+                    .map(Path::to_path_buf),
+                duration_ms: self.elapsed_ms(),
+                exit_code,
+            });
+        }
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
     pub fn publish(&self, plugin: Option<&Publish>) {
         match plugin {
-            // Note: This call to unimplemented is left in, as it's not a Fallible operation that can use ErrorKind::Unimplemented
-            Some(&Publish::Url(_)) => unimplemented!(),
+            Some(&Publish::Url(ref url)) => {
+                post_events(url, &self.events);
+            }
             Some(&Publish::Bin(ref command)) => {
                 send_events(command, &self.events);
             }
@@ -158,10 +302,14 @@ impl EventLog {
 #[cfg(test)]
 pub mod tests {
 
-    use super::{EventKind, EventLog};
+    use std::path::Path;
+
+    use super::{Event, EventKind, EventLog, ResolvedVersion, SCHEMA_VERSION};
     use crate::error::{ErrorKind, ExitCode};
+    use crate::platform::{Platform, Sourced};
     use crate::session::ActivityKind;
     use regex::Regex;
+    use semver::Version;
 
     #[test]
     fn test_adding_events() {
@@ -176,15 +324,24 @@ pub mod tests {
         event_log.add_event_end(ActivityKind::Pin, ExitCode::NetworkError);
         assert_eq!(event_log.events.len(), 2);
         assert_eq!(event_log.events[1].name, "pin");
-        assert_eq!(event_log.events[1].event, EventKind::End { exit_code: 5 });
+        match event_log.events[1].event {
+            EventKind::End { exit_code, .. } => assert_eq!(exit_code, 5),
+            _ => panic!(
+                "Expected EventKind::End, Got: {:?}",
+                event_log.events[1].event
+            ),
+        }
 
         event_log.add_event_tool_end(ActivityKind::Version, 12);
         assert_eq!(event_log.events.len(), 3);
         assert_eq!(event_log.events[2].name, "version");
-        assert_eq!(
-            event_log.events[2].event,
-            EventKind::ToolEnd { exit_code: 12 }
-        );
+        match event_log.events[2].event {
+            EventKind::ToolEnd { exit_code, .. } => assert_eq!(exit_code, 12),
+            _ => panic!(
+                "Expected EventKind::ToolEnd, Got: {:?}",
+                event_log.events[2].event
+            ),
+        }
 
         let error = ErrorKind::BinaryExecError.into();
         event_log.add_event_error(ActivityKind::Install, &error);
@@ -208,4 +365,58 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_end_events_include_context() {
+        let mut event_log = EventLog::init();
+        let platform = Platform {
+            node: Sourced::with_project(Version::parse("16.18.0").unwrap()),
+            npm: None,
+            pnpm: None,
+            yarn: Some(Sourced::with_default(Version::parse("1.22.19").unwrap())),
+        };
+        event_log.set_tool("yarn");
+        event_log.set_platform(&platform);
+        event_log.set_manifest(Some(Path::new("/project/package.json")));
+
+        event_log.add_event_tool_end(ActivityKind::Yarn, 0);
+        match &event_log.events[0].event {
+            EventKind::ToolEnd { context, .. } => {
+                assert_eq!(context.tool.as_deref(), Some("yarn"));
+                assert_eq!(
+                    context.manifest.as_deref(),
+                    Some(Path::new("/project/package.json"))
+                );
+                assert_eq!(
+                    context.node,
+                    Some(ResolvedVersion {
+                        version: "16.18.0".into(),
+                        source: "project".into(),
+                    })
+                );
+                assert_eq!(context.npm, None);
+                assert_eq!(
+                    context.yarn,
+                    Some(ResolvedVersion {
+                        version: "1.22.19".into(),
+                        source: "default".into(),
+                    })
+                );
+            }
+            event => panic!("Expected EventKind::ToolEnd, Got: {:?}", event),
+        }
+
+        let json = serde_json::to_value(&event_log.events[0]).unwrap();
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["event"]["toolend"]["tool"], "yarn");
+        assert_eq!(json["event"]["toolend"]["node"]["source"], "project");
+    }
+
+    #[test]
+    fn test_unversioned_events_are_version_1() {
+        let event: Event =
+            serde_json::from_str(r#"{"timestamp":1,"name":"pin","event":"start"}"#).unwrap();
+        assert_eq!(event.schema_version, 1);
+        assert_eq!(event.event, EventKind::Start);
+    }
 }
@@ -11,6 +11,7 @@ use crate::layout::volta_home;
 use retry::delay::Fibonacci;
 use retry::{retry, Error as RetryError, OperationResult};
 use tempfile::{tempdir_in, NamedTempFile, TempDir};
+use walkdir::WalkDir;
 
 /// Opens a file, creating it if it doesn't exist
 pub fn touch(path: &Path) -> io::Result<File> {
@@ -102,6 +103,20 @@ where
         .collect::<Vec<T>>())
 }
 
+/// Calculates the total size, in bytes, of the files at or within the given path
+///
+/// Symlinks are not followed and any entries that can't be read are skipped, so this is a
+/// best-effort estimate. A path that doesn't exist has a size of 0.
+pub fn disk_usage(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(Metadata::is_file)
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
 /// Creates a NamedTempFile in the Volta tmp directory
 pub fn create_staging_file() -> Fallible<NamedTempFile> {
     let tmp_dir = volta_home()?.tmp_dir();
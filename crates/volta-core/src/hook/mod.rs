@@ -9,7 +9,7 @@ use std::path::Path;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::layout::volta_home;
 use crate::project::Project;
-use crate::tool::{Node, Npm, Tool, Yarn};
+use crate::tool::{Node, Npm, Pnpm, Tool, Yarn};
 use lazycell::LazyCell;
 use log::debug;
 
@@ -50,6 +50,7 @@ impl LazyHookConfig {
 pub struct HookConfig {
     node: Option<ToolHooks<Node>>,
     npm: Option<ToolHooks<Npm>>,
+    pnpm: Option<ToolHooks<Pnpm>>,
     yarn: Option<ToolHooks<Yarn>>,
     events: Option<EventHooks>,
 }
@@ -97,6 +98,10 @@ impl HookConfig {
         self.npm.as_ref()
     }
 
+    pub fn pnpm(&self) -> Option<&ToolHooks<Pnpm>> {
+        self.pnpm.as_ref()
+    }
+
     pub fn yarn(&self) -> Option<&ToolHooks<Yarn>> {
         self.yarn.as_ref()
     }
@@ -161,6 +166,7 @@ impl HookConfig {
                     Self {
                         node: None,
                         npm: None,
+                        pnpm: None,
                         yarn: None,
                         events: None,
                     }
@@ -193,6 +199,7 @@ impl HookConfig {
         Self {
             node: merge_hooks!(self, other, node),
             npm: merge_hooks!(self, other, npm),
+            pnpm: merge_hooks!(self, other, pnpm),
             yarn: merge_hooks!(self, other, yarn),
             events: merge_hooks!(self, other, events),
         }
@@ -8,7 +8,7 @@ use std::path::Path;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::read_dir_eager;
 use crate::layout::volta_home;
-use crate::tool::PackageConfig;
+use crate::tool::{node, PackageConfig};
 use crate::version::parse_version;
 use log::debug;
 use semver::Version;
@@ -16,18 +16,30 @@ use walkdir::WalkDir;
 
 /// Checks if a given Node version image is available on the local machine
 pub fn node_available(version: &Version) -> Fallible<bool> {
-    volta_home().map(|home| {
-        home.node_image_root_dir()
-            .join(version.to_string())
-            .exists()
-    })
+    volta_home().map(|home| node::image_dir(home, version).exists())
 }
 
 /// Collects a set of all Node versions fetched on the local machine
+///
+/// Builds from the prerelease channels (e.g. `node@nightly`) aren't included, so that they never
+/// satisfy a semver range.
 pub fn node_versions() -> Fallible<BTreeSet<Version>> {
     volta_home().and_then(|home| read_versions(home.node_image_root_dir()))
 }
 
+/// Collects a set of all Node builds fetched from the prerelease channels on the local machine
+pub fn node_channel_versions() -> Fallible<BTreeSet<Version>> {
+    volta_home().and_then(|home| {
+        // Volta directories created before channel support won't have the channel image directory
+        let dir = home.node_channel_image_root_dir();
+        if dir.exists() {
+            read_versions(dir)
+        } else {
+            Ok(BTreeSet::new())
+        }
+    })
+}
+
 /// Checks if a given npm version image is available on the local machine
 pub fn npm_available(version: &Version) -> Fallible<bool> {
     volta_home().map(|home| home.npm_image_dir(&version.to_string()).exists())
@@ -38,6 +50,24 @@ pub fn npm_versions() -> Fallible<BTreeSet<Version>> {
     volta_home().and_then(|home| read_versions(home.npm_image_root_dir()))
 }
 
+/// Checks if a given pnpm version image is available on the local machine
+pub fn pnpm_available(version: &Version) -> Fallible<bool> {
+    volta_home().map(|home| home.pnpm_image_dir(&version.to_string()).exists())
+}
+
+/// Collects a set of all pnpm versions fetched on the local machine
+pub fn pnpm_versions() -> Fallible<BTreeSet<Version>> {
+    volta_home().and_then(|home| {
+        // Volta directories created before pnpm support won't have the pnpm image directory yet
+        let dir = home.pnpm_image_root_dir();
+        if dir.exists() {
+            read_versions(dir)
+        } else {
+            Ok(BTreeSet::new())
+        }
+    })
+}
+
 /// Checks if a given Yarn version image is available on the local machine
 pub fn yarn_available(version: &Version) -> Fallible<bool> {
     volta_home().map(|home| home.yarn_image_dir(&version.to_string()).exists())
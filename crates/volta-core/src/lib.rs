@@ -1,6 +1,7 @@
 //! The main implementation crate for the core of Volta.
 
 mod command;
+pub mod config;
 pub mod error;
 pub mod event;
 pub mod fs;
@@ -9,7 +10,9 @@ pub mod inventory;
 pub mod layout;
 pub mod log;
 pub mod monitor;
+pub mod offline;
 pub mod platform;
+pub mod policy;
 pub mod project;
 pub mod run;
 pub mod session;
@@ -19,4 +22,5 @@ pub mod style;
 pub mod sync;
 pub mod tool;
 pub mod toolchain;
+pub mod usage;
 pub mod version;
@@ -7,6 +7,7 @@ use std::fmt::Display;
 use textwrap::word_splitters::NoHyphenation;
 use textwrap::{fill, Options};
 
+use crate::config;
 use crate::style::text_width;
 
 const ERROR_PREFIX: &str = "error:";
@@ -139,13 +140,15 @@ where
 
 /// Determines the correct logging level based on the environment
 /// If VOLTA_LOGLEVEL is set to a valid level, we use that
-/// If not, we check the current stdout to determine whether it is a TTY or not
+/// If not, we use the `logLevel` setting from the configuration files, if any
+/// If neither is set, we check the current stdout to determine whether it is a TTY or not
 ///     If it is a TTY, we use Info
 ///     If it is NOT a TTY, we use Error as we don't want to show warnings when running as a script
 fn level_from_env() -> LevelFilter {
     env::var(VOLTA_LOGLEVEL)
         .ok()
         .and_then(|level| level.to_uppercase().parse().ok())
+        .or_else(config::log_level)
         .unwrap_or_else(|| {
             if atty::is(Stream::Stdout) {
                 LevelFilter::Info
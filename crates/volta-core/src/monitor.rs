@@ -2,12 +2,30 @@ use std::env;
 use std::io::Write;
 use std::path::PathBuf;
 use std::process::{Child, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
+use attohttpc::header::CONTENT_TYPE;
 use log::debug;
 use tempfile::NamedTempFile;
 
 use crate::command::create_command;
 use crate::event::Event;
+use crate::offline::is_offline;
+use crate::tool::http;
+
+/// How long publishing events to a URL may delay the exit of Volta
+///
+/// This is kept short, since it is added to every run of a tool when the server is slow. A request
+/// that hasn't finished by then is abandoned when Volta exits.
+const POST_WAIT: Duration = Duration::from_millis(50);
+
+/// How long the request publishing events may take on its own thread
+///
+/// This is long enough for a real server to answer, so the request isn't cut short by the client
+/// while it is still allowed to finish.
+const POST_REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Send event to the spawned command process
 // if hook command is not configured, this is not called
@@ -30,6 +48,57 @@ pub fn send_events(command: &str, events: &[Event]) {
     }
 }
 
+/// POST the events as JSON to the configured URL
+///
+/// The request is made on a separate thread, and Volta stops waiting for it after a short timeout,
+/// so a slow or unreachable server can't hold up the tool that was run. Nothing is published in
+/// offline mode.
+// if hook url is not configured, this is not called
+pub fn post_events(url: &str, events: &[Event]) {
+    if is_offline() {
+        debug!("Offline mode prevented publishing events to '{}'", url);
+        return;
+    }
+
+    let events_json = match serde_json::to_vec(&events) {
+        Ok(events_json) => events_json,
+        Err(error) => {
+            debug!("Could not serialize events data to JSON: {:?}", error);
+            return;
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let request_url = url.to_string();
+    thread::spawn(move || {
+        // The receiver is gone if the request took too long, which was already reported
+        let _ = sender.send(post_json(&request_url, events_json));
+    });
+
+    match receiver.recv_timeout(POST_WAIT) {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => debug!("Could not publish events to '{}': {}", url, error),
+        Err(_) => debug!("Timed out publishing events to '{}'", url),
+    }
+}
+
+fn post_json(url: &str, body: Vec<u8>) -> Result<(), String> {
+    let client = http::client().map_err(|error| error.to_string())?;
+    let response = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .timeout(POST_REQUEST_TIMEOUT)
+        .bytes(body)
+        .send()
+        .map_err(|error| error.to_string())?;
+
+    if response.is_success() {
+        Ok(())
+    } else {
+        Err(format!("server responded with {}", response.status()))
+    }
+}
+
 // Write the events JSON to a file in the temporary directory
 fn write_events_file(events_json: String) -> Option<PathBuf> {
     match NamedTempFile::new() {
@@ -0,0 +1,117 @@
+//! Provides offline mode, in which versions are resolved only from the local inventory and
+//! Volta never accesses the network.
+
+use std::collections::BTreeSet;
+use std::env;
+
+use crate::config;
+use crate::error::{ErrorKind, Fallible};
+use crate::version::{VersionSpec, VersionTag};
+use log::debug;
+use semver::Version;
+
+/// Environment variable that enables offline mode, also set by the `--offline` flag
+pub const OFFLINE_VAR: &str = "VOLTA_OFFLINE";
+
+/// Determine whether Volta is in offline mode, either from the environment or the `offline`
+/// setting
+pub fn is_offline() -> bool {
+    env::var_os(OFFLINE_VAR).is_some() || config::offline()
+}
+
+/// Check that a request to `url` is allowed, failing if Volta is in offline mode
+pub(crate) fn ensure_online(url: &str) -> Fallible<()> {
+    if is_offline() {
+        debug!("Offline mode prevented a request to {}", url);
+        Err(ErrorKind::OfflineFetchError {
+            from_url: url.into(),
+        }
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Resolve a version of `tool` from its `fetched` versions
+///
+/// Exact versions are returned as they are, so that a version that hasn't been fetched fails when
+/// it is fetched rather than here. Tags other than `latest` are only known to the registry, so
+/// they can't be resolved offline.
+pub(crate) fn resolve_fetched(
+    tool: &str,
+    matching: VersionSpec,
+    fetched: BTreeSet<Version>,
+) -> Fallible<Version> {
+    let (found, description) = match matching {
+        VersionSpec::Exact(version) => return Ok(version),
+        VersionSpec::Semver(requirement) => (
+            fetched
+                .into_iter()
+                .rev()
+                .find(|version| requirement.matches(version)),
+            requirement.to_string(),
+        ),
+        VersionSpec::None | VersionSpec::Tag(VersionTag::Latest) => {
+            (fetched.into_iter().next_back(), "latest".into())
+        }
+        VersionSpec::Tag(tag) => (None, tag.to_string()),
+    };
+
+    match found {
+        Some(version) => {
+            debug!(
+                "Found fetched {}@{} matching '{}'",
+                tool, version, description
+            );
+            Ok(version)
+        }
+        None => Err(ErrorKind::OfflineVersionNotFound {
+            tool: tool.into(),
+            matching: description,
+        }
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fetched() -> BTreeSet<Version> {
+        ["1.22.4", "1.22.10", "3.1.0"]
+            .iter()
+            .map(|version| version.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn resolves_semver_from_fetched() {
+        let matching = "^1.22".parse().unwrap();
+
+        assert_eq!(
+            resolve_fetched("yarn", matching, fetched()).unwrap(),
+            "1.22.10".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn resolves_latest_to_newest_fetched() {
+        assert_eq!(
+            resolve_fetched("yarn", VersionSpec::None, fetched()).unwrap(),
+            "3.1.0".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn fails_without_match() {
+        let matching = "^2".parse().unwrap();
+
+        assert!(resolve_fetched("yarn", matching, fetched()).is_err());
+        assert!(resolve_fetched(
+            "yarn",
+            VersionSpec::Tag(VersionTag::Custom("berry".into())),
+            fetched()
+        )
+        .is_err());
+    }
+}
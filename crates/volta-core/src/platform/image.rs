@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use super::{build_path_error, Sourced};
 use crate::error::{Context, Fallible};
 use crate::layout::volta_home;
-use crate::tool::load_default_npm_version;
+use crate::tool::{load_default_npm_version, node};
 use semver::Version;
 
 /// A platform image.
@@ -13,6 +13,8 @@ pub struct Image {
     pub node: Sourced<Version>,
     /// The custom version of npm, if any. `None` represents using the npm that is bundled with Node
     pub npm: Option<Sourced<Version>>,
+    /// The pinned version of pnpm, if any.
+    pub pnpm: Option<Sourced<Version>>,
     /// The pinned version of Yarn, if any.
     pub yarn: Option<Sourced<Version>>,
 }
@@ -20,26 +22,30 @@ pub struct Image {
 impl Image {
     fn bins(&self) -> Fallible<Vec<PathBuf>> {
         let home = volta_home()?;
-        let mut bins = Vec::with_capacity(3);
+        let mut bins = Vec::with_capacity(4);
 
         if let Some(npm) = &self.npm {
             let npm_str = npm.value.to_string();
             bins.push(home.npm_image_bin_dir(&npm_str));
         }
 
+        if let Some(pnpm) = &self.pnpm {
+            let pnpm_str = pnpm.value.to_string();
+            bins.push(home.pnpm_image_bin_dir(&pnpm_str));
+        }
+
         if let Some(yarn) = &self.yarn {
             let yarn_str = yarn.value.to_string();
             bins.push(home.yarn_image_bin_dir(&yarn_str));
         }
 
         // Add Node path to the bins last, so that any custom version of npm will be earlier in the PATH
-        let node_str = self.node.value.to_string();
-        bins.push(home.node_image_bin_dir(&node_str));
+        bins.push(node::image_bin_dir(home, &self.node.value));
         Ok(bins)
     }
 
     /// Produces a modified version of the current `PATH` environment variable that
-    /// will find toolchain executables (Node, Yarn) in the installation directories
+    /// will find toolchain executables (Node, pnpm, Yarn) in the installation directories
     /// for the given versions instead of in the Volta shim directory.
     pub fn path(&self) -> Fallible<OsString> {
         let old_path = envoy::path().unwrap_or_else(|| envoy::Var::from(""));
@@ -1,8 +1,12 @@
+use std::env;
 use std::fmt;
 
 use crate::error::{ErrorKind, Fallible};
+use crate::project::Project;
 use crate::session::Session;
-use crate::tool::{Node, Npm, Yarn};
+use crate::style::tool_version;
+use crate::tool::{load_default_npm_version, Node, Npm, Pnpm, Yarn};
+use log::warn;
 use semver::Version;
 
 mod image;
@@ -15,6 +19,9 @@ mod tests;
 pub use image::Image;
 pub use system::System;
 
+/// Environment variable that turns `engines` mismatches into errors instead of warnings
+const STRICT_ENGINES_VAR: &str = "VOLTA_STRICT_ENGINES";
+
 /// The source with which a version is associated
 #[derive(Clone, Copy)]
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
@@ -161,6 +168,7 @@ impl<T> Default for InheritOption<T> {
 pub struct PlatformSpec {
     pub node: Version,
     pub npm: Option<Version>,
+    pub pnpm: Option<Version>,
     pub yarn: Option<Version>,
 }
 
@@ -170,6 +178,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_default(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_default),
+            pnpm: self.pnpm.clone().map(Sourced::with_default),
             yarn: self.yarn.clone().map(Sourced::with_default),
         }
     }
@@ -179,6 +188,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_project(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_project),
+            pnpm: self.pnpm.clone().map(Sourced::with_project),
             yarn: self.yarn.clone().map(Sourced::with_project),
         }
     }
@@ -188,6 +198,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_binary(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_binary),
+            pnpm: self.pnpm.clone().map(Sourced::with_binary),
             yarn: self.yarn.clone().map(Sourced::with_binary),
         }
     }
@@ -198,6 +209,7 @@ impl PlatformSpec {
 pub struct CliPlatform {
     pub node: Option<Version>,
     pub npm: InheritOption<Version>,
+    pub pnpm: InheritOption<Version>,
     pub yarn: InheritOption<Version>,
 }
 
@@ -207,6 +219,7 @@ impl CliPlatform {
         Platform {
             node: self.node.map_or(base.node, Sourced::with_command_line),
             npm: self.npm.map(Sourced::with_command_line).inherit(base.npm),
+            pnpm: self.pnpm.map(Sourced::with_command_line).inherit(base.pnpm),
             yarn: self.yarn.map(Sourced::with_command_line).inherit(base.yarn),
         }
     }
@@ -220,6 +233,7 @@ impl From<CliPlatform> for Option<Platform> {
             Some(node) => Some(Platform {
                 node: Sourced::with_command_line(node),
                 npm: base.npm.map(Sourced::with_command_line).into(),
+                pnpm: base.pnpm.map(Sourced::with_command_line).into(),
                 yarn: base.yarn.map(Sourced::with_command_line).into(),
             }),
         }
@@ -231,6 +245,7 @@ impl From<CliPlatform> for Option<Platform> {
 pub struct Platform {
     pub node: Sourced<Version>,
     pub npm: Option<Sourced<Version>>,
+    pub pnpm: Option<Sourced<Version>>,
     pub yarn: Option<Sourced<Version>>,
 }
 
@@ -242,9 +257,29 @@ impl Platform {
     /// - If it exists and has a Yarn version, then we use the project platform
     /// - If it exists but doesn't have a Yarn version, then we merge the two,
     ///   pulling Yarn from the user default platform, if available
+    /// - The same inheritance applies to pnpm
     /// - If there is no Project platform, then we use the user Default Platform
+    ///
+    /// The active platform is then checked against the `engines` of the project, if any.
     pub fn current(session: &mut Session) -> Fallible<Option<Self>> {
+        let platform = Self::current_unchecked(session)?;
+
+        if let (Some(project), Some(platform)) = (session.project()?, &platform) {
+            platform.check_engines(project)?;
+        }
+
+        Ok(platform)
+    }
+
+    fn current_unchecked(session: &mut Session) -> Fallible<Option<Self>> {
         if let Some(mut platform) = session.project_platform()?.map(PlatformSpec::as_project) {
+            if platform.pnpm.is_none() {
+                platform.pnpm = session
+                    .default_platform()?
+                    .and_then(|default_platform| default_platform.pnpm.clone())
+                    .map(Sourced::with_default);
+            }
+
             if platform.yarn.is_none() {
                 platform.yarn = session
                     .default_platform()?
@@ -258,6 +293,53 @@ impl Platform {
         }
     }
 
+    /// Compares each tool version against the `engines` of the project
+    ///
+    /// A mismatch is a warning, unless the user has opted in to strict checking.
+    fn check_engines(&self, project: &Project) -> Fallible<()> {
+        let npm = match &self.npm {
+            Some(npm) => Some(npm.value.clone()),
+            // The bundled npm version is only known once Node has been fetched
+            None if project.has_engines("npm") => load_default_npm_version(&self.node.value).ok(),
+            None => None,
+        };
+        let versions = [
+            ("node", Some(&self.node.value)),
+            ("npm", npm.as_ref()),
+            ("pnpm", self.pnpm.as_ref().map(|pnpm| &pnpm.value)),
+            ("yarn", self.yarn.as_ref().map(|yarn| &yarn.value)),
+        ];
+        let strict = env::var_os(STRICT_ENGINES_VAR).is_some();
+
+        for &(tool, version) in versions.iter() {
+            let version = match version {
+                Some(version) => version,
+                None => continue,
+            };
+
+            match project.engines_violation(tool, version) {
+                Some((file, range)) if strict => {
+                    return Err(ErrorKind::EnginesMismatch {
+                        tool: tool.into(),
+                        version: version.to_string(),
+                        range: range.into(),
+                        file: file.to_owned(),
+                    }
+                    .into());
+                }
+                Some((file, range)) => warn!(
+                    "{} does not satisfy the engines constraint \"{}\" in {}",
+                    tool_version(tool, version),
+                    range,
+                    file.display()
+                ),
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
     /// Check out a `Platform` into a fully-realized `Image`
     ///
     /// This will ensure that all necessary tools are fetched and available for execution
@@ -268,6 +350,10 @@ impl Platform {
             Npm::new(version.clone()).ensure_fetched(session)?;
         }
 
+        if let Some(Sourced { value: version, .. }) = &self.pnpm {
+            Pnpm::new(version.clone()).ensure_fetched(session)?;
+        }
+
         if let Some(Sourced { value: version, .. }) = &self.yarn {
             Yarn::new(version.clone()).ensure_fetched(session)?;
         }
@@ -275,6 +361,7 @@ impl Platform {
         Ok(Image {
             node: self.node,
             npm: self.npm,
+            pnpm: self.pnpm,
             yarn: self.yarn,
         })
     }
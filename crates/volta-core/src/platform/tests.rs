@@ -28,16 +28,21 @@ fn test_image_path() {
     let npm_bin = volta_home().unwrap().npm_image_bin_dir("6.4.3");
     let expected_npm_bin = npm_bin.to_str().unwrap();
 
+    let pnpm_bin = volta_home().unwrap().pnpm_image_bin_dir("7.9.0");
+    let expected_pnpm_bin = pnpm_bin.to_str().unwrap();
+
     let yarn_bin = volta_home().unwrap().yarn_image_bin_dir("4.5.7");
     let expected_yarn_bin = yarn_bin.to_str().unwrap();
 
     let v123 = Version::parse("1.2.3").unwrap();
     let v457 = Version::parse("4.5.7").unwrap();
     let v643 = Version::parse("6.4.3").unwrap();
+    let v790 = Version::parse("7.9.0").unwrap();
 
     let only_node = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: None,
     };
 
@@ -49,6 +54,7 @@ fn test_image_path() {
     let node_npm = Image {
         node: Sourced::with_default(v123.clone()),
         npm: Some(Sourced::with_default(v643.clone())),
+        pnpm: None,
         yarn: None,
     };
 
@@ -63,6 +69,7 @@ fn test_image_path() {
     let node_yarn = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457.clone())),
     };
 
@@ -74,17 +81,37 @@ fn test_image_path() {
         )
     );
 
-    let node_npm_yarn = Image {
+    let node_pnpm = Image {
+        node: Sourced::with_default(v123.clone()),
+        npm: None,
+        pnpm: Some(Sourced::with_default(v790.clone())),
+        yarn: None,
+    };
+
+    assert_eq!(
+        node_pnpm.path().unwrap().into_string().unwrap(),
+        format!(
+            "{}:{}:{}",
+            expected_pnpm_bin, expected_node_bin, starting_path
+        )
+    );
+
+    let node_npm_pnpm_yarn = Image {
         node: Sourced::with_default(v123),
         npm: Some(Sourced::with_default(v643)),
+        pnpm: Some(Sourced::with_default(v790)),
         yarn: Some(Sourced::with_default(v457)),
     };
 
     assert_eq!(
-        node_npm_yarn.path().unwrap().into_string().unwrap(),
+        node_npm_pnpm_yarn.path().unwrap().into_string().unwrap(),
         format!(
-            "{}:{}:{}:{}",
-            expected_npm_bin, expected_yarn_bin, expected_node_bin, starting_path
+            "{}:{}:{}:{}:{}",
+            expected_npm_bin,
+            expected_pnpm_bin,
+            expected_yarn_bin,
+            expected_node_bin,
+            starting_path
         )
     );
 }
@@ -121,6 +148,7 @@ fn test_image_path() {
     let only_node = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: None,
     };
 
@@ -132,6 +160,7 @@ fn test_image_path() {
     let node_npm = Image {
         node: Sourced::with_default(v123.clone()),
         npm: Some(Sourced::with_default(v643.clone())),
+        pnpm: None,
         yarn: None,
     };
 
@@ -146,6 +175,7 @@ fn test_image_path() {
     let node_yarn = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457.clone())),
     };
 
@@ -160,6 +190,7 @@ fn test_image_path() {
     let node_npm_yarn = Image {
         node: Sourced::with_default(v123),
         npm: Some(Sourced::with_default(v643)),
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457)),
     };
 
@@ -273,6 +304,7 @@ mod cli_platform {
     lazy_static! {
         static ref NODE_VERSION: Version = Version::from((12, 14, 1));
         static ref NPM_VERSION: Version = Version::from((6, 13, 2));
+        static ref PNPM_VERSION: Version = Version::from((7, 9, 5));
         static ref YARN_VERSION: Version = Version::from((1, 17, 0));
     }
 
@@ -285,12 +317,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: None,
             };
 
@@ -305,12 +339,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: None,
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(NODE_VERSION.clone()),
                 npm: None,
+                pnpm: None,
                 yarn: None,
             };
 
@@ -325,12 +361,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Some(NPM_VERSION.clone()),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(Version::from((5, 6, 3)))),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -346,12 +384,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Inherit,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(NPM_VERSION.clone())),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -367,12 +407,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::None,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(NPM_VERSION.clone())),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -386,12 +428,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Some(YARN_VERSION.clone()),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(Version::from((1, 10, 3)))),
             };
 
@@ -407,12 +451,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Inherit,
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(YARN_VERSION.clone())),
             };
 
@@ -428,12 +474,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::None,
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(YARN_VERSION.clone())),
             };
 
@@ -441,6 +489,73 @@ mod cli_platform {
 
             assert!(merged.yarn.is_none());
         }
+
+        #[test]
+        fn uses_pnpm() {
+            let test = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::default(),
+                pnpm: InheritOption::Some(PNPM_VERSION.clone()),
+                yarn: InheritOption::default(),
+            };
+
+            let base = Platform {
+                node: Sourced::with_default(Version::from((10, 10, 10))),
+                npm: None,
+                pnpm: Some(Sourced::with_default(Version::from((6, 34, 0)))),
+                yarn: None,
+            };
+
+            let merged = test.merge(base);
+
+            let merged_pnpm = merged.pnpm.unwrap();
+            assert_eq!(merged_pnpm.value, PNPM_VERSION.clone());
+            assert_eq!(merged_pnpm.source, Source::CommandLine);
+        }
+
+        #[test]
+        fn inherits_pnpm() {
+            let test = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::default(),
+                pnpm: InheritOption::Inherit,
+                yarn: InheritOption::default(),
+            };
+
+            let base = Platform {
+                node: Sourced::with_default(Version::from((10, 10, 10))),
+                npm: None,
+                pnpm: Some(Sourced::with_default(PNPM_VERSION.clone())),
+                yarn: None,
+            };
+
+            let merged = test.merge(base);
+
+            let merged_pnpm = merged.pnpm.unwrap();
+            assert_eq!(merged_pnpm.value, PNPM_VERSION.clone());
+            assert_eq!(merged_pnpm.source, Source::Default);
+        }
+
+        #[test]
+        fn none_does_not_inherit_pnpm() {
+            let test = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::default(),
+                pnpm: InheritOption::None,
+                yarn: InheritOption::default(),
+            };
+
+            let base = Platform {
+                node: Sourced::with_default(Version::from((10, 10, 10))),
+                npm: None,
+                pnpm: Some(Sourced::with_default(PNPM_VERSION.clone())),
+                yarn: None,
+            };
+
+            let merged = test.merge(base);
+
+            assert!(merged.pnpm.is_none());
+        }
     }
 
     mod into_platform {
@@ -452,6 +567,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: None,
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -465,6 +581,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -480,6 +597,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Some(NPM_VERSION.clone()),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -495,6 +613,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::None,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -508,6 +627,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Inherit,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -521,6 +641,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Some(YARN_VERSION.clone()),
             };
 
@@ -536,6 +657,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::None,
             };
 
@@ -549,6 +671,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Inherit,
             };
 
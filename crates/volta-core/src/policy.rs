@@ -0,0 +1,123 @@
+//! Provides the resolution policy, which determines whether version requirements are satisfied
+//! from the local inventory before consulting the index of published versions.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fmt::{self, Display};
+
+use crate::config;
+use crate::error::Fallible;
+use log::debug;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+
+/// Environment variable that selects the `prefer-local` policy, also set by the `--prefer-local`
+/// flag
+pub const PREFER_LOCAL_VAR: &str = "VOLTA_PREFER_LOCAL";
+
+/// The policies for resolving a version requirement, such as `node@16`
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolutionPolicy {
+    /// Always resolve to the newest published version that matches
+    Newest,
+    /// Resolve to the newest fetched version that matches, only consulting the index of published
+    /// versions if none does
+    PreferLocal,
+}
+
+impl ResolutionPolicy {
+    /// Determine the active policy
+    ///
+    /// The environment variable takes precedence over the `resolution` setting.
+    pub fn current() -> Self {
+        if env::var_os(PREFER_LOCAL_VAR).is_some() {
+            ResolutionPolicy::PreferLocal
+        } else {
+            config::resolution().unwrap_or(ResolutionPolicy::Newest)
+        }
+    }
+}
+
+impl Display for ResolutionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ResolutionPolicy::Newest => "newest",
+            ResolutionPolicy::PreferLocal => "prefer-local",
+        })
+    }
+}
+
+/// Find the newest fetched version of `tool` that matches `requirement`, if the active policy
+/// prefers local versions
+///
+/// The inventory is only read when the policy applies, so that resolution under the default
+/// policy doesn't touch the file system.
+pub(crate) fn find_fetched<F>(
+    tool: &str,
+    requirement: &VersionReq,
+    fetched: F,
+) -> Fallible<Option<Version>>
+where
+    F: FnOnce() -> Fallible<BTreeSet<Version>>,
+{
+    if ResolutionPolicy::current() != ResolutionPolicy::PreferLocal {
+        return Ok(None);
+    }
+
+    let found = newest_matching(requirement, fetched()?);
+    match &found {
+        Some(version) => debug!(
+            "Found fetched {}@{} matching requirement '{}'",
+            tool, version, requirement
+        ),
+        None => debug!(
+            "No fetched {} version matches requirement '{}', checking the index",
+            tool, requirement
+        ),
+    }
+
+    Ok(found)
+}
+
+fn newest_matching(requirement: &VersionReq, fetched: BTreeSet<Version>) -> Option<Version> {
+    fetched
+        .into_iter()
+        .rev()
+        .find(|version| requirement.matches(version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_newest_matching() {
+        let fetched = ["16.13.0", "16.14.2", "17.0.1"]
+            .iter()
+            .map(|version| version.parse().unwrap())
+            .collect();
+
+        assert_eq!(
+            newest_matching(&"16".parse().unwrap(), fetched),
+            Some("16.14.2".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn finds_nothing_without_match() {
+        let fetched = ["16.13.0"]
+            .iter()
+            .map(|version| version.parse().unwrap())
+            .collect();
+
+        assert_eq!(newest_matching(&"^14".parse().unwrap(), fetched), None);
+    }
+
+    #[test]
+    fn parses_policy() {
+        let policy: ResolutionPolicy = serde_json::from_str(r#""prefer-local""#).unwrap();
+        assert_eq!(policy, ResolutionPolicy::PreferLocal);
+        assert_eq!(policy.to_string(), "prefer-local");
+    }
+}
@@ -0,0 +1,154 @@
+//! Infers the platform of projects that aren't pinned with Volta from the `engines.node` and
+//! `packageManager` fields of their `package.json`
+
+use std::env;
+use std::path::Path;
+
+use super::PartialPlatform;
+use crate::error::Fallible;
+use crate::hook::ToolHooks;
+use crate::inventory::node_versions;
+use crate::tool::integrity::Integrity;
+use crate::tool::package::PackageManager;
+use crate::tool::{node, Node};
+use crate::version::{parse_requirements, parse_version, VersionSpec};
+use log::{debug, warn};
+use semver::Version;
+
+/// Environment variable that opts in to inferring the platform from `engines` and `packageManager`
+const FALLBACK_VAR: &str = "VOLTA_FEATURE_ENGINES";
+
+pub(super) fn enabled() -> bool {
+    env::var_os(FALLBACK_VAR).is_some()
+}
+
+/// Resolves the `engines.node` range to the newest fetched Node version that satisfies it, or else
+/// the newest matching version in the Node index
+pub(super) fn resolve_engines_node(
+    range: &str,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
+    let requirement = parse_requirements(range)?;
+    let fetched = node_versions()?
+        .into_iter()
+        .rev()
+        .find(|version| requirement.matches(version));
+
+    match fetched {
+        Some(version) => {
+            debug!(
+                "Using fetched Node version {} for engines.node \"{}\"",
+                version, range
+            );
+            Ok(version)
+        }
+        None => node::resolve_with_hooks(VersionSpec::Semver(requirement), hooks),
+    }
+}
+
+/// A package manager pinned by the corepack `packageManager` field, e.g. `yarn@1.22.19+sha256.abc`
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub(super) struct PinnedPackageManager {
+    pub manager: PackageManager,
+    pub version: Version,
+    /// The hash of the package manager tarball, if the field includes one
+    pub integrity: Option<Integrity>,
+}
+
+impl PinnedPackageManager {
+    /// Parse the `packageManager` field, if Volta can pin the package manager it names
+    ///
+    /// Corepack accepts values that Volta can't use, such as other package managers, so those are
+    /// reported with a warning and no package manager is inferred, rather than failing the project.
+    pub fn parse(value: &str, file: &Path) -> Option<Self> {
+        let pinned = Self::try_parse(value);
+        if pinned.is_none() {
+            warn!(
+                "Ignoring \"packageManager\": \"{}\" from {}, Volta only supports npm, pnpm, or Yarn with an exact version and an optional SHA hash",
+                value,
+                file.display()
+            );
+        }
+        pinned
+    }
+
+    fn try_parse(value: &str) -> Option<Self> {
+        let (name, rest) = value.split_once('@')?;
+        let manager = match name {
+            "npm" => PackageManager::Npm,
+            "pnpm" => PackageManager::Pnpm,
+            "yarn" => PackageManager::Yarn,
+            _ => return None,
+        };
+
+        let (version, hash) = match rest.split_once('+') {
+            Some((version, hash)) => (version, Some(hash)),
+            None => (rest, None),
+        };
+        let version = parse_version(version).ok()?;
+        let integrity = match hash {
+            Some(hash) => Some(Integrity::from_package_manager_hash(hash)?),
+            None => None,
+        };
+
+        Some(PinnedPackageManager {
+            manager,
+            version,
+            integrity,
+        })
+    }
+
+    /// Sets the version of this package manager in `platform`
+    pub fn pin(&self, platform: &mut PartialPlatform) {
+        let version = Some(self.version.clone());
+        match self.manager {
+            PackageManager::Npm => platform.npm = version,
+            PackageManager::Pnpm => platform.pnpm = version,
+            PackageManager::Yarn => platform.yarn = version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Option<PinnedPackageManager> {
+        PinnedPackageManager::parse(value, Path::new("package.json"))
+    }
+
+    #[test]
+    fn parse_package_manager() {
+        let pinned = parse("pnpm@7.9.0").unwrap();
+
+        assert_eq!(pinned.manager, PackageManager::Pnpm);
+        assert_eq!(pinned.version, "7.9.0".parse().unwrap());
+        assert_eq!(pinned.integrity, None);
+    }
+
+    #[test]
+    fn parse_package_manager_with_hash() {
+        let pinned = parse("yarn@1.22.19+sha256.deadbeef").unwrap();
+
+        assert_eq!(pinned.manager, PackageManager::Yarn);
+        assert_eq!(pinned.version, "1.22.19".parse().unwrap());
+        assert_eq!(pinned.integrity.unwrap().to_string(), "sha256-3q2+7w==");
+    }
+
+    #[test]
+    fn parse_package_manager_with_corepack_hash() {
+        let pinned = parse("yarn@3.2.4+sha224.deadbeef").unwrap();
+
+        assert_eq!(pinned.manager, PackageManager::Yarn);
+        assert_eq!(pinned.version, "3.2.4".parse().unwrap());
+        assert_eq!(pinned.integrity.unwrap().to_string(), "sha224-3q2+7w==");
+    }
+
+    #[test]
+    fn parse_unsupported_package_manager() {
+        assert_eq!(parse("yarn"), None);
+        assert_eq!(parse("bun@1.0.0"), None);
+        assert_eq!(parse("yarn@^1.22.0"), None);
+        assert_eq!(parse("yarn@1.22.19+md5.deadbeef"), None);
+    }
+}
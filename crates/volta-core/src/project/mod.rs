@@ -1,6 +1,7 @@
 //! Provides the `Project` type, which represents a Node project tree in
 //! the filesystem.
 
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::env;
 use std::ffi::OsStr;
@@ -11,16 +12,26 @@ use lazycell::LazyCell;
 use semver::Version;
 
 use crate::error::{Context, ErrorKind, Fallible, VoltaError};
+use crate::hook::LazyHookConfig;
 use crate::layout::volta_home;
 use crate::platform::PlatformSpec;
-use crate::tool::BinConfig;
+use crate::tool::integrity::Integrity;
+use crate::tool::package::PackageManager;
+use crate::tool::{node, BinConfig};
+use crate::version::{parse_requirements, VersionSpec};
 use chain_map::ChainMap;
 use indexmap::IndexSet;
+use log::debug;
 
+mod fallback;
+mod node_version_file;
+pub mod recent;
 mod serial;
 #[cfg(test)]
 mod tests;
+mod yarnrc;
 
+use fallback::PinnedPackageManager;
 use serial::{update_manifest, Manifest, ManifestKey};
 
 /// A lazily loaded Project
@@ -44,6 +55,11 @@ impl LazyProject {
         let project = self.project.try_borrow_mut_with(Project::for_current_dir)?;
         Ok(project.as_mut())
     }
+
+    /// Returns the project if it has already been loaded, without looking for it
+    pub fn loaded(&self) -> Option<&Project> {
+        self.project.borrow().and_then(Option::as_ref)
+    }
 }
 
 /// A Node project workspace in the filesystem
@@ -53,6 +69,10 @@ pub struct Project {
     workspace_manifests: IndexSet<PathBuf>,
     dependencies: ChainMap<String, String>,
     platform: Option<PlatformSpec>,
+    node_version_file: Option<PathBuf>,
+    package_manager: Option<PinnedPackageManager>,
+    /// The `engines` of each manifest file, starting with the project manifest
+    engines: Vec<(PathBuf, HashMap<String, String>)>,
 }
 
 impl Project {
@@ -66,22 +86,29 @@ impl Project {
     ///
     /// Will search ancestors to find a `package.json` and use that as the root of the project
     fn for_dir(base_dir: PathBuf) -> Fallible<Option<Self>> {
-        match find_closest_root(base_dir) {
+        match find_closest_root(base_dir.clone()) {
             Some(mut project) => {
+                let version_file = node_version_file::find(&base_dir, &project);
                 project.push("package.json");
-                Self::from_file(project).map(Some)
+                Self::from_file(project, version_file).map(Some)
             }
             None => Ok(None),
         }
     }
 
     /// Creates a Project instance from the given package manifest file (`package.json`)
-    fn from_file(manifest_file: PathBuf) -> Fallible<Self> {
+    ///
+    /// If the `volta` settings don't pin a Node version, the version from `version_file` (a
+    /// `.node-version` or `.nvmrc` file) is used instead. When opted in, the `engines.node` and
+    /// `packageManager` fields fill in anything that is still missing.
+    fn from_file(manifest_file: PathBuf, version_file: Option<PathBuf>) -> Fallible<Self> {
         let manifest = Manifest::from_file(&manifest_file)?;
         let mut dependencies: ChainMap<String, String> = manifest.dependency_maps.collect();
         let mut workspace_manifests = IndexSet::new();
         let mut platform = manifest.platform;
         let mut extends = manifest.extends;
+        let mut engines = vec![(manifest_file.clone(), manifest.engines)];
+        let package_manager = manifest.package_manager;
 
         // Iterate the `volta.extends` chain, parsing each file in turn
         while let Some(path) = extends {
@@ -98,6 +125,7 @@ impl Project {
             }
 
             let manifest = Manifest::from_file(&path)?;
+            engines.push((path.clone(), manifest.engines));
             workspace_manifests.insert(path);
             dependencies.extend(manifest.dependency_maps);
 
@@ -110,14 +138,109 @@ impl Project {
             extends = manifest.extends;
         }
 
-        let platform = platform.map(TryInto::try_into).transpose()?;
-
-        Ok(Project {
+        let mut project = Project {
             manifest_file,
             workspace_manifests,
             dependencies,
-            platform,
-        })
+            platform: None,
+            node_version_file: None,
+            package_manager: None,
+            engines,
+        };
+        // Hooks are only loaded if a version needs to be resolved against the Node index
+        let hooks = LazyHookConfig::init();
+
+        let has_node = platform.as_ref().map_or(false, |plat| plat.node.is_some());
+        if let (false, Some(file)) = (has_node, version_file) {
+            let file_platform = PartialPlatform {
+                node: Some(project.resolve_node_version_file(&file, &hooks)?),
+                npm: None,
+                pnpm: None,
+                yarn: None,
+            };
+            platform = Some(match platform {
+                Some(plat) => plat.merge(file_platform),
+                None => file_platform,
+            });
+            project.node_version_file = Some(file);
+        }
+
+        if fallback::enabled() {
+            let engines_node = project.engines[0].1.get("node").cloned();
+            platform = project.infer_platform(platform, engines_node, package_manager, &hooks)?;
+        }
+
+        project.platform = platform.map(TryInto::try_into).transpose()?;
+
+        Ok(project)
+    }
+
+    /// Resolves the Node version requested by a `.node-version` or `.nvmrc` file
+    ///
+    /// Those files commonly contain partial versions or aliases like `lts/*`, which are resolved
+    /// like nvm does, to the newest matching version in the Node index (respecting the project
+    /// hooks). The cached copy of the index is used until it expires. Fetched versions are only
+    /// preferred offline or under the `prefer-local` policy.
+    fn resolve_node_version_file(&self, file: &Path, hooks: &LazyHookConfig) -> Fallible<Version> {
+        let matching = node_version_file::read(file)?;
+        match node::resolve_local(&matching)? {
+            Some(version) => Ok(version),
+            None => node::resolve_with_hooks(matching, hooks.get(Some(self))?.node()),
+        }
+    }
+
+    /// Fills in the tools that aren't pinned any other way from the `engines.node` and
+    /// `packageManager` fields of the manifest
+    ///
+    /// A project platform can't exist without a Node version, so nothing is inferred for a
+    /// project that has no Node version from any source.
+    fn infer_platform(
+        &mut self,
+        platform: Option<PartialPlatform>,
+        engines_node: Option<String>,
+        package_manager: Option<String>,
+        hooks: &LazyHookConfig,
+    ) -> Fallible<Option<PartialPlatform>> {
+        let mut inferred = PartialPlatform {
+            node: None,
+            npm: None,
+            pnpm: None,
+            yarn: None,
+        };
+
+        let has_node = platform.as_ref().map_or(false, |plat| plat.node.is_some());
+        if let (false, Some(range)) = (has_node, engines_node) {
+            let node_hooks = hooks.get(Some(self))?.node();
+            inferred.node = Some(fallback::resolve_engines_node(&range, node_hooks)?);
+        }
+
+        let has_package_manager = platform.as_ref().map_or(false, |plat| {
+            plat.npm.is_some() || plat.pnpm.is_some() || plat.yarn.is_some()
+        });
+        let package_manager = match (has_package_manager, package_manager) {
+            (false, Some(value)) => PinnedPackageManager::parse(&value, &self.manifest_file),
+            _ => None,
+        };
+        if let Some(pinned) = &package_manager {
+            pinned.pin(&mut inferred);
+        }
+
+        match platform {
+            Some(plat) => {
+                self.package_manager = package_manager;
+                Ok(Some(plat.merge(inferred)))
+            }
+            None if inferred.node.is_some() => {
+                self.package_manager = package_manager;
+                Ok(Some(inferred))
+            }
+            None => {
+                if package_manager.is_some() {
+                    debug!("Ignoring packageManager field since the project has no Node version");
+                }
+                Ok(None)
+            }
+        }
     }
 
     /// Returns a reference to the manifest file for the current project
@@ -125,6 +248,16 @@ impl Project {
         &self.manifest_file
     }
 
+    /// Returns the file that supplied the project's Node version
+    ///
+    /// This is the `.node-version` or `.nvmrc` file when the version came from one, and the
+    /// manifest file otherwise.
+    pub fn node_source_file(&self) -> &Path {
+        self.node_version_file
+            .as_deref()
+            .unwrap_or(&self.manifest_file)
+    }
+
     /// Returns an iterator of paths to all of the workspace roots
     pub fn workspace_roots(&self) -> impl Iterator<Item = &Path> {
         // Invariant: self.manifest_file and self.extensions will only contain paths to files that we successfully loaded
@@ -133,11 +266,66 @@ impl Project {
             .map(|file| file.parent().expect("File paths always have a parent"))
     }
 
+    /// Returns the release of Yarn that the project vendors with the `yarnPath` setting of a
+    /// `.yarnrc.yml` file, if any
+    pub fn yarn_path(&self) -> Option<PathBuf> {
+        let root = self
+            .manifest_file
+            .parent()
+            .expect("File paths always have a parent");
+        yarnrc::find_yarn_path(root)
+    }
+
     /// Returns a reference to the Project's `PlatformSpec`, if available
     pub fn platform(&self) -> Option<&PlatformSpec> {
         self.platform.as_ref()
     }
 
+    /// Returns the hash that the `packageManager` field pins for the given package manager
+    /// version, if any
+    pub(crate) fn package_manager_integrity(
+        &self,
+        manager: PackageManager,
+        version: &Version,
+    ) -> Option<&Integrity> {
+        self.package_manager
+            .as_ref()
+            .filter(|pinned| pinned.manager == manager && &pinned.version == version)
+            .and_then(|pinned| pinned.integrity.as_ref())
+    }
+
+    /// Returns true if any of the project manifests constrains `tool` in their `engines`
+    pub fn has_engines(&self, tool: &str) -> bool {
+        self.engines
+            .iter()
+            .any(|(_, engines)| engines.contains_key(tool))
+    }
+
+    /// Returns the first `engines` range for `tool` that `version` doesn't satisfy, along with
+    /// the manifest file that declares it
+    ///
+    /// Ranges that can't be parsed are ignored, since they are only advisory.
+    pub fn engines_violation(&self, tool: &str, version: &Version) -> Option<(&Path, &str)> {
+        self.engines.iter().find_map(|(file, engines)| {
+            let range = engines.get(tool)?;
+            match parse_requirements(range) {
+                Ok(requirement) if !requirement.matches(version) => {
+                    Some((file.as_path(), range.as_str()))
+                }
+                Ok(_) => None,
+                Err(_) => {
+                    debug!(
+                        "Ignoring invalid engines.{} range \"{}\" in {}",
+                        tool,
+                        range,
+                        file.display()
+                    );
+                    None
+                }
+            }
+        })
+    }
+
     /// Returns true if the project dependency map contains the specified dependency
     pub fn has_direct_dependency(&self, dependency: &str) -> bool {
         self.dependencies.contains_key(dependency)
@@ -174,6 +362,7 @@ impl Project {
     /// Pins the Node version in this project's manifest file
     pub fn pin_node(&mut self, version: Version) -> Fallible<()> {
         update_manifest(&self.manifest_file, ManifestKey::Node, Some(&version))?;
+        self.node_version_file = None;
 
         if let Some(platform) = self.platform.as_mut() {
             platform.node = version;
@@ -181,6 +370,7 @@ impl Project {
             self.platform = Some(PlatformSpec {
                 node: version,
                 npm: None,
+                pnpm: None,
                 yarn: None,
             });
         }
@@ -201,6 +391,22 @@ impl Project {
         }
     }
 
+    /// Pins the pnpm version in this project's manifest file
+    pub fn pin_pnpm(&mut self, version: Option<Version>) -> Fallible<()> {
+        if let Some(platform) = self.platform.as_mut() {
+            update_manifest(&self.manifest_file, ManifestKey::Pnpm, version.as_ref())?;
+
+            platform.pnpm = version;
+
+            Ok(())
+        } else {
+            Err(ErrorKind::NoPinnedNodeVersion {
+                tool: "pnpm".into(),
+            }
+            .into())
+        }
+    }
+
     /// Pins the Yarn version in this project's manifest file
     pub fn pin_yarn(&mut self, version: Option<Version>) -> Fallible<()> {
         if let Some(platform) = self.platform.as_mut() {
@@ -245,9 +451,38 @@ pub(crate) fn find_closest_root(mut dir: PathBuf) -> Option<PathBuf> {
     Some(dir)
 }
 
+/// Finds the workspace roots of the project containing `base_dir`, without loading the project
+///
+/// The roots are in the same order as `Project::workspace_roots`, following the `volta.extends`
+/// chain from the project root. Only the manifests are read, so no versions are resolved.
+pub(crate) fn find_workspace_roots(base_dir: PathBuf) -> Fallible<Vec<PathBuf>> {
+    let mut manifests = IndexSet::new();
+    let mut manifest_file = find_closest_root(base_dir).map(|root| root.join("package.json"));
+
+    // Cycles are reported when the project itself is loaded, so here they only end the search
+    while let Some(file) = manifest_file {
+        if manifests.contains(&file) {
+            break;
+        }
+
+        manifest_file = Manifest::from_file(&file)?.extends;
+        manifests.insert(file);
+    }
+
+    Ok(manifests
+        .into_iter()
+        .map(|file| {
+            file.parent()
+                .expect("File paths always have a parent")
+                .to_path_buf()
+        })
+        .collect())
+}
+
 struct PartialPlatform {
     node: Option<Version>,
     npm: Option<Version>,
+    pnpm: Option<Version>,
     yarn: Option<Version>,
 }
 
@@ -256,6 +491,7 @@ impl PartialPlatform {
         PartialPlatform {
             node: self.node.or(other.node),
             npm: self.npm.or(other.npm),
+            pnpm: self.pnpm.or(other.pnpm),
             yarn: self.yarn.or(other.yarn),
         }
     }
@@ -270,6 +506,7 @@ impl TryFrom<PartialPlatform> for PlatformSpec {
         Ok(PlatformSpec {
             node,
             npm: partial.npm,
+            pnpm: partial.pnpm,
             yarn: partial.yarn,
         })
     }
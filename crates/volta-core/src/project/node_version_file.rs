@@ -0,0 +1,91 @@
+//! Support for the `.node-version` and `.nvmrc` files used by other Node version managers
+
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::version::VersionSpec;
+
+/// The supported version files, in order of precedence within a single directory
+const VERSION_FILES: [&str; 2] = [".node-version", ".nvmrc"];
+
+/// Starts at `base_dir` and walks up to the project root, returning the closest version file
+///
+/// The search never leaves the project, so a version file in a parent directory can't affect
+/// projects that it doesn't contain.
+pub(super) fn find(base_dir: &Path, project_root: &Path) -> Option<PathBuf> {
+    let search_from = if base_dir.starts_with(project_root) {
+        base_dir
+    } else {
+        project_root
+    };
+
+    search_from
+        .ancestors()
+        .take_while(|dir| dir.starts_with(project_root))
+        .flat_map(|dir| VERSION_FILES.iter().map(move |name| dir.join(name)))
+        .find(|file| file.is_file())
+}
+
+/// Reads the Node version requested by a version file
+pub(super) fn read(file: &Path) -> Fallible<VersionSpec> {
+    let contents = read_to_string(file).with_context(|| ErrorKind::ReadNodeVersionFileError {
+        file: file.to_owned(),
+    })?;
+
+    match parse(&contents) {
+        Some(version) => version.parse(),
+        None => Err(ErrorKind::ParseNodeVersionFileError {
+            file: file.to_owned(),
+        }
+        .into()),
+    }
+}
+
+/// Finds the version in the contents of a version file
+///
+/// Only the first line that isn't blank or a comment is used, and the leading `v` that nvm allows
+/// (e.g. `v16.14.0`) is removed.
+fn parse(contents: &str) -> Option<&str> {
+    let line = contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))?;
+
+    match line.strip_prefix('v') {
+        Some(stripped) if stripped.starts_with(|c: char| c.is_ascii_digit()) => Some(stripped),
+        _ => Some(line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_exact_version() {
+        assert_eq!(parse("16.14.0\n"), Some("16.14.0"));
+        assert_eq!(parse("v16.14.0"), Some("16.14.0"));
+        assert_eq!(parse("  v14\r\n"), Some("14"));
+    }
+
+    #[test]
+    fn parse_skips_comments_and_blank_lines() {
+        assert_eq!(
+            parse("\n# Node version\n\n12.22.1\n14.17.0\n"),
+            Some("12.22.1")
+        );
+    }
+
+    #[test]
+    fn parse_keeps_aliases() {
+        assert_eq!(parse("lts/gallium"), Some("lts/gallium"));
+        assert_eq!(parse("node"), Some("node"));
+    }
+
+    #[test]
+    fn parse_empty_file() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("\n  \n# only a comment\n"), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! Tracks the platforms pinned by recently used projects.
+//!
+//! Projects can live anywhere on disk, so Volta has no way to enumerate their pins. Instead, we
+//! keep a small record of every project whose pinned platform we run with, so that `volta prune`
+//! can avoid removing tool versions that a project still needs.
+
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::sync::VoltaLock;
+use crate::version::{option_version_serde, version_serde};
+use fs_utils::ensure_containing_dir_exists;
+use log::debug;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+/// How long a project's pins are considered recent after it was last used
+pub const RECENT_PROJECT_WINDOW: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// How often the last-used time of an unchanged project is refreshed on disk
+const REFRESH_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Recently used projects, keyed by the path to their `package.json`
+type RecentProjects = BTreeMap<PathBuf, RecentProject>;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentProject {
+    /// Seconds since the Unix epoch when the project's platform was last used
+    last_used: u64,
+    #[serde(with = "version_serde")]
+    node: Version,
+    #[serde(default, with = "option_version_serde")]
+    npm: Option<Version>,
+    #[serde(default, with = "option_version_serde")]
+    pnpm: Option<Version>,
+    #[serde(default, with = "option_version_serde")]
+    yarn: Option<Version>,
+}
+
+impl RecentProject {
+    fn same_platform(&self, platform: &PlatformSpec) -> bool {
+        self.node == platform.node
+            && self.npm == platform.npm
+            && self.pnpm == platform.pnpm
+            && self.yarn == platform.yarn
+    }
+}
+
+impl From<&RecentProject> for PlatformSpec {
+    fn from(project: &RecentProject) -> Self {
+        PlatformSpec {
+            node: project.node.clone(),
+            npm: project.npm.clone(),
+            pnpm: project.pnpm.clone(),
+            yarn: project.yarn.clone(),
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn load() -> Fallible<RecentProjects> {
+    let path = volta_home()?.recent_projects_file();
+    match read_to_string(path) {
+        // A missing or corrupt file only means that we don't know of any recent projects
+        Ok(contents) => Ok(serde_json::from_str(&contents).unwrap_or_default()),
+        Err(_) => Ok(RecentProjects::default()),
+    }
+}
+
+/// Records that the platform pinned by the project at `manifest` was just used
+///
+/// Failures are logged rather than returned, since this bookkeeping should never prevent a tool
+/// from running.
+pub fn record(manifest: &Path, platform: &PlatformSpec) {
+    if let Err(error) = try_record(manifest, platform) {
+        debug!("Could not record recently used project: {}", error);
+    }
+}
+
+fn try_record(manifest: &Path, platform: &PlatformSpec) -> Fallible<()> {
+    let now = now();
+    if is_up_to_date(&load()?, manifest, platform, now) {
+        return Ok(());
+    }
+
+    // Another process may be updating the file, so it is only written under the lock. A tool
+    // shouldn't wait for the lock just for this, so the record is left for a later run instead.
+    let _lock = match VoltaLock::try_acquire() {
+        Some(lock) => lock,
+        None => {
+            debug!("Volta directory is locked, not recording recently used project");
+            return Ok(());
+        }
+    };
+
+    let mut recent = load()?;
+    if is_up_to_date(&recent, manifest, platform, now) {
+        return Ok(());
+    }
+
+    recent.insert(
+        manifest.to_owned(),
+        RecentProject {
+            last_used: now,
+            node: platform.node.clone(),
+            npm: platform.npm.clone(),
+            pnpm: platform.pnpm.clone(),
+            yarn: platform.yarn.clone(),
+        },
+    );
+
+    // Drop anything that has aged out, so the file doesn't grow forever
+    let cutoff = now.saturating_sub(RECENT_PROJECT_WINDOW.as_secs());
+    recent.retain(|_, project| project.last_used >= cutoff);
+
+    let path = volta_home()?.recent_projects_file();
+    let src = serde_json::to_string_pretty(&recent)
+        .with_context(|| ErrorKind::StringifyRecentProjectsError)?;
+    ensure_containing_dir_exists(&path)
+        .and_then(|_| write(path, src))
+        .with_context(|| ErrorKind::WriteRecentProjectsError {
+            file: path.to_owned(),
+        })
+}
+
+/// Whether the project at `manifest` is already recorded with `platform`, recently enough that
+/// its last-used time doesn't need to be refreshed
+fn is_up_to_date(
+    recent: &RecentProjects,
+    manifest: &Path,
+    platform: &PlatformSpec,
+    now: u64,
+) -> bool {
+    recent.get(manifest).map_or(false, |existing| {
+        existing.same_platform(platform)
+            && now.saturating_sub(existing.last_used) < REFRESH_INTERVAL.as_secs()
+    })
+}
+
+/// Returns the platforms of every project used within the `RECENT_PROJECT_WINDOW`
+pub fn recent_platforms() -> Fallible<Vec<PlatformSpec>> {
+    let cutoff = now().saturating_sub(RECENT_PROJECT_WINDOW.as_secs());
+
+    Ok(load()?
+        .values()
+        .filter(|project| project.last_used >= cutoff)
+        .map(PlatformSpec::from)
+        .collect())
+}
@@ -21,12 +21,28 @@ pub(super) struct Manifest {
     pub dependency_maps: DependencyMapIterator,
     pub platform: Option<PartialPlatform>,
     pub extends: Option<PathBuf>,
+    /// The ranges in the `engines` field, keyed by tool name
+    pub engines: HashMap<String, String>,
+    /// The corepack `packageManager` field, if any
+    pub package_manager: Option<String>,
 }
 
 impl Manifest {
     pub fn from_file(file: &Path) -> Fallible<Self> {
         let raw = RawManifest::from_file(file)?;
 
+        // `engines` has had other shapes in the past (e.g. an array), so we only pick out strings
+        let engines = match raw.engines {
+            Some(Value::Object(engines)) => engines
+                .into_iter()
+                .filter_map(|(tool, range)| match range {
+                    Value::String(range) => Some((tool, range)),
+                    _ => None,
+                })
+                .collect(),
+            _ => HashMap::new(),
+        };
+
         let dependency_maps = raw
             .dependencies
             .into_iter()
@@ -56,6 +72,8 @@ impl Manifest {
             dependency_maps,
             platform,
             extends,
+            engines,
+            package_manager: raw.package_manager,
         })
     }
 }
@@ -63,6 +81,7 @@ impl Manifest {
 pub(super) enum ManifestKey {
     Node,
     Npm,
+    Pnpm,
     Yarn,
 }
 
@@ -71,6 +90,7 @@ impl fmt::Display for ManifestKey {
         f.write_str(match self {
             ManifestKey::Node => "node",
             ManifestKey::Npm => "npm",
+            ManifestKey::Pnpm => "pnpm",
             ManifestKey::Yarn => "yarn",
         })
     }
@@ -147,6 +167,11 @@ struct RawManifest {
     dev_dependencies: Option<HashMap<String, String>>,
 
     volta: Option<ToolchainSpec>,
+
+    engines: Option<Value>,
+
+    #[serde(rename = "packageManager")]
+    package_manager: Option<String>,
 }
 
 impl RawManifest {
@@ -168,6 +193,8 @@ struct ToolchainSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     npm: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pnpm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     yarn: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     extends: Option<PathBuf>,
@@ -178,9 +205,15 @@ impl ToolchainSpec {
     fn parse_split(self) -> Fallible<(PartialPlatform, Option<PathBuf>)> {
         let node = self.node.map(parse_version).transpose()?;
         let npm = self.npm.map(parse_version).transpose()?;
+        let pnpm = self.pnpm.map(parse_version).transpose()?;
         let yarn = self.yarn.map(parse_version).transpose()?;
 
-        let platform = PartialPlatform { node, npm, yarn };
+        let platform = PartialPlatform {
+            node,
+            npm,
+            pnpm,
+            yarn,
+        };
 
         Ok((platform, self.extends))
     }
@@ -77,6 +77,7 @@ mod project {
 
         assert_eq!(platform.node, "6.11.1".parse().unwrap());
         assert_eq!(platform.npm, Some("3.10.10".parse().unwrap()));
+        assert_eq!(platform.pnpm, Some("7.9.0".parse().unwrap()));
         assert_eq!(platform.yarn, Some("1.2.0".parse().unwrap()));
     }
 
@@ -94,6 +95,72 @@ mod project {
         assert_eq!(platform.yarn, Some("1.22.4".parse().unwrap()));
     }
 
+    #[test]
+    fn platform_node_version_file() {
+        let project_path = fixture_path(&["node_version_file"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        let platform = test_project.platform().unwrap();
+
+        assert_eq!(platform.node, "14.17.0".parse().unwrap());
+        // The rest of the platform still comes from the `volta` key
+        assert_eq!(platform.yarn, Some("1.22.4".parse().unwrap()));
+        assert_eq!(
+            test_project.node_source_file(),
+            fixture_path(&["node_version_file", ".nvmrc"])
+        );
+    }
+
+    #[test]
+    fn platform_closest_node_version_file() {
+        let project_path = fixture_path(&["node_version_file", "subdir"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        let platform = test_project.platform().unwrap();
+
+        assert_eq!(platform.node, "16.14.0".parse().unwrap());
+        assert_eq!(
+            test_project.node_source_file(),
+            fixture_path(&["node_version_file", "subdir", ".node-version"])
+        );
+    }
+
+    #[test]
+    fn platform_volta_key_over_node_version_file() {
+        let project_path = fixture_path(&["volta_over_nvmrc"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        let platform = test_project.platform().unwrap();
+
+        assert_eq!(platform.node, "12.22.1".parse().unwrap());
+        assert_eq!(
+            test_project.node_source_file(),
+            fixture_path(&["volta_over_nvmrc", "package.json"])
+        );
+    }
+
+    #[test]
+    fn engines_violation() {
+        let project_path = fixture_path(&["engines"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+
+        assert!(test_project.has_engines("node"));
+        assert!(!test_project.has_engines("yarn"));
+
+        assert_eq!(
+            test_project.engines_violation("node", &"14.17.0".parse().unwrap()),
+            None
+        );
+        assert_eq!(
+            test_project.engines_violation("node", &"18.0.0".parse().unwrap()),
+            Some((
+                fixture_path(&["engines", "package.json"]).as_path(),
+                ">=12 <17"
+            ))
+        );
+        assert_eq!(
+            test_project.engines_violation("yarn", &"1.22.4".parse().unwrap()),
+            None
+        );
+    }
+
     #[test]
     fn direct_dependencies_single() {
         let project_path = fixture_path(&["basic"]);
@@ -181,6 +248,25 @@ mod project {
         assert!(test_project.find_bin("ember").is_none());
     }
 
+    #[test]
+    fn finds_workspace_roots_without_loading() {
+        let project_path = fixture_path(&["nested", "subproject", "inner_project"]);
+        let test_project = Project::for_dir(project_path.clone()).unwrap().unwrap();
+
+        let expected: Vec<_> = test_project
+            .workspace_roots()
+            .map(Path::to_path_buf)
+            .collect();
+        assert_eq!(find_workspace_roots(project_path).unwrap(), expected);
+
+        // Cycles end the search rather than failing
+        let cycle_path = fixture_path(&["cycle-1"]);
+        assert_eq!(
+            find_workspace_roots(cycle_path.clone()).unwrap(),
+            vec![cycle_path.clone(), cycle_path]
+        );
+    }
+
     #[test]
     fn detects_workspace_cycles() {
         // cycle-1 has a cycle with the original package.json
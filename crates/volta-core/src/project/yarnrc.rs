@@ -0,0 +1,76 @@
+//! Support for the `yarnPath` setting of `.yarnrc.yml`, which projects use to vendor their own
+//! release of Yarn 2 or later
+
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use log::debug;
+
+const YARNRC_FILE: &str = ".yarnrc.yml";
+
+/// Starts at `base_dir` and walks up the tree, returning the release set by the closest
+/// `.yarnrc.yml` that has a `yarnPath`
+///
+/// Like Yarn itself, the search continues past the project root, so the release vendored at the
+/// root of a monorepo applies to every workspace within it. The path is relative to the directory
+/// of the file that sets it.
+pub(super) fn find_yarn_path(base_dir: &Path) -> Option<PathBuf> {
+    base_dir.ancestors().find_map(|dir| {
+        let file = dir.join(YARNRC_FILE);
+        let contents = read_to_string(&file).ok()?;
+        let yarn_path = parse(&contents)?;
+
+        debug!("Found yarnPath '{}' in {}", yarn_path, file.display());
+        Some(dir.join(yarn_path))
+    })
+}
+
+/// Finds the top-level `yarnPath` setting in the contents of a `.yarnrc.yml` file
+///
+/// The value may be quoted, and an unquoted value may be followed by a comment.
+fn parse(contents: &str) -> Option<&str> {
+    contents.lines().find_map(|line| {
+        let value = line.strip_prefix("yarnPath:")?.trim();
+        let quoted = ['"', '\''].iter().find_map(|&quote| {
+            value
+                .strip_prefix(quote)
+                .and_then(|rest| rest.split(quote).next())
+        });
+        let value = match quoted {
+            Some(quoted) => quoted,
+            None => value.split(" #").next().unwrap_or(value).trim_end(),
+        };
+
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_yarn_path() {
+        assert_eq!(
+            parse("nodeLinker: node-modules\nyarnPath: .yarn/releases/yarn-3.2.4.cjs\n"),
+            Some(".yarn/releases/yarn-3.2.4.cjs")
+        );
+        assert_eq!(
+            parse("yarnPath: \"releases/yarn 3.cjs\"\r\n"),
+            Some("releases/yarn 3.cjs")
+        );
+        assert_eq!(parse("yarnPath: 'yarn.cjs' # vendored"), Some("yarn.cjs"));
+        assert_eq!(parse("yarnPath: yarn.cjs # vendored"), Some("yarn.cjs"));
+    }
+
+    #[test]
+    fn parse_ignores_nested_and_missing_settings() {
+        assert_eq!(parse("nodeLinker: pnp\n"), None);
+        assert_eq!(parse("packageExtensions:\n  yarnPath: yarn.cjs\n"), None);
+        assert_eq!(parse("yarnPath:\n"), None);
+    }
+}
@@ -77,7 +77,7 @@ impl Executor {
             Executor::PackageLink(cmd) => cmd.execute(session),
             Executor::PackageUpgrade(cmd) => cmd.execute(session),
             Executor::InternalInstall(cmd) => cmd.execute(session),
-            Executor::Uninstall(cmd) => cmd.execute(),
+            Executor::Uninstall(cmd) => cmd.execute(session),
             Executor::Multiple(executors) => {
                 info!(
                     "{} Volta is processing each package separately",
@@ -123,6 +123,8 @@ pub enum ToolKind {
     Node,
     Npm,
     Npx,
+    Pnpm,
+    Pnpx,
     Yarn,
     ProjectLocalBinary(String),
     DefaultBinary(String),
@@ -175,10 +177,16 @@ impl ToolCommand {
 
     /// Runs the command, returning the `ExitStatus` if it successfully launches
     pub fn execute(mut self, session: &mut Session) -> Fallible<ExitStatus> {
+        if let Some(platform) = &self.platform {
+            session.record_platform(platform);
+        }
+
         let (path, on_failure) = match self.kind {
             ToolKind::Node => super::node::execution_context(self.platform, session)?,
             ToolKind::Npm => super::npm::execution_context(self.platform, session)?,
             ToolKind::Npx => super::npx::execution_context(self.platform, session)?,
+            ToolKind::Pnpm => super::pnpm::execution_context(self.platform, session)?,
+            ToolKind::Pnpx => super::pnpx::execution_context(self.platform, session)?,
             ToolKind::Yarn => super::yarn::execution_context(self.platform, session)?,
             ToolKind::DefaultBinary(bin) => {
                 super::binary::default_execution_context(bin, self.platform, session)?
@@ -226,6 +234,7 @@ impl PackageInstallCommand {
 
         let mut command = match manager {
             PackageManager::Npm => create_command("npm"),
+            PackageManager::Pnpm => create_command("pnpm"),
             PackageManager::Yarn => create_command("yarn"),
         };
         command.args(args);
@@ -273,6 +282,7 @@ impl PackageInstallCommand {
     /// data directory
     pub fn execute(mut self, session: &mut Session) -> Fallible<ExitStatus> {
         let _lock = VoltaLock::acquire();
+        session.record_platform(&self.platform);
         let image = self.platform.checkout(session)?;
         let path = image.path()?;
 
@@ -350,6 +360,7 @@ impl PackageLinkCommand {
     pub fn execute(mut self, session: &mut Session) -> Fallible<ExitStatus> {
         self.check_linked_package(session)?;
 
+        session.record_platform(&self.platform);
         let image = self.platform.checkout(session)?;
         let path = image.path()?;
 
@@ -430,6 +441,7 @@ impl PackageUpgradeCommand {
 
         let mut command = match manager {
             PackageManager::Npm => create_command("npm"),
+            PackageManager::Pnpm => create_command("pnpm"),
             PackageManager::Yarn => create_command("yarn"),
         };
         command.args(args);
@@ -465,6 +477,7 @@ impl PackageUpgradeCommand {
         self.upgrader.check_upgraded_package()?;
 
         let _lock = VoltaLock::acquire();
+        session.record_platform(&self.platform);
         let image = self.platform.checkout(session)?;
         let path = image.path()?;
 
@@ -539,14 +552,14 @@ impl UninstallCommand {
     }
 
     /// Runs the uninstall with Volta's internal uninstall logic
-    fn execute(self) -> Fallible<ExitStatus> {
+    fn execute(self, session: &mut Session) -> Fallible<ExitStatus> {
         info!(
             "{} using Volta to uninstall {}",
             note_prefix(),
             self.tool.name()
         );
 
-        self.tool.uninstall()?;
+        self.tool.uninstall(session, false)?;
 
         Ok(ExitStatus::from_raw(0))
     }
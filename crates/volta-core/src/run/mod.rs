@@ -16,12 +16,14 @@ mod node;
 mod npm;
 mod npx;
 mod parser;
+mod pnpm;
+mod pnpx;
 mod yarn;
 
 /// Environment variable set internally when a shim has been executed and the context evaluated
 ///
 /// This is set when executing a shim command. If this is already, then the built-in shims (Node,
-/// npm, npx, and Yarn) will assume that the context has already been evaluated & the PATH has
+/// npm, npx, pnpm, pnpx, and Yarn) will assume that the context has already been evaluated & the PATH has
 /// already been modified, so they will use the pass-through behavior.
 ///
 /// Shims should only be called recursively when the environment is misconfigured, so this will
@@ -37,6 +39,7 @@ pub fn execute_shim(session: &mut Session) -> Fallible<ExitStatus> {
     let mut native_args = env::args_os();
     let exe = get_tool_name(&mut native_args)?;
     let args: Vec<_> = native_args.collect();
+    session.record_tool(&exe.to_string_lossy());
 
     get_executor(&exe, &args, session)?.execute(session)
 }
@@ -56,6 +59,7 @@ where
     // Remove the recursion environment variable so that the context is correctly re-evaluated
     // when calling `volta run` (even when called from a Node script)
     env::remove_var(RECURSION_ENV_VAR);
+    session.record_tool(&exe.to_string_lossy());
 
     let mut runner = get_executor(exe, args, session)?;
     runner.cli_platform(cli);
@@ -84,6 +88,8 @@ fn get_executor(
             Some("node") => node::command(args, session),
             Some("npm") => npm::command(args, session),
             Some("npx") => npx::command(args, session),
+            Some("pnpm") => pnpm::command(args, session),
+            Some("pnpx") => pnpx::command(args, session),
             Some("yarn") => yarn::command(args, session),
             _ => binary::command(exe, args, session),
         }
@@ -126,6 +132,7 @@ fn debug_active_image(image: &Image) {
         "Active Image:
     Node: {}
     npm: {}
+    pnpm: {}
     Yarn: {}",
         format_tool_version(&image.node),
         image
@@ -134,6 +141,11 @@ fn debug_active_image(image: &Image) {
             .as_ref()
             .map(format_tool_version)
             .unwrap_or_else(|| "Bundled with Node".into()),
+        image
+            .pnpm
+            .as_ref()
+            .map(format_tool_version)
+            .unwrap_or_else(|| "None".into()),
         image
             .yarn
             .as_ref()
@@ -25,6 +25,12 @@ const NPM_UNINSTALL_ALIASES: [&str; 5] = ["un", "uninstall", "remove", "rm", "r"
 const NPM_LINK_ALIASES: [&str; 2] = ["link", "ln"];
 /// Aliases that npm supports for the `update` command
 const NPM_UPDATE_ALIASES: [&str; 4] = ["update", "udpate", "upgrade", "up"];
+/// Aliases that pnpm supports for the 'add' command
+const PNPM_ADD_ALIASES: [&str; 3] = ["add", "install", "i"];
+/// Aliases that pnpm supports for the 'remove' command
+const PNPM_REMOVE_ALIASES: [&str; 4] = ["remove", "rm", "uninstall", "un"];
+/// Aliases that pnpm supports for the 'update' command
+const PNPM_UPDATE_ALIASES: [&str; 3] = ["update", "up", "upgrade"];
 
 pub enum CommandArg<'a> {
     Global(GlobalCommand<'a>),
@@ -126,6 +132,75 @@ impl<'a> CommandArg<'a> {
         }
     }
 
+    /// Parse the given set of arguments to see if they correspond to an intercepted pnpm command
+    pub fn for_pnpm<S>(args: &'a [S]) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        // If VOLTA_UNSAFE_GLOBAL is set, then we always skip any global parsing
+        if env::var_os(UNSAFE_GLOBAL).is_some() {
+            return CommandArg::Standard;
+        }
+
+        let mut positionals = args.iter().filter(is_positional).map(AsRef::as_ref);
+
+        // Like npm, pnpm globals are marked with a `--global` flag rather than a subcommand. If
+        // there are no tools to add or remove, we let pnpm handle the command and show any errors.
+        match positionals.next() {
+            Some(cmd) if PNPM_ADD_ALIASES.iter().any(|a| a == &cmd) => {
+                if has_global_without_prefix(args) {
+                    let tools: Vec<_> = positionals.collect();
+
+                    if tools.is_empty() {
+                        CommandArg::Standard
+                    } else {
+                        // The common args for an install should be the command combined with any flags
+                        let mut common_args = vec![cmd];
+                        common_args.extend(args.iter().filter(is_flag).map(AsRef::as_ref));
+
+                        CommandArg::Global(GlobalCommand::Install(InstallArgs {
+                            manager: PackageManager::Pnpm,
+                            common_args,
+                            tools,
+                        }))
+                    }
+                } else {
+                    CommandArg::Standard
+                }
+            }
+            Some(cmd) if PNPM_REMOVE_ALIASES.iter().any(|a| a == &cmd) => {
+                if has_global_without_prefix(args) {
+                    let tools: Vec<_> = positionals.collect();
+
+                    if tools.is_empty() {
+                        CommandArg::Standard
+                    } else {
+                        CommandArg::Global(GlobalCommand::Uninstall(UninstallArgs { tools }))
+                    }
+                } else {
+                    CommandArg::Standard
+                }
+            }
+            Some(cmd) if PNPM_UPDATE_ALIASES.iter().any(|a| a == &cmd) => {
+                if has_global_without_prefix(args) {
+                    // The common args for an upgrade are the command combined with any flags
+                    let mut common_args = vec![cmd];
+                    common_args.extend(args.iter().filter(is_flag).map(AsRef::as_ref));
+                    let tools: Vec<_> = positionals.collect();
+
+                    CommandArg::Global(GlobalCommand::Upgrade(UpgradeArgs {
+                        common_args,
+                        tools,
+                        manager: PackageManager::Pnpm,
+                    }))
+                } else {
+                    CommandArg::Standard
+                }
+            }
+            _ => CommandArg::Standard,
+        }
+    }
+
     /// Parse the given set of arguments to see if they correspond to an intercepted Yarn command
     pub fn for_yarn<S>(args: &'a [S]) -> Self
     where
@@ -748,6 +823,79 @@ mod tests {
         }
     }
 
+    mod pnpm {
+        use super::super::*;
+        use super::*;
+
+        #[test]
+        fn handles_global_add() {
+            match CommandArg::for_pnpm(&arg_list(&["add", "--global", "typescript@3"])) {
+                CommandArg::Global(GlobalCommand::Install(install)) => {
+                    assert_eq!(install.manager, PackageManager::Pnpm);
+                    assert_eq!(install.common_args, vec!["add", "--global"]);
+                    assert_eq!(install.tools, vec!["typescript@3"]);
+                }
+                _ => panic!("Doesn't parse global add as a global"),
+            };
+        }
+
+        #[test]
+        fn handles_local_add() {
+            match CommandArg::for_pnpm(&arg_list(&["add", "--save-dev", "typescript"])) {
+                CommandArg::Standard => (),
+                _ => panic!("Parses local add as a global"),
+            };
+        }
+
+        #[test]
+        fn handles_global_remove() {
+            match CommandArg::for_pnpm(&arg_list(&["remove", "-g", "typescript"])) {
+                CommandArg::Global(GlobalCommand::Uninstall(uninstall)) => {
+                    assert_eq!(uninstall.tools, vec!["typescript"]);
+                }
+                _ => panic!("Doesn't parse global remove as a global"),
+            };
+        }
+
+        #[test]
+        fn handles_global_update() {
+            match CommandArg::for_pnpm(&arg_list(&["update", "--global", "typescript"])) {
+                CommandArg::Global(GlobalCommand::Upgrade(upgrade)) => {
+                    assert_eq!(upgrade.manager, PackageManager::Pnpm);
+                    assert_eq!(upgrade.common_args, vec!["update", "--global"]);
+                    assert_eq!(upgrade.tools, vec!["typescript"]);
+                }
+                _ => panic!("Doesn't parse global update as a global"),
+            };
+        }
+
+        #[test]
+        fn handles_multiple_add() {
+            match CommandArg::for_pnpm(&arg_list(&[
+                "add",
+                "-g",
+                "typescript",
+                "cowsay",
+                "ember-cli",
+            ])) {
+                CommandArg::Global(GlobalCommand::Install(install)) => {
+                    assert_eq!(install.manager, PackageManager::Pnpm);
+                    assert_eq!(install.common_args, vec!["add", "-g"]);
+                    assert_eq!(install.tools, vec!["typescript", "cowsay", "ember-cli"]);
+                }
+                _ => panic!("Doesn't parse global add as a global"),
+            };
+        }
+
+        #[test]
+        fn skips_global_with_prefix() {
+            match CommandArg::for_pnpm(&arg_list(&["add", "-g", "--prefix=~/", "typescript"])) {
+                CommandArg::Standard => (),
+                _ => panic!("Parses global add with prefix as a global"),
+            };
+        }
+    }
+
     mod yarn {
         use super::super::*;
         use super::*;
@@ -1,12 +1,15 @@
 use std::env;
 use std::ffi::OsString;
+use std::iter::once;
 
 use super::executor::{Executor, ToolCommand, ToolKind};
 use super::parser::CommandArg;
 use super::{debug_active_image, debug_no_platform, RECURSION_ENV_VAR};
 use crate::error::{ErrorKind, Fallible};
 use crate::platform::{Platform, Source, System};
+use crate::project::Project;
 use crate::session::{ActivityKind, Session};
+use log::debug;
 
 /// Build an `Executor` for Yarn
 ///
@@ -15,7 +18,9 @@ use crate::session::{ActivityKind, Session};
 /// directory.
 ///
 /// If the command is _not_ a global add / remove or we don't have a default platform, then
-/// we will allow Yarn to execute the command as usual.
+/// we will allow Yarn to execute the command as usual. When the project vendors its own release
+/// of Yarn with the `yarnPath` setting of `.yarnrc.yml`, that release is run with the project's
+/// Node instead of the Yarn from the platform.
 pub(super) fn command(args: &[OsString], session: &mut Session) -> Fallible<Executor> {
     session.add_event_start(ActivityKind::Yarn);
     // Don't re-evaluate the context or global install interception if this is a recursive call
@@ -29,7 +34,15 @@ pub(super) fn command(args: &[OsString], session: &mut Session) -> Fallible<Exec
                 }
             }
 
-            Platform::current(session)?
+            let platform = Platform::current(session)?;
+
+            if let Some(yarn_path) = session.project()?.and_then(Project::yarn_path) {
+                debug!("Running the Yarn release at '{}'", yarn_path.display());
+                let args = once(yarn_path.into_os_string()).chain(args.iter().cloned());
+                return Ok(ToolCommand::new("node", args, platform, ToolKind::Node).into());
+            }
+
+            platform
         }
     };
 
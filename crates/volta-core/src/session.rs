@@ -5,12 +5,14 @@
 use std::fmt::{self, Display, Formatter};
 use std::process::exit;
 
+use crate::config::{self, Config};
 use crate::error::{ExitCode, Fallible, VoltaError};
 use crate::event::EventLog;
 use crate::hook::{HookConfig, LazyHookConfig};
-use crate::platform::PlatformSpec;
-use crate::project::{LazyProject, Project};
+use crate::platform::{Platform, PlatformSpec};
+use crate::project::{recent, LazyProject, Project};
 use crate::toolchain::{LazyToolchain, Toolchain};
+use crate::usage;
 use log::debug;
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
@@ -22,9 +24,18 @@ pub enum ActivityKind {
     Current,
     Default,
     Pin,
+    Prune,
+    Outdated,
+    Upgrade,
+    Config,
+    Export,
+    Import,
+    Stats,
     Node,
     Npm,
     Npx,
+    Pnpm,
+    Pnpx,
     Yarn,
     Volta,
     Tool,
@@ -49,9 +60,18 @@ impl Display for ActivityKind {
             ActivityKind::Current => "current",
             ActivityKind::Default => "default",
             ActivityKind::Pin => "pin",
+            ActivityKind::Prune => "prune",
+            ActivityKind::Outdated => "outdated",
+            ActivityKind::Upgrade => "upgrade",
+            ActivityKind::Config => "config",
+            ActivityKind::Export => "export",
+            ActivityKind::Import => "import",
+            ActivityKind::Stats => "stats",
             ActivityKind::Node => "node",
             ActivityKind::Npm => "npm",
             ActivityKind::Npx => "npx",
+            ActivityKind::Pnpm => "pnpm",
+            ActivityKind::Pnpx => "pnpx",
             ActivityKind::Yarn => "yarn",
             ActivityKind::Volta => "volta",
             ActivityKind::Tool => "tool",
@@ -76,6 +96,7 @@ impl Display for ActivityKind {
 /// - the current directory
 /// - the Node project tree that contains the current directory (if any)
 /// - the Volta hook configuration
+/// - the Volta configuration settings
 /// - the inventory of locally-fetched Volta tools
 pub struct Session {
     hooks: LazyHookConfig,
@@ -133,19 +154,46 @@ impl Session {
         self.hooks.get(self.project()?)
     }
 
+    /// Produces a reference to the configuration settings
+    pub fn config(&self) -> Fallible<&Config> {
+        config::current()
+    }
+
     pub fn add_event_start(&mut self, activity_kind: ActivityKind) {
         self.event_log.add_event_start(activity_kind)
     }
     pub fn add_event_end(&mut self, activity_kind: ActivityKind, exit_code: ExitCode) {
+        self.record_manifest();
         self.event_log.add_event_end(activity_kind, exit_code)
     }
     pub fn add_event_tool_end(&mut self, activity_kind: ActivityKind, exit_code: i32) {
+        self.record_manifest();
         self.event_log.add_event_tool_end(activity_kind, exit_code)
     }
     pub fn add_event_error(&mut self, activity_kind: ActivityKind, error: &VoltaError) {
         self.event_log.add_event_error(activity_kind, error)
     }
 
+    /// Records the tool that is being run, for the event that ends the session
+    pub fn record_tool(&mut self, tool: &str) {
+        self.event_log.set_tool(tool)
+    }
+
+    /// Records the platform that a tool is run with, for the event that ends the session
+    pub fn record_platform(&mut self, platform: &Platform) {
+        self.event_log.set_platform(platform)
+    }
+
+    /// Records the manifest of the project, if the command has already loaded it, so that the
+    /// project isn't searched for only to report it
+    fn record_manifest(&mut self) {
+        let manifest = self
+            .project
+            .loaded()
+            .map(|project| project.manifest_file().to_path_buf());
+        self.event_log.set_manifest(manifest.as_deref());
+    }
+
     fn publish_to_event_log(self) {
         let Self {
             project,
@@ -168,12 +216,36 @@ impl Session {
         }
     }
 
+    /// Remembers the pins of the project, if the command has loaded it, so that `volta prune`
+    /// won't remove them
+    ///
+    /// This is done on the way out rather than when the platform is resolved, so that reading
+    /// the list of recent projects never delays a tool. It is only written when the project is
+    /// new to it or its entry is stale.
+    fn record_recent_project(&self) {
+        if let Some(project) = self.project.loaded() {
+            if let Some(platform) = project.platform() {
+                recent::record(project.manifest_file(), platform);
+            }
+        }
+    }
+
+    fn record_usage(&self, exit_code: i32) {
+        if usage::is_enabled() {
+            self.event_log.record_usage(exit_code);
+        }
+    }
+
     pub fn exit(self, code: ExitCode) -> ! {
+        self.record_recent_project();
+        self.record_usage(code as i32);
         self.publish_to_event_log();
         code.exit();
     }
 
     pub fn exit_tool(self, code: i32) -> ! {
+        self.record_recent_project();
+        self.record_usage(code);
         self.publish_to_event_log();
         exit(code);
     }
@@ -74,14 +74,7 @@ impl VoltaLock {
                 inner.count += 1;
             }
             None => {
-                let path = volta_home()?.root().join(LOCK_FILE);
-                debug!("Acquiring lock on Volta directory: {}", path.display());
-
-                let file = OpenOptions::new()
-                    .write(true)
-                    .create(true)
-                    .open(path)
-                    .with_context(|| ErrorKind::LockAcquireError)?;
+                let file = open_lock_file()?;
                 // First we try to lock the file without blocking. If that fails, then we show a spinner
                 // and block until the lock completes.
                 if file.try_lock_exclusive().is_err() {
@@ -102,6 +95,41 @@ impl VoltaLock {
             _private: PhantomData,
         })
     }
+
+    /// Acquires a lock without waiting for another process to release it
+    ///
+    /// Returns `None` if the lock can't be acquired right away, for bookkeeping that should be
+    /// skipped rather than hold up a tool.
+    pub fn try_acquire() -> Option<Self> {
+        let mut state = LOCK_STATE.lock().ok()?;
+
+        match &mut *state {
+            Some(inner) => {
+                inner.count += 1;
+            }
+            None => {
+                let file = open_lock_file().ok()?;
+                file.try_lock_exclusive().ok()?;
+
+                *state = Some(LockState { file, count: 1 });
+            }
+        }
+
+        Some(Self {
+            _private: PhantomData,
+        })
+    }
+}
+
+fn open_lock_file() -> Fallible<File> {
+    let path = volta_home()?.root().join(LOCK_FILE);
+    debug!("Acquiring lock on Volta directory: {}", path.display());
+
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .open(path)
+        .with_context(|| ErrorKind::LockAcquireError)
 }
 
 impl Drop for VoltaLock {
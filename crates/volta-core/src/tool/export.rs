@@ -0,0 +1,399 @@
+//! Exports the default toolchain and installed packages to a portable file, and reproduces them
+//! from that file on another machine.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::path::Path;
+
+use super::package::PackageManager;
+use super::{prefetch, BundledNpm, Node, Npm, Package, PackageConfig, Pnpm, Tool, Yarn};
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::inventory::package_configs;
+use crate::platform::PlatformSpec;
+use crate::session::Session;
+use crate::style::tool_version;
+use crate::sync::VoltaLock;
+use crate::version::{option_version_serde, version_serde, VersionSpec};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+/// The version of the export format, which is increased whenever older versions of Volta could
+/// no longer import it correctly
+const FORMAT_VERSION: u32 = 1;
+
+/// The default platform and installed packages, in the format written by `volta export`
+#[derive(Serialize, Deserialize)]
+pub struct ToolchainExport {
+    format: u32,
+    platform: Option<ExportedPlatform>,
+    packages: Vec<ExportedPackage>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+struct ExportedPlatform {
+    #[serde(with = "version_serde")]
+    node: Version,
+    #[serde(default, with = "option_version_serde")]
+    npm: Option<Version>,
+    #[serde(default, with = "option_version_serde")]
+    pnpm: Option<Version>,
+    #[serde(default, with = "option_version_serde")]
+    yarn: Option<Version>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+struct ExportedPackage {
+    name: String,
+    #[serde(with = "version_serde")]
+    version: Version,
+    platform: ExportedPlatform,
+    manager: PackageManager,
+}
+
+/// A difference between the local toolchain and an exported one
+pub enum Drift {
+    /// A default tool or package whose local version differs from the exported one
+    Changed {
+        name: String,
+        local: String,
+        exported: String,
+    },
+    /// A package that is installed locally but isn't in the export, which importing leaves alone
+    Extra { name: String, local: String },
+}
+
+impl Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::Changed {
+                name,
+                local,
+                exported,
+            } => write!(f, "{}: {} -> {}", name, local, exported),
+            Drift::Extra { name, local } => {
+                write!(f, "{}: {} (not in the exported toolchain)", name, local)
+            }
+        }
+    }
+}
+
+impl ToolchainExport {
+    /// Collect the default platform and every installed package
+    pub fn current(session: &Session) -> Fallible<Self> {
+        let platform = session.default_platform()?.map(ExportedPlatform::from);
+        let packages = package_configs()?
+            .into_iter()
+            .map(ExportedPackage::from)
+            .collect();
+
+        Ok(ToolchainExport {
+            format: FORMAT_VERSION,
+            platform,
+            packages,
+        })
+    }
+
+    /// Read a toolchain that was written by `volta export`
+    pub fn from_file(file: &Path) -> Fallible<Self> {
+        let parse_error = || ErrorKind::ParseToolchainExportError {
+            file: file.to_owned(),
+        };
+
+        let contents = File::open(file).with_context(|| ErrorKind::ReadToolchainExportError {
+            file: file.to_owned(),
+        })?;
+        let export: Self = serde_json::from_reader(contents).with_context(parse_error)?;
+
+        if export.format > FORMAT_VERSION {
+            return Err(parse_error().into());
+        }
+        Ok(export)
+    }
+
+    pub fn to_json(&self) -> Fallible<String> {
+        serde_json::to_string_pretty(self).with_context(|| ErrorKind::StringifyToolchainExportError)
+    }
+
+    /// Compare the exported toolchain with the local one
+    pub fn drift(&self, session: &Session) -> Fallible<Vec<Drift>> {
+        let local_platform = session.default_platform()?.map(ExportedPlatform::from);
+        let mut drift = platform_drift(local_platform.as_ref(), self.platform.as_ref());
+
+        let mut local_packages: BTreeMap<String, ExportedPackage> = package_configs()?
+            .into_iter()
+            .map(|config| (config.name.clone(), ExportedPackage::from(config)))
+            .collect();
+
+        for package in &self.packages {
+            match local_packages.remove(&package.name) {
+                Some(local) if local == *package => {}
+                local => drift.push(Drift::Changed {
+                    name: package.name.clone(),
+                    local: local
+                        .map(|local| local.describe())
+                        .unwrap_or_else(|| "not installed".into()),
+                    exported: package.describe(),
+                }),
+            }
+        }
+
+        drift.extend(
+            local_packages
+                .into_iter()
+                .map(|(name, local)| Drift::Extra {
+                    name,
+                    local: local.describe(),
+                }),
+        );
+
+        Ok(drift)
+    }
+
+    /// Install the exported default platform and packages, skipping anything that already matches
+    ///
+    /// Packages that are installed locally but aren't part of the export are left in place.
+    pub fn import(self, session: &mut Session) -> Fallible<()> {
+        if let Some(exported) = &self.platform {
+            import_platform(exported, session)?;
+        }
+
+        let _lock = VoltaLock::acquire();
+        let local_packages: BTreeMap<String, ExportedPackage> = package_configs()?
+            .into_iter()
+            .map(|config| (config.name.clone(), ExportedPackage::from(config)))
+            .collect();
+
+        for package in self.packages {
+            if local_packages.get(&package.name) == Some(&package) {
+                continue;
+            }
+
+            let image = PlatformSpec::from(package.platform)
+                .as_default()
+                .checkout(session)?;
+            let installer = Package::with_manager(
+                package.name,
+                VersionSpec::Exact(package.version),
+                package.manager,
+            )?;
+            installer.run_install(&image)?;
+            installer.complete_install(&image)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Install the default tools that differ from the exported platform
+///
+/// All of them are downloaded before any are installed, and Node is installed first since the
+/// package managers need a default platform to be set.
+fn import_platform(exported: &ExportedPlatform, session: &mut Session) -> Fallible<()> {
+    let local = session.default_platform()?.map(ExportedPlatform::from);
+    let differs = |field: fn(&ExportedPlatform) -> Option<&Version>| {
+        local.as_ref().and_then(field) != field(exported)
+    };
+
+    let mut tools: Vec<Box<dyn Tool>> = Vec::new();
+    if differs(|platform| Some(&platform.node)) {
+        tools.push(Box::new(Node::new(exported.node.clone())));
+    }
+    if differs(|platform| platform.npm.as_ref()) {
+        match &exported.npm {
+            Some(npm) => tools.push(Box::new(Npm::new(npm.clone()))),
+            None => tools.push(Box::new(BundledNpm)),
+        }
+    }
+    if differs(|platform| platform.pnpm.as_ref()) {
+        tools.extend(
+            exported
+                .pnpm
+                .clone()
+                .map(|pnpm| Box::new(Pnpm::new(pnpm)) as Box<dyn Tool>),
+        );
+    }
+    if differs(|platform| platform.yarn.as_ref()) {
+        tools.extend(
+            exported
+                .yarn
+                .clone()
+                .map(|yarn| Box::new(Yarn::new(yarn)) as Box<dyn Tool>),
+        );
+    }
+
+    prefetch(&tools, session)?;
+    for tool in tools {
+        tool.install(session)?;
+    }
+
+    // Package managers that aren't part of the exported platform are removed from the default
+    if exported.pnpm.is_none() && differs(|platform| platform.pnpm.as_ref()) {
+        session.toolchain_mut()?.set_active_pnpm(None)?;
+    }
+    if exported.yarn.is_none() && differs(|platform| platform.yarn.as_ref()) {
+        session.toolchain_mut()?.set_active_yarn(None)?;
+    }
+
+    Ok(())
+}
+
+fn platform_drift(
+    local: Option<&ExportedPlatform>,
+    exported: Option<&ExportedPlatform>,
+) -> Vec<Drift> {
+    let exported = match exported {
+        Some(exported) => exported,
+        None => return Vec::new(),
+    };
+
+    let mut drift = Vec::new();
+    let mut compare =
+        |name: &str, local: Option<&Version>, exported: Option<&Version>, unset: &str| {
+            if local != exported {
+                let describe = |version: Option<&Version>| {
+                    version.map_or_else(|| unset.to_string(), Version::to_string)
+                };
+                drift.push(Drift::Changed {
+                    name: name.into(),
+                    local: describe(local),
+                    exported: describe(exported),
+                });
+            }
+        };
+
+    compare(
+        "node",
+        local.map(|platform| &platform.node),
+        Some(&exported.node),
+        "none",
+    );
+    compare(
+        "npm",
+        local.and_then(|platform| platform.npm.as_ref()),
+        exported.npm.as_ref(),
+        "bundled",
+    );
+    compare(
+        "pnpm",
+        local.and_then(|platform| platform.pnpm.as_ref()),
+        exported.pnpm.as_ref(),
+        "none",
+    );
+    compare(
+        "yarn",
+        local.and_then(|platform| platform.yarn.as_ref()),
+        exported.yarn.as_ref(),
+        "none",
+    );
+
+    drift
+}
+
+impl ExportedPackage {
+    /// Describe the installed version, e.g. `4.8.4 (node@18.12.0)`
+    fn describe(&self) -> String {
+        let node = tool_version("node", &self.platform.node);
+        match self.manager {
+            PackageManager::Npm => format!("{} ({})", self.version, node),
+            PackageManager::Pnpm => format!("{} ({}, pnpm)", self.version, node),
+            PackageManager::Yarn => format!("{} ({}, Yarn)", self.version, node),
+        }
+    }
+}
+
+impl From<&PlatformSpec> for ExportedPlatform {
+    fn from(platform: &PlatformSpec) -> Self {
+        ExportedPlatform {
+            node: platform.node.clone(),
+            npm: platform.npm.clone(),
+            pnpm: platform.pnpm.clone(),
+            yarn: platform.yarn.clone(),
+        }
+    }
+}
+
+impl From<ExportedPlatform> for PlatformSpec {
+    fn from(platform: ExportedPlatform) -> Self {
+        PlatformSpec {
+            node: platform.node,
+            npm: platform.npm,
+            pnpm: platform.pnpm,
+            yarn: platform.yarn,
+        }
+    }
+}
+
+impl From<PackageConfig> for ExportedPackage {
+    fn from(config: PackageConfig) -> Self {
+        ExportedPackage {
+            name: config.name,
+            version: config.version,
+            platform: ExportedPlatform::from(&config.platform),
+            manager: config.manager,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn platform(node: &str, npm: Option<&str>, yarn: Option<&str>) -> ExportedPlatform {
+        ExportedPlatform {
+            node: Version::parse(node).unwrap(),
+            npm: npm.map(|npm| Version::parse(npm).unwrap()),
+            pnpm: None,
+            yarn: yarn.map(|yarn| Version::parse(yarn).unwrap()),
+        }
+    }
+
+    #[test]
+    fn platform_drift_lists_changed_tools() {
+        let local = platform("16.18.0", None, Some("1.22.19"));
+        let exported = platform("18.12.0", Some("8.19.2"), Some("1.22.19"));
+
+        let drift: Vec<String> = platform_drift(Some(&local), Some(&exported))
+            .iter()
+            .map(Drift::to_string)
+            .collect();
+        assert_eq!(
+            drift,
+            vec!["node: 16.18.0 -> 18.12.0", "npm: bundled -> 8.19.2"]
+        );
+    }
+
+    #[test]
+    fn platform_drift_without_local_platform() {
+        let exported = platform("18.12.0", None, None);
+
+        let drift: Vec<String> = platform_drift(None, Some(&exported))
+            .iter()
+            .map(Drift::to_string)
+            .collect();
+        assert_eq!(drift, vec!["node: none -> 18.12.0"]);
+        assert!(platform_drift(Some(&exported), Some(&exported)).is_empty());
+    }
+
+    #[test]
+    fn parses_export() {
+        let export: ToolchainExport = serde_json::from_str(
+            r#"{
+                "format": 1,
+                "platform": { "node": "18.12.0", "npm": null, "yarn": "1.22.19" },
+                "packages": [
+                    {
+                        "name": "typescript",
+                        "version": "4.8.4",
+                        "platform": { "node": "18.12.0", "npm": null, "pnpm": null, "yarn": null },
+                        "manager": "Npm"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert!(export.platform == Some(platform("18.12.0", None, Some("1.22.19"))));
+        assert_eq!(export.packages[0].describe(), "4.8.4 (node@18.12.0)");
+    }
+}
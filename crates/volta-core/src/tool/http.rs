@@ -0,0 +1,190 @@
+//! Provides the HTTP client shared by every request Volta makes, configured with the proxy and
+//! certificate authorities of the environment.
+
+use std::env;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use super::npmrc::npmrc;
+use crate::error::{Context, ErrorKind, Fallible, VoltaError};
+use archive::ArchiveError;
+use attohttpc::{ProxySettings, RequestBuilder, Session};
+use double_checked_cell::DoubleCheckedCell;
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use native_tls::Certificate;
+
+/// Environment variable naming a file of extra certificate authorities, shared with Node
+const EXTRA_CA_CERTS_VAR: &str = "NODE_EXTRA_CA_CERTS";
+
+const PEM_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+const PEM_END: &str = "-----END CERTIFICATE-----";
+
+lazy_static! {
+    static ref CLIENT: DoubleCheckedCell<Session> = DoubleCheckedCell::new();
+}
+
+/// Returns the shared HTTP client
+///
+/// The client uses the proxy from `HTTP_PROXY` / `HTTPS_PROXY`, except for the hosts in
+/// `NO_PROXY`, and trusts the certificate authorities in `NODE_EXTRA_CA_CERTS` and the `cafile`
+/// setting of `.npmrc` in addition to those of the system. Client certificates (`certfile` and
+/// `keyfile`) aren't supported, since attohttpc has no way to present one.
+pub(crate) fn client() -> Fallible<&'static Session> {
+    CLIENT.get_or_try_init(|| {
+        let mut client = Session::new();
+        client.proxy_settings(ProxySettings::from_env());
+
+        // Like Node, a file of extra certificates that can't be loaded is only reported, so that
+        // it doesn't prevent requests that don't need them
+        if let Some(file) = env::var_os(EXTRA_CA_CERTS_VAR).map(PathBuf::from) {
+            match load_certificates(&file) {
+                Ok(certificates) => {
+                    for certificate in certificates {
+                        client.add_root_certificate(certificate);
+                    }
+                }
+                Err(error) => warn!("Ignoring {}: {}", EXTRA_CA_CERTS_VAR, error),
+            }
+        }
+
+        if let Some(file) = npmrc_ca_file()? {
+            for certificate in load_certificates(&file)? {
+                client.add_root_certificate(certificate);
+            }
+        }
+
+        Ok(client)
+    })
+}
+
+/// Start a GET request to `url` with the shared client
+pub(crate) fn get(url: &str) -> Fallible<RequestBuilder> {
+    client().map(|client| client.get(url))
+}
+
+fn npmrc_ca_file() -> Fallible<Option<PathBuf>> {
+    npmrc().map(|npmrc| npmrc.get_path("cafile"))
+}
+
+fn load_certificates(file: &Path) -> Fallible<Vec<Certificate>> {
+    let parse_error = || ErrorKind::ParseCertificatesError {
+        file: file.to_path_buf(),
+    };
+
+    let contents = read_to_string(file).with_context(|| ErrorKind::ReadCertificatesError {
+        file: file.to_path_buf(),
+    })?;
+    let certificates = pem_blocks(&contents)
+        .map(|block| Certificate::from_pem(block.as_bytes()))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(parse_error)?;
+
+    if certificates.is_empty() {
+        return Err(parse_error().into());
+    }
+
+    debug!(
+        "Loaded {} certificate authorities from {}",
+        certificates.len(),
+        file.display()
+    );
+    Ok(certificates)
+}
+
+/// Split a bundle of PEM-encoded certificates into the individual certificates, ignoring any text
+/// between them
+fn pem_blocks(contents: &str) -> impl Iterator<Item = &str> {
+    contents
+        .match_indices(PEM_BEGIN)
+        .filter_map(move |(start, _)| {
+            contents[start..]
+                .find(PEM_END)
+                .map(|length| &contents[start..start + length + PEM_END.len()])
+        })
+}
+
+/// Extends `Context` for the results of HTTP requests, so that proxy and TLS failures are reported
+/// with their own errors rather than the general error for the request
+pub(crate) trait HttpContext<T> {
+    fn with_http_context<F>(self, url: &str, f: F) -> Fallible<T>
+    where
+        F: FnOnce() -> ErrorKind;
+}
+
+impl<T> HttpContext<T> for Result<T, attohttpc::Error> {
+    fn with_http_context<F>(self, url: &str, f: F) -> Fallible<T>
+    where
+        F: FnOnce() -> ErrorKind,
+    {
+        self.map_err(|error| {
+            let kind = connection_error(&error, url).unwrap_or_else(f);
+            VoltaError::from_source(error, kind)
+        })
+    }
+}
+
+impl<T> HttpContext<T> for Result<T, ArchiveError> {
+    fn with_http_context<F>(self, url: &str, f: F) -> Fallible<T>
+    where
+        F: FnOnce() -> ErrorKind,
+    {
+        self.map_err(|error| {
+            let kind = match &error {
+                ArchiveError::AttohttpcError(inner) => connection_error(inner, url),
+                _ => None,
+            };
+            VoltaError::from_source(error, kind.unwrap_or_else(f))
+        })
+    }
+}
+
+/// The error for a failure to connect through the proxy or to establish a secure connection, if
+/// that is why the request failed
+fn connection_error(error: &attohttpc::Error, url: &str) -> Option<ErrorKind> {
+    match error.kind() {
+        attohttpc::ErrorKind::ConnectNotSupported | attohttpc::ErrorKind::ConnectError { .. } => {
+            Some(ErrorKind::ProxyConnectError {
+                from_url: url.to_string(),
+            })
+        }
+        attohttpc::ErrorKind::Tls(_) => Some(ErrorKind::TlsConnectError {
+            from_url: url.to_string(),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pem_blocks() {
+        let bundle = "# Corporate root
+-----BEGIN CERTIFICATE-----
+MIIBroot
+-----END CERTIFICATE-----
+
+# Corporate intermediate
+-----BEGIN CERTIFICATE-----
+MIIBintermediate
+-----END CERTIFICATE-----
+";
+
+        let blocks: Vec<&str> = pem_blocks(bundle).collect();
+        assert_eq!(
+            blocks,
+            vec![
+                "-----BEGIN CERTIFICATE-----\nMIIBroot\n-----END CERTIFICATE-----",
+                "-----BEGIN CERTIFICATE-----\nMIIBintermediate\n-----END CERTIFICATE-----",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pem_blocks_ignores_truncated() {
+        let bundle = "-----BEGIN CERTIFICATE-----\nMIIBtruncated\n";
+        assert_eq!(pem_blocks(bundle).count(), 0);
+    }
+}
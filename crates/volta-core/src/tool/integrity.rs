@@ -0,0 +1,452 @@
+//! Verifies tarballs from the npm registry against the hashes published in the registry metadata
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt::{self, Display};
+use std::fs::{read_to_string, write, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::http::{self, HttpContext};
+use super::npmrc::authorize;
+use super::registry::{RawDistInfo, RawPackageMetadata, NPM_ABBREVIATED_ACCEPT_HEADER};
+use super::registry_fetch_error;
+use crate::config::allow_unverified;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::remove_file_if_exists;
+use crate::offline::ensure_online;
+use crate::style::tool_version;
+use attohttpc::header::ACCEPT;
+use attohttpc::Response;
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use semver::Version;
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+
+lazy_static! {
+    /// The integrity of each version in the registry metadata fetched so far, by package name
+    static ref PUBLISHED: Mutex<HashMap<(String, Version), Integrity>> = Mutex::new(HashMap::new());
+}
+
+/// The hash algorithms we accept, strongest first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Algorithm {
+    Sha512,
+    Sha384,
+    Sha256,
+    Sha224,
+    Sha1,
+}
+
+impl Algorithm {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha512" => Some(Algorithm::Sha512),
+            "sha384" => Some(Algorithm::Sha384),
+            "sha256" => Some(Algorithm::Sha256),
+            "sha224" => Some(Algorithm::Sha224),
+            "sha1" => Some(Algorithm::Sha1),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm::Sha512 => "sha512",
+            Algorithm::Sha384 => "sha384",
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha224 => "sha224",
+            Algorithm::Sha1 => "sha1",
+        }
+    }
+
+    fn digest_file(self, path: &Path) -> io::Result<Vec<u8>> {
+        let mut file = File::open(path)?;
+        match self {
+            Algorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                io::copy(&mut file, &mut hasher)?;
+                Ok(hasher.finalize().to_vec())
+            }
+            Algorithm::Sha384 => {
+                let mut hasher = Sha384::new();
+                io::copy(&mut file, &mut hasher)?;
+                Ok(hasher.finalize().to_vec())
+            }
+            Algorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                io::copy(&mut file, &mut hasher)?;
+                Ok(hasher.finalize().to_vec())
+            }
+            Algorithm::Sha224 => {
+                let mut hasher = Sha224::new();
+                io::copy(&mut file, &mut hasher)?;
+                Ok(hasher.finalize().to_vec())
+            }
+            Algorithm::Sha1 => {
+                let mut hasher = Sha1::new();
+                io::copy(&mut file, &mut hasher)?;
+                Ok(hasher.finalize().to_vec())
+            }
+        }
+    }
+}
+
+/// The expected hash of a tarball, displayed in Subresource Integrity format
+#[derive(Clone, Debug, PartialEq)]
+pub struct Integrity {
+    algorithm: Algorithm,
+    digest: Vec<u8>,
+}
+
+impl Integrity {
+    /// Determine the integrity published in a registry `dist` entry
+    ///
+    /// We prefer the sha512 hash from `dist.integrity`, falling back to the legacy sha1 hash in
+    /// `dist.shasum` for tarballs published before the registry computed anything stronger.
+    fn from_dist(dist: &RawDistInfo) -> Option<Self> {
+        dist.integrity
+            .as_deref()
+            .and_then(Integrity::parse)
+            .or_else(|| Integrity::from_shasum(&dist.shasum))
+    }
+
+    /// Parse a Subresource Integrity string, keeping the strongest hash that we support
+    ///
+    /// The string may contain several whitespace-separated hashes, each of the form
+    /// `<algorithm>-<base64 digest>[?<options>]`.
+    fn parse(sri: &str) -> Option<Self> {
+        sri.split_whitespace()
+            .filter_map(|entry| {
+                let (algorithm, encoded) = entry.split_once('-')?;
+                let algorithm = Algorithm::from_name(algorithm)?;
+                let encoded = encoded.split('?').next()?;
+                let digest = base64::decode(encoded).ok()?;
+
+                Some(Integrity { algorithm, digest })
+            })
+            .min_by_key(|integrity| integrity.algorithm)
+    }
+
+    fn from_shasum(shasum: &str) -> Option<Self> {
+        hex::decode(shasum)
+            .ok()
+            .filter(|digest| !digest.is_empty())
+            .map(|digest| Integrity {
+                algorithm: Algorithm::Sha1,
+                digest,
+            })
+    }
+
+    /// Parse the hash that corepack allows after the version in the `packageManager` field
+    ///
+    /// The hash has the form `<algorithm>.<hex digest>`, e.g. `sha256.0a1b...`. Corepack accepts
+    /// any of the SHA-1 and SHA-2 algorithms.
+    pub fn from_package_manager_hash(hash: &str) -> Option<Self> {
+        let (algorithm, encoded) = hash.split_once('.')?;
+        let algorithm = Algorithm::from_name(algorithm)?;
+        let digest = hex::decode(encoded)
+            .ok()
+            .filter(|digest| !digest.is_empty())?;
+
+        Some(Integrity { algorithm, digest })
+    }
+
+    /// Parse a hex-encoded SHA-256 checksum, as published in the `SHASUMS256.txt` file of Node
+    pub(crate) fn from_sha256_hex(checksum: &str) -> Option<Self> {
+        hex::decode(checksum)
+            .ok()
+            .filter(|digest| !digest.is_empty())
+            .map(|digest| Integrity {
+                algorithm: Algorithm::Sha256,
+                digest,
+            })
+    }
+
+    fn matches_file(&self, path: &Path) -> bool {
+        self.algorithm
+            .digest_file(path)
+            .map_or(false, |actual| actual == self.digest)
+    }
+
+    /// Check that the downloaded tarball at `path` matches this integrity
+    pub fn verify(&self, tool: &str, version: &Version, path: &Path) -> Fallible<()> {
+        let actual =
+            self.algorithm
+                .digest_file(path)
+                .with_context(|| ErrorKind::ReadArchiveError {
+                    file: path.to_owned(),
+                })?;
+
+        if actual == self.digest {
+            debug!("Verified {} of {}", self.algorithm.name(), path.display());
+            Ok(())
+        } else {
+            Err(ErrorKind::ChecksumMismatch {
+                tool: tool.into(),
+                version: version.to_string(),
+                expected: self.to_string(),
+                actual: Integrity {
+                    algorithm: self.algorithm,
+                    digest: actual,
+                }
+                .to_string(),
+            }
+            .into())
+        }
+    }
+}
+
+impl Display for Integrity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.algorithm.name(),
+            base64::encode(&self.digest)
+        )
+    }
+}
+
+/// Record the integrity of every version in registry metadata, so that a version resolved from
+/// the metadata can be verified without fetching it again
+pub fn record_published(metadata: &RawPackageMetadata) {
+    if let Ok(mut published) = PUBLISHED.lock() {
+        for info in metadata.versions.values() {
+            if let Some(integrity) = Integrity::from_dist(&info.dist) {
+                published.insert((metadata.name.clone(), info.version.clone()), integrity);
+            }
+        }
+    }
+}
+
+fn recorded(package: &str, version: &Version) -> Option<Integrity> {
+    PUBLISHED
+        .lock()
+        .ok()?
+        .get(&(package.to_string(), version.clone()))
+        .cloned()
+}
+
+/// Determine the integrity that a tarball of `package` has to match
+///
+/// The integrity recorded when the version was resolved is used if there is one, and otherwise
+/// it is looked up in the registry metadata at `index_url`, if any. A tarball from the registry
+/// can only be installed without a published integrity if the user allows unverified downloads,
+/// while a tarball from a `distro` hook is installed with a warning.
+pub fn published(
+    tool: &str,
+    package: &str,
+    index_url: Option<&str>,
+    version: &Version,
+    from_registry: bool,
+) -> Fallible<Option<Integrity>> {
+    if let Some(integrity) = recorded(package, version) {
+        return Ok(Some(integrity));
+    }
+
+    if let Some(url) = index_url {
+        match fetch_metadata(tool, url) {
+            Ok(metadata) => record_published(&metadata),
+            Err(error) if allow_unverified() => {
+                debug!("Could not read registry metadata from {}: {}", url, error)
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    match recorded(package, version) {
+        Some(integrity) => Ok(Some(integrity)),
+        None if from_registry && !allow_unverified() => Err(ErrorKind::IntegrityNotFound {
+            tool: tool.into(),
+            version: version.to_string(),
+        }
+        .into()),
+        None => {
+            warn!(
+                "Could not find a published checksum for {}, so the download will not be verified",
+                tool_version(tool, version)
+            );
+            Ok(None)
+        }
+    }
+}
+
+fn fetch_metadata(tool: &str, url: &str) -> Fallible<RawPackageMetadata> {
+    ensure_online(url)?;
+    debug!("Fetching published integrity of {} from {}", tool, url);
+
+    authorize(http::get(url)?, url)?
+        .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::json)
+        .with_http_context(url, registry_fetch_error(tool, url))
+}
+
+/// The file beside a cached archive that records the integrity it was verified against
+pub fn integrity_file(archive: &Path) -> PathBuf {
+    let mut file_name = archive.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".integrity");
+    archive.with_file_name(file_name)
+}
+
+/// Record the integrity that a newly cached archive was verified against
+///
+/// Archives that couldn't be verified have any previous record removed, so that a stale hash
+/// doesn't cause the new archive to be rejected later.
+pub fn save(archive: &Path, integrity: Option<&Integrity>) -> Fallible<()> {
+    let file = integrity_file(archive);
+    match integrity {
+        Some(integrity) => write(&file, integrity.to_string())
+            .with_context(|| ErrorKind::WriteIntegrityError { file }),
+        None => remove_file_if_exists(file),
+    }
+}
+
+/// Re-verify a cached archive against the integrity pinned by the project, if any, or else
+/// against its recorded integrity
+///
+/// An archive without a recorded integrity, such as one cached before integrities were recorded,
+/// is unverified. Unless the user allows unverified downloads, it is rejected so that it will be
+/// downloaded and verified again.
+pub fn cached_archive_is_valid(archive: &Path, pinned: Option<&Integrity>) -> bool {
+    let valid = match pinned {
+        Some(integrity) => integrity.matches_file(archive),
+        None => match read_to_string(integrity_file(archive)) {
+            Ok(recorded) => Integrity::parse(recorded.trim())
+                .map_or(false, |integrity| integrity.matches_file(archive)),
+            Err(_) => {
+                debug!(
+                    "Cached archive at '{}' has no recorded integrity",
+                    archive.display()
+                );
+                return allow_unverified();
+            }
+        },
+    };
+
+    if !valid {
+        debug!(
+            "Cached archive at '{}' does not match its expected integrity",
+            archive.display()
+        );
+    }
+
+    valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dist(shasum: &str, integrity: Option<&str>) -> RawDistInfo {
+        RawDistInfo {
+            shasum: shasum.into(),
+            tarball: String::new(),
+            integrity: integrity.map(String::from),
+        }
+    }
+
+    #[test]
+    fn prefers_sha512_integrity() {
+        let integrity = Integrity::from_dist(&dist(
+            "0a1b2c3d4e5f60718293a4b5c6d7e8f901234567",
+            Some("sha512-3q2+7w=="),
+        ))
+        .unwrap();
+
+        assert_eq!(integrity.algorithm, Algorithm::Sha512);
+        assert_eq!(integrity.digest, vec![0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn falls_back_to_shasum() {
+        let integrity =
+            Integrity::from_dist(&dist("0a1b2c3d4e5f60718293a4b5c6d7e8f901234567", None)).unwrap();
+
+        assert_eq!(integrity.algorithm, Algorithm::Sha1);
+        assert_eq!(integrity.to_string(), "sha1-ChssPU5fYHGCk6S1xtfo+QEjRWc=");
+    }
+
+    #[test]
+    fn empty_dist_has_no_integrity() {
+        assert_eq!(Integrity::from_dist(&dist("", None)), None);
+    }
+
+    #[test]
+    fn parse_keeps_strongest_supported_hash() {
+        let integrity =
+            Integrity::parse("sha1-ChssPU5fYHGCk6S1xtfo+QEjRWc= sha384-AAAA sha512-3q2+7w==?foo")
+                .unwrap();
+
+        assert_eq!(integrity.algorithm, Algorithm::Sha512);
+        assert_eq!(integrity.to_string(), "sha512-3q2+7w==");
+    }
+
+    #[test]
+    fn parses_package_manager_hash() {
+        let integrity = Integrity::from_package_manager_hash("sha256.deadbeef").unwrap();
+
+        assert_eq!(integrity.algorithm, Algorithm::Sha256);
+        assert_eq!(integrity.to_string(), "sha256-3q2+7w==");
+        assert_eq!(
+            Integrity::from_package_manager_hash("sha224.deadbeef")
+                .unwrap()
+                .to_string(),
+            "sha224-3q2+7w=="
+        );
+        assert_eq!(
+            Integrity::from_package_manager_hash("sha384.deadbeef")
+                .unwrap()
+                .to_string(),
+            "sha384-3q2+7w=="
+        );
+        assert_eq!(Integrity::from_package_manager_hash("md5.deadbeef"), None);
+        assert_eq!(
+            Integrity::from_package_manager_hash("sha256-3q2+7w=="),
+            None
+        );
+    }
+
+    #[test]
+    fn published_uses_integrity_recorded_from_metadata() {
+        let metadata: RawPackageMetadata = serde_json::from_str(
+            r#"{
+                "name": "recorded-tool",
+                "dist-tags": {},
+                "versions": {
+                    "1.0.0": {
+                        "version": "1.0.0",
+                        "dist": { "shasum": "", "tarball": "", "integrity": "sha512-3q2+7w==" }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        record_published(&metadata);
+
+        let version = Version::parse("1.0.0").unwrap();
+        let integrity = published("tool", "recorded-tool", None, &version, true).unwrap();
+        assert_eq!(integrity.unwrap().to_string(), "sha512-3q2+7w==");
+    }
+
+    #[test]
+    fn published_without_metadata_warns_for_hooked_distros() {
+        let version = Version::parse("1.0.0").unwrap();
+        assert_eq!(
+            published("tool", "unrecorded-tool", None, &version, false).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn integrity_file_is_beside_archive() {
+        assert_eq!(
+            integrity_file(Path::new("/volta/tools/inventory/npm/npm-6.14.4.tgz")),
+            PathBuf::from("/volta/tools/inventory/npm/npm-6.14.4.tgz.integrity")
+        );
+    }
+}
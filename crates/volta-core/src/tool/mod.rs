@@ -1,17 +1,41 @@
+use std::collections::HashMap;
 use std::fmt::{self, Display};
+use std::io;
+use std::panic::resume_unwind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
 
 use crate::error::{ErrorKind, Fallible};
+use crate::event::record_fetch;
+use crate::fs::{create_staging_file, remove_file_if_exists, rename};
+use crate::layout::volta_home;
+use crate::offline::ensure_online;
 use crate::session::Session;
-use crate::style::{note_prefix, success_prefix, tool_version};
+use crate::style::{note_prefix, progress_bar, success_prefix, tool_version};
 use crate::sync::VoltaLock;
 use crate::version::VersionSpec;
-use log::{debug, info};
+use archive::{ArchiveError, Origin};
+use indicatif::{MultiProgress, ProgressBar};
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use semver::Version;
 
+pub mod export;
+pub(crate) mod http;
+pub(crate) mod integrity;
 pub mod node;
 pub mod npm;
+mod npmrc;
+pub mod outdated;
 pub mod package;
+pub mod pnpm;
+pub mod prune;
 mod registry;
 mod serial;
+mod uninstall;
+pub mod upgrade;
 pub mod yarn;
 
 pub use node::{
@@ -19,9 +43,19 @@ pub use node::{
 };
 pub use npm::{BundledNpm, Npm};
 pub use package::{BinConfig, Package, PackageConfig, PackageManifest};
+pub use pnpm::Pnpm;
 pub use registry::PackageDetails;
 pub use yarn::Yarn;
 
+use http::HttpContext;
+use npmrc::{npmrc, Npmrc};
+use uninstall::InventoryTool;
+
+lazy_static! {
+    /// The archives that `prefetch` downloaded and the fetches haven't used yet, by URL
+    static ref PREFETCHED: Mutex<HashMap<String, PathBuf>> = Mutex::new(HashMap::new());
+}
+
 #[inline]
 fn debug_already_fetched<T: Display + Sized>(tool: T) {
     debug!("{} has already been fetched, skipping download", tool);
@@ -59,6 +93,13 @@ pub trait Tool: Display {
     fn install(self: Box<Self>, session: &mut Session) -> Fallible<()>;
     /// Pin a tool in the local project so that it is usable within the project
     fn pin(self: Box<Self>, session: &mut Session) -> Fallible<()>;
+    /// The archive that fetching the tool would download, if it isn't already available locally
+    ///
+    /// Packages are downloaded by the package manager that installs them, so they have no archive
+    /// to download ahead of time.
+    fn archive_download(&self, _session: &Session) -> Fallible<Option<ArchiveDownload>> {
+        Ok(None)
+    }
 }
 
 /// Specification for a tool and its associated version.
@@ -67,6 +108,7 @@ pub trait Tool: Display {
 pub enum Spec {
     Node(VersionSpec),
     Npm(VersionSpec),
+    Pnpm(VersionSpec),
     Yarn(VersionSpec),
     Package(String, VersionSpec),
 }
@@ -83,36 +125,75 @@ impl Spec {
                 Some(version) => Ok(Box::new(Npm::new(version))),
                 None => Ok(Box::new(BundledNpm)),
             },
+            Spec::Pnpm(version) => {
+                let version = pnpm::resolve(version, session)?;
+                Ok(Box::new(Pnpm::new(version)))
+            }
             Spec::Yarn(version) => {
                 let version = yarn::resolve(version, session)?;
                 Ok(Box::new(Yarn::new(version)))
             }
             // When using global package install, we allow the package manager to perform the version resolution
             Spec::Package(name, version) => {
-                let package = Package::new(name, version)?;
+                let manager = session.config()?.default_package_manager();
+                let package = Package::with_manager(name, version, manager)?;
                 Ok(Box::new(package))
             }
         }
     }
 
+    /// Resolve a tool spec and pin it in the local project
+    ///
+    /// Runtime and package manager versions that don't satisfy the `engines` of the project are
+    /// only pinned when `force` is set.
+    pub fn pin(self, session: &mut Session, force: bool) -> Fallible<()> {
+        let tool: Box<dyn Tool> = match self {
+            Spec::Node(version) => {
+                let version = node::resolve(version, session)?;
+                check_pin_engines("node", &version, session, force)?;
+                Box::new(Node::new(version))
+            }
+            Spec::Npm(version) => match npm::resolve(version, session)? {
+                Some(version) => {
+                    check_pin_engines("npm", &version, session, force)?;
+                    Box::new(Npm::new(version))
+                }
+                None => Box::new(BundledNpm),
+            },
+            Spec::Pnpm(version) => {
+                let version = pnpm::resolve(version, session)?;
+                check_pin_engines("pnpm", &version, session, force)?;
+                Box::new(Pnpm::new(version))
+            }
+            Spec::Yarn(version) => {
+                let version = yarn::resolve(version, session)?;
+                check_pin_engines("yarn", &version, session, force)?;
+                Box::new(Yarn::new(version))
+            }
+            package @ Spec::Package(..) => package.resolve(session)?,
+        };
+
+        tool.pin(session)
+    }
+
     /// Uninstall a tool, removing it from the local inventory
     ///
-    /// This is implemented on Spec, instead of Resolved, because there is currently no need to
-    /// resolve the specific version before uninstalling a tool.
-    pub fn uninstall(self) -> Fallible<()> {
+    /// This is implemented on Spec, instead of Resolved, because uninstalling only ever considers
+    /// versions that are already available locally, so there is no need to resolve against the
+    /// registry. Runtime and package manager versions that are still in use are only removed
+    /// when `force` is set.
+    pub fn uninstall(self, session: &Session, force: bool) -> Fallible<()> {
         match self {
-            Spec::Node(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling node".into(),
+            Spec::Node(version) => {
+                uninstall::uninstall(InventoryTool::Node, version, session, force)
             }
-            .into()),
-            Spec::Npm(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling npm".into(),
+            Spec::Npm(version) => uninstall::uninstall(InventoryTool::Npm, version, session, force),
+            Spec::Pnpm(version) => {
+                uninstall::uninstall(InventoryTool::Pnpm, version, session, force)
             }
-            .into()),
-            Spec::Yarn(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling yarn".into(),
+            Spec::Yarn(version) => {
+                uninstall::uninstall(InventoryTool::Yarn, version, session, force)
             }
-            .into()),
             Spec::Package(name, _) => package::uninstall(&name),
         }
     }
@@ -122,17 +203,52 @@ impl Spec {
         match self {
             Spec::Node(_) => "Node",
             Spec::Npm(_) => "npm",
+            Spec::Pnpm(_) => "pnpm",
             Spec::Yarn(_) => "Yarn",
             Spec::Package(name, _) => name,
         }
     }
 }
 
+/// Check a version that is about to be pinned against the `engines` of the project
+fn check_pin_engines(
+    tool: &str,
+    version: &Version,
+    session: &Session,
+    force: bool,
+) -> Fallible<()> {
+    let project = match session.project()? {
+        Some(project) => project,
+        None => return Ok(()),
+    };
+
+    match project.engines_violation(tool, version) {
+        Some((file, range)) if !force => Err(ErrorKind::PinEnginesMismatch {
+            tool: tool.into(),
+            version: version.to_string(),
+            range: range.into(),
+            file: file.to_owned(),
+        }
+        .into()),
+        Some((file, range)) => {
+            warn!(
+                "Pinning {}, which does not satisfy the engines constraint \"{}\" in {}",
+                tool_version(tool, version),
+                range,
+                file.display()
+            );
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
 impl Display for Spec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             Spec::Node(ref version) => tool_version("node", version),
             Spec::Npm(ref version) => tool_version("npm", version),
+            Spec::Pnpm(ref version) => tool_version("pnpm", version),
             Spec::Yarn(ref version) => tool_version("yarn", version),
             Spec::Package(ref name, ref version) => tool_version(name, version),
         };
@@ -184,6 +300,210 @@ fn download_tool_error(tool: Spec, from_url: impl AsRef<str>) -> impl FnOnce() -
     || ErrorKind::DownloadToolNetworkError { tool, from_url }
 }
 
+/// Download a tool archive in full to the tmp directory, showing a progress bar while it downloads
+///
+/// Archives are downloaded completely, rather than unpacked as they stream in, so that their
+/// checksums can be verified before anything is extracted. The partial file is named after the
+/// archive, so if the download is interrupted, the next attempt to fetch the tool resumes it. An
+/// archive that `prefetch` downloaded from the same URL in this run is used as-is.
+fn download_tool_archive(tool: Spec, from_url: &str, file_name: &str) -> Fallible<StagedArchive> {
+    let prefetched = PREFETCHED
+        .lock()
+        .ok()
+        .and_then(|mut prefetched| prefetched.remove(from_url));
+    if let Some(path) = prefetched {
+        debug!("Using {} downloaded to '{}'", tool, path.display());
+        return Ok(StagedArchive {
+            path,
+            persisted: false,
+        });
+    }
+
+    ensure_online(from_url)?;
+    let tmp_dir = volta_home()?.tmp_dir();
+    let path = tmp_dir.join(format!("{}.partial", file_name));
+    debug!(
+        "Downloading {} from {} to '{}'",
+        tool,
+        from_url,
+        path.display()
+    );
+
+    let progress = progress_bar(Origin::Remote, &tool.to_string(), 0);
+    download_archive(&tool, from_url, &path, &progress, http::client()?, npmrc()?)
+        .with_http_context(from_url, download_tool_error(tool, from_url))?;
+
+    Ok(StagedArchive {
+        path,
+        persisted: false,
+    })
+}
+
+/// Download `from_url` to `dest`, reporting on `progress`, which is cleared once the download
+/// has finished or failed
+///
+/// A successful download is recorded as a fetch event for `tool`, counting only the bytes that
+/// were downloaded this time if a partial download was resumed.
+fn download_archive(
+    tool: &Spec,
+    from_url: &str,
+    dest: &Path,
+    progress: &ProgressBar,
+    client: &attohttpc::Session,
+    npmrc: &Npmrc,
+) -> Result<(), ArchiveError> {
+    let started = Instant::now();
+    let resumed_from = file_size(dest);
+    let result = archive::download(
+        || npmrc.authorize(client.get(from_url), from_url),
+        dest,
+        &mut |downloaded, total| {
+            progress.set_length(total);
+            progress.set_position(downloaded);
+        },
+    );
+    progress.finish_and_clear();
+
+    if result.is_ok() {
+        let bytes = file_size(dest).saturating_sub(resumed_from);
+        record_fetch(tool, bytes, started.elapsed());
+    }
+    result
+}
+
+fn file_size(path: &Path) -> u64 {
+    path.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+}
+
+/// A tool archive that fetching a tool will download, which `prefetch` can download ahead of time
+pub struct ArchiveDownload {
+    tool: Spec,
+    url: String,
+    file_name: String,
+}
+
+/// Download the archives of several tools at the same time, before they are fetched one by one
+///
+/// Each archive is downloaded to a new staging file in the tmp directory, which is handed to
+/// `download_tool_archive` by URL so that it isn't downloaded again, and the fetches themselves
+/// still verify and unpack the tools in order. Nothing is done unless more than one archive needs
+/// to be downloaded. If any download fails, the archives that did finish are removed again.
+pub fn prefetch(tools: &[Box<dyn Tool>], session: &Session) -> Fallible<()> {
+    let mut downloads = Vec::new();
+    for tool in tools {
+        downloads.extend(tool.archive_download(session)?);
+    }
+    if downloads.len() < 2 {
+        return Ok(());
+    }
+
+    for download in &downloads {
+        ensure_online(&download.url)?;
+    }
+
+    // Hold the lock until all of the downloads have finished, so that another Volta process
+    // doesn't write to the same files in the tmp directory at the same time
+    let _lock = VoltaLock::acquire();
+    let tmp_dir = volta_home()?.tmp_dir();
+    let client = http::client()?;
+    let npmrc = npmrc()?;
+    debug!("Downloading {} tool archives concurrently", downloads.len());
+
+    // The finished archives are removed when these are dropped, unless they are all kept below
+    let completed = downloads
+        .iter()
+        .map(|_| create_staging_file().map(|file| file.into_temp_path()))
+        .collect::<Fallible<Vec<_>>>()?;
+
+    let progress = MultiProgress::new();
+    let results = thread::scope(|scope| {
+        let handles: Vec<_> = downloads
+            .iter()
+            .zip(&completed)
+            .map(|(download, complete)| {
+                let bar = progress.add(progress_bar(Origin::Remote, &download.tool.to_string(), 0));
+                let partial = tmp_dir.join(format!("{}.partial", download.file_name));
+
+                scope.spawn(move || -> Result<(), ArchiveError> {
+                    download_archive(&download.tool, &download.url, &partial, &bar, client, npmrc)?;
+                    rename(&partial, complete)?;
+                    Ok(())
+                })
+            })
+            .collect();
+
+        // Draws the progress bars until every download has finished
+        if let Err(error) = progress.join_and_clear() {
+            debug!("Could not draw download progress: {}", error);
+        }
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|panic| resume_unwind(panic)))
+            .collect::<Vec<_>>()
+    });
+
+    // The tools won't be fetched if any download failed, so the archives that did finish are
+    // removed again when `completed` is dropped
+    let urls: Vec<_> = downloads
+        .iter()
+        .map(|download| download.url.clone())
+        .collect();
+    for (download, result) in downloads.into_iter().zip(results) {
+        let ArchiveDownload { tool, url, .. } = download;
+        result.with_http_context(&url, download_tool_error(tool, &url))?;
+    }
+
+    if let Ok(mut prefetched) = PREFETCHED.lock() {
+        for (url, complete) in urls.into_iter().zip(completed) {
+            match complete.keep() {
+                Ok(path) => {
+                    prefetched.insert(url, path);
+                }
+                Err(error) => debug!("Could not keep downloaded archive: {}", error),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A tool archive that has been downloaded in full, but not yet moved into the inventory
+///
+/// The file is removed when this is dropped without being persisted, such as when the archive
+/// fails verification.
+struct StagedArchive {
+    path: PathBuf,
+    persisted: bool,
+}
+
+impl StagedArchive {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Move the archive to `dest`
+    fn persist<P: AsRef<Path>>(mut self, dest: P) -> io::Result<()> {
+        rename(&self.path, dest)?;
+        self.persisted = true;
+        Ok(())
+    }
+}
+
+impl Drop for StagedArchive {
+    fn drop(&mut self) {
+        if !self.persisted {
+            if let Err(error) = remove_file_if_exists(&self.path) {
+                debug!(
+                    "Could not remove staged archive '{}': {}",
+                    self.path.display(),
+                    error
+                );
+            }
+        }
+    }
+}
+
 fn registry_fetch_error(
     tool: impl AsRef<str>,
     from_url: impl AsRef<str>,
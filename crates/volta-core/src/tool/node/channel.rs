@@ -0,0 +1,176 @@
+//! Provides the prerelease channels of Node (nightly builds, release candidates, and the V8 canary
+//! and test builds), which are published separately from the releases in `nodejs.org/dist`
+
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+
+use cfg_if::cfg_if;
+use semver::Version;
+use volta_layout::v3::VoltaHome;
+
+cfg_if! {
+    if #[cfg(feature = "mock-network")] {
+        // TODO: We need to reconsider our mocking strategy in light of mockito deprecating the
+        // SERVER_URL constant: Since our acceptance tests run the binary in a separate process,
+        // we can't use `mockito::server_url()`, which relies on shared memory.
+        #[allow(deprecated)]
+        const SERVER_URL: &str = mockito::SERVER_URL;
+        fn download_server_root() -> String {
+            format!("{}/download", SERVER_URL)
+        }
+    } else {
+        fn download_server_root() -> String {
+            "https://nodejs.org/download".to_string()
+        }
+    }
+}
+
+/// A channel of prerelease Node builds, requested with a tag such as `node@nightly`
+///
+/// The builds are always resolved and downloaded from the Node download server, since mirrors and
+/// hooks are only expected to provide the releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NodeChannel {
+    Nightly,
+    Rc,
+    V8Canary,
+    Test,
+}
+
+impl NodeChannel {
+    const ALL: [NodeChannel; 4] = [
+        NodeChannel::Nightly,
+        NodeChannel::Rc,
+        NodeChannel::V8Canary,
+        NodeChannel::Test,
+    ];
+
+    /// Find the channel requested by a tag, if it is the name of one
+    pub(crate) fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|channel| channel.name() == tag)
+    }
+
+    /// Find the channel that a version was built for, from its prerelease identifier
+    ///
+    /// Channel builds are versioned like `20.0.0-nightly20230301e3b0c44298` or `19.0.0-rc.1`,
+    /// while releases never have a prerelease identifier.
+    pub(crate) fn of(version: &Version) -> Option<Self> {
+        let version = version.to_string();
+        let (_, prerelease) = version.split_once('-')?;
+
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|channel| prerelease.starts_with(channel.name()))
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            NodeChannel::Nightly => "nightly",
+            NodeChannel::Rc => "rc",
+            NodeChannel::V8Canary => "v8-canary",
+            NodeChannel::Test => "test",
+        }
+    }
+
+    /// The root of the channel on the download server, which is laid out like `nodejs.org/dist`
+    pub(super) fn server_root(self) -> String {
+        format!("{}/{}", download_server_root(), self.name())
+    }
+
+    /// The URL of the index of the builds in the channel
+    pub(super) fn index_url(self) -> String {
+        format!("{}/index.json", self.server_root())
+    }
+}
+
+impl Display for NodeChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The directory of the unpacked image for a version of Node
+///
+/// Channel builds are stored apart from the releases, so that they are never among the fetched
+/// versions that semver ranges are matched against.
+pub(crate) fn image_dir(home: &VoltaHome, version: &Version) -> PathBuf {
+    match NodeChannel::of(version) {
+        Some(_) => home.node_channel_image_dir(&version.to_string()),
+        None => home.node_image_dir(&version.to_string()),
+    }
+}
+
+/// The directory of the executables in the image for a version of Node
+pub(crate) fn image_bin_dir(home: &VoltaHome, version: &Version) -> PathBuf {
+    match NodeChannel::of(version) {
+        Some(_) => home.node_channel_image_bin_dir(&version.to_string()),
+        None => home.node_image_bin_dir(&version.to_string()),
+    }
+}
+
+/// The directory that the archive for a version of Node is cached in
+pub(crate) fn inventory_dir<'a>(home: &'a VoltaHome, version: &Version) -> &'a Path {
+    match NodeChannel::of(version) {
+        Some(_) => home.node_channel_inventory_dir(),
+        None => home.node_inventory_dir(),
+    }
+}
+
+/// The file that records the version of npm bundled with a version of Node
+pub(crate) fn npm_version_file(home: &VoltaHome, version: &Version) -> PathBuf {
+    match NodeChannel::of(version) {
+        Some(_) => home.node_channel_npm_version_file(&version.to_string()),
+        None => home.node_npm_version_file(&version.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::{VersionSpec, VersionTag};
+
+    fn channel_of(version: &str) -> Option<NodeChannel> {
+        NodeChannel::of(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn channel_of_version() {
+        assert_eq!(
+            channel_of("20.0.0-nightly20230301e3b0c44298"),
+            Some(NodeChannel::Nightly)
+        );
+        assert_eq!(channel_of("19.0.0-rc.1"), Some(NodeChannel::Rc));
+        assert_eq!(
+            channel_of("20.0.0-v8-canary20221103f7e2421e91"),
+            Some(NodeChannel::V8Canary)
+        );
+        assert_eq!(
+            channel_of("17.0.0-test20210818f6d8a3e00e"),
+            Some(NodeChannel::Test)
+        );
+        assert_eq!(channel_of("18.12.0"), None);
+        assert_eq!(channel_of("18.12.0-pre"), None);
+    }
+
+    #[test]
+    fn channel_from_tag() {
+        assert_eq!(NodeChannel::from_tag("nightly"), Some(NodeChannel::Nightly));
+        assert_eq!(
+            NodeChannel::from_tag("v8-canary"),
+            Some(NodeChannel::V8Canary)
+        );
+        assert_eq!(NodeChannel::from_tag("lts/gallium"), None);
+    }
+
+    #[test]
+    fn channel_tags_parse_as_tags() {
+        for channel in NodeChannel::ALL.iter() {
+            let spec: VersionSpec = channel.name().parse().unwrap();
+            assert!(matches!(spec, VersionSpec::Tag(VersionTag::Custom(_))));
+        }
+    }
+}
@@ -3,13 +3,14 @@
 use std::fs::{read_to_string, write, File};
 use std::path::{Path, PathBuf};
 
-use super::NodeVersion;
+use super::{image_dir, inventory_dir, npm_version_file, shasums, NodeChannel, NodeVersion};
+use crate::config::node_mirror_override;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, create_staging_file, rename};
+use crate::fs::{create_staging_dir, rename};
 use crate::hook::ToolHooks;
 use crate::layout::volta_home;
 use crate::style::{progress_bar, tool_version};
-use crate::tool::{self, download_tool_error, Node};
+use crate::tool::{self, download_tool_archive, integrity, ArchiveDownload, Node};
 use crate::version::{parse_version, VersionSpec};
 use archive::{self, Archive};
 use cfg_if::cfg_if;
@@ -23,17 +24,21 @@ cfg_if! {
         // TODO: We need to reconsider our mocking strategy in light of mockito deprecating the
         // SERVER_URL constant: Since our acceptance tests run the binary in a separate process,
         // we can't use `mockito::server_url()`, which relies on shared memory.
-        fn public_node_server_root() -> String {
+        fn default_node_server_root() -> String {
             #[allow(deprecated)]
             mockito::SERVER_URL.to_string()
         }
     } else {
-        fn public_node_server_root() -> String {
+        fn default_node_server_root() -> String {
             "https://nodejs.org/dist".to_string()
         }
     }
 }
 
+fn public_node_server_root() -> String {
+    node_mirror_override().unwrap_or_else(default_node_server_root)
+}
+
 fn npm_manifest_path(version: &Version) -> PathBuf {
     let mut manifest = PathBuf::from(Node::archive_basename(version));
 
@@ -48,9 +53,7 @@ fn npm_manifest_path(version: &Version) -> PathBuf {
 }
 
 pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<NodeVersion> {
-    let home = volta_home()?;
-    let node_dir = home.node_inventory_dir();
-    let cache_file = node_dir.join(Node::archive_filename(version));
+    let cache_file = inventory_dir(volta_home()?, version).join(Node::archive_filename(version));
 
     let (archive, staging) = match load_cached_distro(&cache_file) {
         Some(archive) => {
@@ -62,31 +65,54 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<Nod
             (archive, None)
         }
         None => {
-            let staging = create_staging_file()?;
             let remote_url = determine_remote_url(version, hooks)?;
-            let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
-            (archive, Some(staging))
+            let staging = download_tool_archive(
+                tool::Spec::Node(VersionSpec::Exact(version.clone())),
+                &remote_url,
+                &Node::archive_filename(version),
+            )?;
+            let integrity = shasums::verify(version, &remote_url, staging.path())?;
+            let archive = load_verified_distro(version, staging.path())?;
+            (archive, Some((staging, integrity)))
         }
     };
 
     let node_version = unpack_archive(archive, version)?;
 
-    if let Some(staging_file) = staging {
+    if let Some((staging_file, integrity)) = staging {
         ensure_containing_dir_exists(&cache_file).with_context(|| {
             ErrorKind::ContainingDirError {
                 path: cache_file.clone(),
             }
         })?;
         staging_file
-            .persist(cache_file)
+            .persist(&cache_file)
             .with_context(|| ErrorKind::PersistInventoryError {
                 tool: "Node".into(),
             })?;
+        integrity::save(&cache_file, integrity.as_ref())?;
     }
 
     Ok(node_version)
 }
 
+/// The archive that `fetch` will download, unless there is a valid one in the inventory already
+pub fn archive_download(
+    version: &Version,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Option<ArchiveDownload>> {
+    let cache_file = inventory_dir(volta_home()?, version).join(Node::archive_filename(version));
+    if load_cached_distro(&cache_file).is_some() {
+        return Ok(None);
+    }
+
+    Ok(Some(ArchiveDownload {
+        tool: tool::Spec::Node(VersionSpec::Exact(version.clone())),
+        url: determine_remote_url(version, hooks)?,
+        file_name: Node::archive_filename(version),
+    }))
+}
+
 /// Unpack the node archive into the image directory so that it is ready for use
 fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<NodeVersion> {
     let temp = create_staging_dir()?;
@@ -115,7 +141,7 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
     let npm = Manifest::version(&npm_package_json)?;
     save_default_npm_version(version, &npm)?;
 
-    let dest = volta_home()?.node_image_dir(&version_string);
+    let dest = image_dir(volta_home()?, version);
     ensure_containing_dir_exists(&dest)
         .with_context(|| ErrorKind::ContainingDirError { path: dest.clone() })?;
 
@@ -140,10 +166,9 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
 }
 
 /// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-// ISSUE(#134) - verify checksum
+/// downloading, or no longer match the checksum it was verified against.
 fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
-    if file.is_file() {
+    if file.is_file() && integrity::cached_archive_is_valid(file, None) {
         let file = File::open(file).ok()?;
         archive::load_native(file).ok()
     } else {
@@ -152,8 +177,19 @@ fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
 }
 
 /// Determine the remote URL to download from, using the hooks if available
+///
+/// Builds from a prerelease channel are always downloaded from the channel.
 fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<String> {
     let distro_file_name = Node::archive_filename(version);
+    if let Some(channel) = NodeChannel::of(version) {
+        return Ok(format!(
+            "{}/v{}/{}",
+            channel.server_root(),
+            version,
+            distro_file_name
+        ));
+    }
+
     match hooks {
         Some(&ToolHooks {
             distro: Some(ref hook),
@@ -171,17 +207,15 @@ fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Node>>) -> F
     }
 }
 
-/// Fetch the distro archive from the internet
-fn fetch_remote_distro(
-    version: &Version,
-    url: &str,
-    staging_path: &Path,
-) -> Fallible<Box<dyn Archive>> {
-    debug!("Downloading {} from {}", tool_version("node", version), url);
-    archive::fetch_native(url, staging_path).with_context(download_tool_error(
-        tool::Spec::Node(VersionSpec::Exact(version.clone())),
-        url,
-    ))
+/// Open the downloaded archive once its checksum has been verified
+fn load_verified_distro(version: &Version, staging_path: &Path) -> Fallible<Box<dyn Archive>> {
+    let unpack_error = || ErrorKind::UnpackArchiveError {
+        tool: "Node".into(),
+        version: version.to_string(),
+    };
+
+    let file = File::open(staging_path).with_context(unpack_error)?;
+    archive::load_native(file).with_context(unpack_error)
 }
 
 /// The portion of npm's `package.json` file that we care about
@@ -202,7 +236,7 @@ impl Manifest {
 
 /// Load the local npm version file to determine the default npm version for a given version of Node
 pub fn load_default_npm_version(node: &Version) -> Fallible<Version> {
-    let npm_version_file_path = volta_home()?.node_npm_version_file(&node.to_string());
+    let npm_version_file_path = npm_version_file(volta_home()?, node);
     let npm_version =
         read_to_string(&npm_version_file_path).with_context(|| ErrorKind::ReadDefaultNpmError {
             file: npm_version_file_path,
@@ -212,7 +246,13 @@ pub fn load_default_npm_version(node: &Version) -> Fallible<Version> {
 
 /// Save the default npm version to the filesystem for a given version of Node
 fn save_default_npm_version(node: &Version, npm: &Version) -> Fallible<()> {
-    let npm_version_file_path = volta_home()?.node_npm_version_file(&node.to_string());
+    let npm_version_file_path = npm_version_file(volta_home()?, node);
+    // Volta directories created before channel support won't have the channel inventory yet
+    ensure_containing_dir_exists(&npm_version_file_path).with_context(|| {
+        ErrorKind::ContainingDirError {
+            path: npm_version_file_path.clone(),
+        }
+    })?;
     write(&npm_version_file_path, npm.to_string().as_bytes()).with_context(|| {
         ErrorKind::WriteDefaultNpmError {
             file: npm_version_file_path,
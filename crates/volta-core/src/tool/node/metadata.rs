@@ -15,7 +15,8 @@ pub struct NodeIndex {
 #[derive(Debug)]
 pub struct NodeEntry {
     pub version: Version,
-    pub lts: bool,
+    /// The codename of the LTS line this version belongs to, e.g. `Gallium`
+    pub lts: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -30,7 +31,7 @@ pub struct RawNodeEntry {
     npm: Option<Version>,
     files: HashSet<String>,
     #[serde(deserialize_with = "lts_version_serde")]
-    lts: bool,
+    lts: Option<String>,
 }
 
 impl From<RawNodeIndex> for NodeIndex {
@@ -69,12 +70,12 @@ impl From<RawNodeIndex> for NodeIndex {
 }
 
 #[allow(clippy::unnecessary_wraps)] // Needs to match the API expected by Serde
-fn lts_version_serde<'de, D>(deserializer: D) -> Result<bool, D::Error>
+fn lts_version_serde<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
     match String::deserialize(deserializer) {
-        Ok(_) => Ok(true),
-        Err(_) => Ok(false),
+        Ok(codename) => Ok(Some(codename)),
+        Err(_) => Ok(None),
     }
 }
@@ -2,7 +2,7 @@ use std::fmt::{self, Display};
 
 use super::{
     check_fetched, debug_already_fetched, info_fetched, info_installed, info_pinned,
-    info_project_version, FetchStatus, Tool,
+    info_project_version, ArchiveDownload, FetchStatus, Tool,
 };
 use crate::error::{ErrorKind, Fallible};
 use crate::inventory::node_available;
@@ -13,12 +13,17 @@ use cfg_if::cfg_if;
 use log::info;
 use semver::Version;
 
+mod channel;
 mod fetch;
 mod metadata;
 mod resolve;
+mod shasums;
+
+pub(crate) use channel::{image_bin_dir, image_dir, inventory_dir, npm_version_file, NodeChannel};
 
 pub use fetch::load_default_npm_version;
-pub use resolve::resolve;
+pub(crate) use resolve::resolve_available;
+pub use resolve::{resolve, resolve_local, resolve_with_hooks};
 
 cfg_if! {
     if #[cfg(all(target_os = "windows", target_arch = "x86"))] {
@@ -244,6 +249,13 @@ impl Tool for Node {
             Err(ErrorKind::NotInPackage.into())
         }
     }
+    fn archive_download(&self, session: &Session) -> Fallible<Option<ArchiveDownload>> {
+        if node_available(&self.version)? {
+            return Ok(None);
+        }
+
+        fetch::archive_download(&self.version, session.hooks()?.node())
+    }
 }
 
 impl Display for Node {
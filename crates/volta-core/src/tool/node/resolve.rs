@@ -1,16 +1,24 @@
 //! Provides resolution of Node requirements into specific versions, using the NodeJS index
 
+use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::Write;
 use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 
+use super::super::http::{self, HttpContext};
+use super::super::outdated::Available;
 use super::super::registry_fetch_error;
 use super::metadata::{NodeEntry, NodeIndex, RawNodeIndex};
+use super::NodeChannel;
+use crate::config::{index_cache_ttl, node_mirror_override};
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::{create_staging_file, read_file};
 use crate::hook::ToolHooks;
+use crate::inventory::{node_channel_versions, node_versions};
 use crate::layout::volta_home;
+use crate::offline::{ensure_online, is_offline, resolve_fetched};
+use crate::policy::{find_fetched, ResolutionPolicy};
 use crate::session::Session;
 use crate::style::progress_spinner;
 use crate::tool::Node;
@@ -23,7 +31,6 @@ use hyperx::header::{CacheControl, CacheDirective, Expires, HttpDate, TypedHeade
 use log::debug;
 use semver::{Version, VersionReq};
 
-// ISSUE (#86): Move public repository URLs to config file
 cfg_if! {
     if #[cfg(feature = "mock-network")] {
         // TODO: We need to reconsider our mocking strategy in light of mockito deprecating the
@@ -31,28 +38,270 @@ cfg_if! {
         // we can't use `mockito::server_url()`, which relies on shared memory.
         #[allow(deprecated)]
         const SERVER_URL: &str = mockito::SERVER_URL;
-        fn public_node_version_index() -> String {
+        fn default_node_version_index() -> String {
             format!("{}/node-dist/index.json", SERVER_URL)
         }
     } else {
         /// Returns the URL of the index of available Node versions on the public Node server.
-        fn public_node_version_index() -> String {
+        fn default_node_version_index() -> String {
             "https://nodejs.org/dist/index.json".to_string()
         }
     }
 }
 
+/// Returns the URL of the index of available Node versions, using the configured mirror if any
+fn public_node_version_index() -> String {
+    match node_mirror_override() {
+        Some(mirror) => format!("{}/index.json", mirror),
+        None => default_node_version_index(),
+    }
+}
+
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
-    let hooks = session.hooks()?.node();
+    resolve_with_hooks(matching, session.hooks()?.node())
+}
+
+/// Resolve a Node version using the given hooks, for callers that don't have a `Session`
+pub fn resolve_with_hooks(
+    matching: VersionSpec,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks),
+        matching if is_offline() => resolve_offline(matching),
+        VersionSpec::Semver(requirement) => {
+            match find_fetched("node", &requirement, node_versions)? {
+                Some(version) => Ok(version),
+                None => resolve_semver(requirement, hooks),
+            }
+        }
         VersionSpec::Exact(version) => Ok(version),
         VersionSpec::None | VersionSpec::Tag(VersionTag::Lts) => resolve_lts(hooks),
         VersionSpec::Tag(VersionTag::Latest) => resolve_latest(hooks),
-        // Node doesn't have "tagged" versions (apart from 'latest' and 'lts'), so custom tags will always be an error
-        VersionSpec::Tag(VersionTag::Custom(tag)) => {
-            Err(ErrorKind::NodeVersionNotFound { matching: tag }.into())
+        VersionSpec::Tag(VersionTag::Custom(tag)) => resolve_alias(tag, hooks),
+    }
+}
+
+/// Resolve a Node version to a fetched version, if the `prefer-local` policy is active
+///
+/// Aliases like `lts/*` only match the fetched versions that the cached copy of the Node index
+/// (even if it has expired) places in the right line. Under the default policy nothing is
+/// resolved, so that aliases always resolve to the newest matching version in the index.
+pub fn resolve_local(matching: &VersionSpec) -> Fallible<Option<Version>> {
+    if ResolutionPolicy::current() != ResolutionPolicy::PreferLocal {
+        return Ok(None);
+    }
+
+    let matches_entry: Box<dyn Fn(&NodeEntry) -> bool + '_> = match matching {
+        VersionSpec::Exact(version) => return Ok(Some(version.clone())),
+        VersionSpec::Semver(requirement) => {
+            Box::new(move |entry: &NodeEntry| requirement.matches(&entry.version))
         }
+        VersionSpec::None | VersionSpec::Tag(VersionTag::Lts) => {
+            Box::new(|entry: &NodeEntry| entry.lts.is_some())
+        }
+        VersionSpec::Tag(VersionTag::Latest) => Box::new(|_: &NodeEntry| true),
+        VersionSpec::Tag(VersionTag::Custom(alias)) => match alias.as_str() {
+            "node" | "stable" | "current" => Box::new(|_: &NodeEntry| true),
+            "lts/*" => Box::new(|entry: &NodeEntry| entry.lts.is_some()),
+            _ => match alias.strip_prefix("lts/") {
+                Some(codename) => Box::new(move |entry: &NodeEntry| {
+                    entry
+                        .lts
+                        .as_deref()
+                        .map_or(false, |lts| lts.eq_ignore_ascii_case(codename))
+                }),
+                None => {
+                    return Ok(NodeChannel::from_tag(alias)
+                        .and_then(|channel| resolve_fetched_channel(channel).ok()))
+                }
+            },
+        },
+    };
+
+    let fetched = node_versions()?;
+    let cached = read_cached_index()?.map_or_else(Vec::new, |index| index.entries);
+
+    // Fetched versions can be matched against a requirement directly, but are only known to be
+    // part of an LTS line from the index
+    let newest_fetched = match matching {
+        VersionSpec::Semver(requirement) => fetched
+            .iter()
+            .rev()
+            .find(|version| requirement.matches(version))
+            .cloned(),
+        _ => None,
+    };
+
+    let version_opt = newest_fetched.or_else(|| {
+        cached
+            .iter()
+            .find(|entry| fetched.contains(&entry.version) && matches_entry(entry))
+            .map(|entry| entry.version.clone())
+    });
+
+    if let Some(version) = &version_opt {
+        debug!("Found node@{} matching '{}' locally", version, matching);
+    }
+    Ok(version_opt)
+}
+
+/// Resolve a Node version from the local inventory, without accessing the network
+///
+/// The inventory doesn't record which versions are LTS releases, so LTS requests are matched
+/// against the cached copy of the Node index, even if it has expired.
+fn resolve_offline(matching: VersionSpec) -> Fallible<Version> {
+    let fetched = node_versions()?;
+
+    match matching {
+        VersionSpec::Tag(VersionTag::Lts) | VersionSpec::None => {
+            resolve_fetched_lts(fetched, |_| true, "lts".into())
+        }
+        VersionSpec::Tag(VersionTag::Custom(alias)) => match alias.as_str() {
+            "node" | "stable" | "current" => resolve_fetched("node", VersionSpec::None, fetched),
+            "lts/*" => resolve_fetched_lts(fetched, |_| true, alias),
+            _ => match alias.strip_prefix("lts/") {
+                Some(codename) => {
+                    let codename = codename.to_string();
+                    resolve_fetched_lts(fetched, |line| line.eq_ignore_ascii_case(&codename), alias)
+                }
+                None => match NodeChannel::from_tag(&alias) {
+                    Some(channel) => resolve_fetched_channel(channel),
+                    None => Err(ErrorKind::NodeVersionNotFound { matching: alias }.into()),
+                },
+            },
+        },
+        matching => resolve_fetched("node", matching, fetched),
+    }
+}
+
+/// Find the newest fetched Node version in an LTS line accepted by `line_matches`
+fn resolve_fetched_lts(
+    fetched: BTreeSet<Version>,
+    line_matches: impl Fn(&str) -> bool,
+    matching: String,
+) -> Fallible<Version> {
+    let version_opt = read_cached_index()?.and_then(|index| {
+        index
+            .entries
+            .into_iter()
+            .find(|NodeEntry { version, lts }| {
+                fetched.contains(version) && lts.as_deref().map_or(false, &line_matches)
+            })
+            .map(|NodeEntry { version, .. }| version)
+    });
+
+    match version_opt {
+        Some(version) => {
+            debug!("Found fetched node@{} matching '{}'", version, matching);
+            Ok(version)
+        }
+        None => Err(ErrorKind::OfflineVersionNotFound {
+            tool: "node".into(),
+            matching,
+        }
+        .into()),
+    }
+}
+
+/// Find the newest fetched build from a prerelease channel
+fn resolve_fetched_channel(channel: NodeChannel) -> Fallible<Version> {
+    let version_opt = node_channel_versions()?
+        .into_iter()
+        .rev()
+        .find(|version| NodeChannel::of(version) == Some(channel));
+
+    match version_opt {
+        Some(version) => {
+            debug!("Found fetched node@{} matching '{}'", version, channel);
+            Ok(version)
+        }
+        None => Err(ErrorKind::OfflineVersionNotFound {
+            tool: "node".into(),
+            matching: channel.to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Determine the newest Node versions that `current` can be upgraded to
+///
+/// Both versions come from a single read of the Node index, so the cached copy of the index is
+/// used unless it has expired.
+pub(crate) fn resolve_available(
+    current: &Version,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Available> {
+    let url = match hooks {
+        Some(&ToolHooks {
+            index: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using node.index hook to determine node index URL");
+            hook.resolve("index.json")?
+        }
+        _ => public_node_version_index(),
+    };
+    let index: NodeIndex = resolve_node_versions(&url)?.into();
+
+    // NOTE: As in `resolve_latest`, this assumes the index is sorted from newest to oldest
+    let latest = match index.entries.first() {
+        Some(entry) => entry.version.clone(),
+        None => {
+            return Err(ErrorKind::NodeVersionNotFound {
+                matching: "latest".into(),
+            }
+            .into())
+        }
+    };
+
+    Ok(Available::new(
+        current,
+        index.entries.iter().map(|entry| &entry.version),
+        latest,
+    ))
+}
+
+/// Resolve the aliases that nvm supports, which are commonly found in `.nvmrc` files, and the
+/// names of the prerelease channels
+///
+/// Node doesn't have "tagged" versions (apart from 'latest' and 'lts'), so any other custom tag
+/// will always be an error
+fn resolve_alias(alias: String, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
+    match alias.as_str() {
+        "node" | "stable" | "current" => resolve_latest(hooks),
+        "lts/*" => resolve_lts(hooks),
+        _ => match alias.strip_prefix("lts/") {
+            Some(codename) => resolve_lts_codename(codename, hooks),
+            None => match NodeChannel::from_tag(&alias) {
+                Some(channel) => resolve_channel(channel),
+                None => Err(ErrorKind::NodeVersionNotFound { matching: alias }.into()),
+            },
+        },
+    }
+}
+
+/// Resolve the newest build in a prerelease channel
+fn resolve_channel(channel: NodeChannel) -> Fallible<Version> {
+    // NOTE: As in `resolve_latest`, this assumes the index is sorted from newest to oldest
+    let url = channel.index_url();
+    let version_opt = fetch_channel_index(&url)?
+        .entries
+        .into_iter()
+        .next()
+        .map(|NodeEntry { version, .. }| version);
+
+    match version_opt {
+        Some(version) => {
+            debug!(
+                "Found newest node version ({}) in channel '{}' from {}",
+                version, channel, url
+            );
+            Ok(version)
+        }
+        None => Err(ErrorKind::NodeVersionNotFound {
+            matching: channel.to_string(),
+        }
+        .into()),
     }
 }
 
@@ -95,7 +344,7 @@ fn resolve_lts(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
         }
         _ => public_node_version_index(),
     };
-    let version_opt = match_node_version(&url, |&NodeEntry { lts, .. }| lts)?;
+    let version_opt = match_node_version(&url, |NodeEntry { lts, .. }| lts.is_some())?;
 
     match version_opt {
         Some(version) => {
@@ -109,6 +358,37 @@ fn resolve_lts(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
     }
 }
 
+fn resolve_lts_codename(codename: &str, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
+    let url = match hooks {
+        Some(&ToolHooks {
+            index: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using node.index hook to determine node index URL");
+            hook.resolve("index.json")?
+        }
+        _ => public_node_version_index(),
+    };
+    let version_opt = match_node_version(&url, |NodeEntry { lts, .. }| {
+        lts.as_deref()
+            .map_or(false, |lts| lts.eq_ignore_ascii_case(codename))
+    })?;
+
+    match version_opt {
+        Some(version) => {
+            debug!(
+                "Found newest node version ({}) in LTS line '{}' from {}",
+                version, codename, url
+            );
+            Ok(version)
+        }
+        None => Err(ErrorKind::NodeVersionNotFound {
+            matching: format!("lts/{}", codename),
+        }
+        .into()),
+    }
+}
+
 fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
     let url = match hooks {
         Some(&ToolHooks {
@@ -149,6 +429,23 @@ fn match_node_version(
         .map(|NodeEntry { version, .. }| version))
 }
 
+/// Fetches the index of a prerelease channel
+///
+/// Channel indexes aren't cached, since the cache only holds a single index and it is needed for
+/// resolving releases offline.
+fn fetch_channel_index(url: &str) -> Fallible<NodeIndex> {
+    ensure_online(url)?;
+    let spinner = progress_spinner(format!("Fetching public registry: {}", url));
+    let index: RawNodeIndex = http::get(url)?
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::json)
+        .with_http_context(url, registry_fetch_error("Node", url))?;
+
+    spinner.finish_and_clear();
+    Ok(index.into())
+}
+
 /// Reads a public index from the Node cache, if it exists and hasn't expired.
 fn read_cached_opt(url: &str) -> Fallible<Option<RawNodeIndex>> {
     let expiry_file = volta_home()?.node_index_expiry_file();
@@ -180,6 +477,25 @@ fn read_cached_opt(url: &str) -> Fallible<Option<RawNodeIndex>> {
     Ok(None)
 }
 
+/// Reads the cached Node index regardless of its expiry, if there is one
+fn read_cached_index() -> Fallible<Option<NodeIndex>> {
+    let index_file = volta_home()?.node_index_file();
+    let cached = read_file(&index_file).with_context(|| ErrorKind::ReadNodeIndexCacheError {
+        file: index_file.to_owned(),
+    })?;
+
+    // The first line of the cache is the URL that the index was fetched from
+    match cached
+        .as_deref()
+        .and_then(|content| content.split_once('\n'))
+    {
+        Some((_, json)) => serde_json::de::from_str::<RawNodeIndex>(json)
+            .with_context(|| ErrorKind::ParseNodeIndexCacheError)
+            .map(|raw| Some(raw.into())),
+        None => Ok(None),
+    }
+}
+
 /// Get the cache max-age of an HTTP reponse.
 fn max_age(headers: &HeaderMap) -> u32 {
     if let Ok(cache_control_header) = headers.decode::<CacheControl>() {
@@ -202,15 +518,19 @@ fn resolve_node_versions(url: &str) -> Fallible<RawNodeIndex> {
         }
         None => {
             debug!("Node index cache was not found or was invalid");
+            ensure_online(url)?;
             let spinner = progress_spinner(format!("Fetching public registry: {}", url));
 
-            let (_, headers, response) = attohttpc::get(url)
+            let (_, headers, response) = http::get(url)?
                 .send()
                 .and_then(Response::error_for_status)
-                .with_context(registry_fetch_error("Node", url))?
+                .with_http_context(url, registry_fetch_error("Node", url))?
                 .split();
 
-            let expires = if let Ok(expires_header) = headers.decode::<Expires>() {
+            // A configured cache TTL takes precedence over the caching headers from the server
+            let expires = if let Some(ttl) = index_cache_ttl() {
+                HttpDate::from(SystemTime::now() + ttl).to_string()
+            } else if let Ok(expires_header) = headers.decode::<Expires>() {
                 expires_header.to_string()
             } else {
                 let expiry_date = SystemTime::now() + Duration::from_secs(max_age(&headers).into());
@@ -219,7 +539,7 @@ fn resolve_node_versions(url: &str) -> Fallible<RawNodeIndex> {
 
             let response_text = response
                 .text()
-                .with_context(registry_fetch_error("Node", url))?;
+                .with_http_context(url, registry_fetch_error("Node", url))?;
 
             let index: RawNodeIndex =
                 serde_json::de::from_str(&response_text).with_context(|| {
@@ -0,0 +1,270 @@
+//! Verifies Node distro archives against the `SHASUMS256.txt` file published alongside them
+
+use std::env;
+use std::fs::{read_to_string, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use super::super::http::{self, HttpContext};
+use crate::command::create_command;
+use crate::config::allow_unverified;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::create_staging_file;
+use crate::offline::ensure_online;
+use crate::tool::integrity::Integrity;
+use crate::tool::Node;
+use attohttpc::{Response, StatusCode};
+use log::{debug, warn};
+use semver::Version;
+use sha2::{Digest, Sha256};
+
+const SHASUMS_FILE: &str = "SHASUMS256.txt";
+
+/// Environment variable that opts in to requiring a signed `SHASUMS256.txt`
+const SIGNATURE_VAR: &str = "VOLTA_SHASUMS_SIGNATURE";
+
+/// Environment variable holding the minisign public key used to verify `SHASUMS256.txt`
+const MINISIGN_KEY_VAR: &str = "VOLTA_MINISIGN_PUBLIC_KEY";
+
+/// The tool used to verify the signature of the `SHASUMS256.txt` file
+enum Verifier {
+    Gpg,
+    Minisign { public_key: String },
+}
+
+impl Verifier {
+    /// Determine which verifier, if any, the user has opted in to
+    fn from_env() -> Fallible<Option<Self>> {
+        match env::var(SIGNATURE_VAR) {
+            Ok(value) if value.is_empty() => Ok(None),
+            Ok(value) if value == "gpg" => Ok(Some(Verifier::Gpg)),
+            Ok(value) if value == "minisign" => match env::var(MINISIGN_KEY_VAR) {
+                Ok(public_key) if !public_key.is_empty() => {
+                    Ok(Some(Verifier::Minisign { public_key }))
+                }
+                _ => Err(ErrorKind::MissingMinisignPublicKey.into()),
+            },
+            Ok(value) => Err(ErrorKind::InvalidSignatureVerifier { value }.into()),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verifier::Gpg => "gpg",
+            Verifier::Minisign { .. } => "minisign",
+        }
+    }
+
+    /// The extension of the detached signature published next to `SHASUMS256.txt`
+    fn signature_extension(&self) -> &'static str {
+        match self {
+            Verifier::Gpg => "sig",
+            Verifier::Minisign { .. } => "minisig",
+        }
+    }
+
+    fn verify(&self, shasums_url: &str, shasums: &Path) -> Fallible<()> {
+        let signature_url = format!("{}.{}", shasums_url, self.signature_extension());
+        let signature = create_staging_file()?;
+        if !download(&signature_url, signature.path())? {
+            return Err(ErrorKind::DownloadChecksumsError {
+                from_url: signature_url,
+            }
+            .into());
+        }
+
+        let mut command = create_command(self.name());
+        match self {
+            Verifier::Gpg => {
+                command.arg("--verify").arg(signature.path()).arg(shasums);
+            }
+            Verifier::Minisign { public_key } => {
+                command
+                    .arg("-V")
+                    .arg("-P")
+                    .arg(public_key)
+                    .arg("-x")
+                    .arg(signature.path())
+                    .arg("-m")
+                    .arg(shasums);
+            }
+        }
+
+        debug!("Verifying signature of {} with {:?}", shasums_url, command);
+        let signature_error = || ErrorKind::ShasumsSignatureError {
+            from_url: shasums_url.to_string(),
+            verifier: self.name().to_string(),
+        };
+        let output = command.output().with_context(signature_error)?;
+        debug!("{}", String::from_utf8_lossy(&output.stderr));
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(signature_error().into())
+        }
+    }
+}
+
+/// Verify the downloaded archive at `archive` against the checksums published next to `distro_url`
+///
+/// The checksum file is expected in the same directory as the archive, and the download fails if
+/// it is missing. Mirrors and `distro` hooks don't always publish one, so users of those can allow
+/// unverified downloads, in which case the archive is used with a warning unless the user has also
+/// opted in to requiring a signed checksum file.
+///
+/// Returns the checksum that the archive was verified against, if any, so that it can be recorded
+/// alongside the cached archive.
+pub(super) fn verify(
+    version: &Version,
+    distro_url: &str,
+    archive: &Path,
+) -> Fallible<Option<Integrity>> {
+    let url = shasums_url(distro_url);
+    let file_name = Node::archive_filename(version);
+    let verifier = Verifier::from_env()?;
+
+    let shasums = create_staging_file()?;
+    if !download(&url, shasums.path())? {
+        if verifier.is_some() || !allow_unverified() {
+            return Err(ErrorKind::DownloadChecksumsError { from_url: url }.into());
+        }
+
+        warn!(
+            "Could not verify the download of node@{}: no checksums were found at {}",
+            version, url
+        );
+        return Ok(None);
+    }
+
+    if let Some(verifier) = verifier {
+        verifier.verify(&url, shasums.path())?;
+    }
+
+    let contents =
+        read_to_string(shasums.path()).with_context(|| ErrorKind::DownloadChecksumsError {
+            from_url: url.clone(),
+        })?;
+    let expected =
+        find_checksum(&contents, &file_name).ok_or_else(|| ErrorKind::ChecksumNotFound {
+            file: file_name.clone(),
+            from_url: url.clone(),
+        })?;
+
+    let actual = sha256_file(archive).with_context(|| ErrorKind::ReadArchiveError {
+        file: archive.to_owned(),
+    })?;
+
+    if actual.eq_ignore_ascii_case(expected) {
+        debug!("Verified checksum of {} against {}", file_name, url);
+        Ok(Integrity::from_sha256_hex(expected))
+    } else {
+        Err(ErrorKind::ChecksumMismatch {
+            tool: "node".into(),
+            version: version.to_string(),
+            expected: expected.to_string(),
+            actual,
+        }
+        .into())
+    }
+}
+
+/// Download a small text file, such as the checksums or their signature, to `dest`
+///
+/// Returns `false` if the server doesn't have the file.
+fn download(url: &str, dest: &Path) -> Fallible<bool> {
+    ensure_online(url)?;
+    debug!("Downloading {}", url);
+    let download_error = || ErrorKind::DownloadChecksumsError {
+        from_url: url.to_string(),
+    };
+
+    let response = http::get(url)?
+        .send()
+        .with_http_context(url, download_error)?;
+    if response.status() == StatusCode::NOT_FOUND {
+        debug!("{} was not found", url);
+        return Ok(false);
+    }
+
+    let mut response = response
+        .error_for_status()
+        .with_http_context(url, download_error)?;
+
+    File::create(dest)
+        .and_then(|mut file| io::copy(&mut response, &mut file).and_then(|_| file.flush()))
+        .with_context(download_error)
+        .map(|_| true)
+}
+
+/// The URL of `SHASUMS256.txt` in the same directory as the distro archive
+fn shasums_url(distro_url: &str) -> String {
+    match distro_url.rfind('/') {
+        Some(index) => format!("{}/{}", &distro_url[..index], SHASUMS_FILE),
+        None => SHASUMS_FILE.to_string(),
+    }
+}
+
+/// Find the checksum for `file_name` in the contents of a `SHASUMS256.txt` file
+///
+/// Each line has the form `<hex digest>  <file name>`, matching the output of `sha256sum`.
+fn find_checksum<'a>(contents: &'a str, file_name: &str) -> Option<&'a str> {
+    contents.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let checksum = parts.next()?;
+        let name = parts.next()?.trim_start_matches('*');
+
+        if name == file_name {
+            Some(checksum)
+        } else {
+            None
+        }
+    })
+}
+
+fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHASUMS: &str = "\
+2ff2d4d4a2ce2e9dcd5dc2bd5cd8ee3ae3e8f3d6a8e4a54d8e1a3d4c4a1ef5b7  node-v14.17.0-darwin-x64.tar.gz
+7e2a3cf4b4c83fcd2d1c1bc7b38a5d2b9c6b2f8fb1c3c2b7fd1e4e0f0c8a2d11  node-v14.17.0-linux-x64.tar.gz
+4c3e1f9b1b2fa0d0b6f55d4d1c4f6d5e3b0b5a2c8cf1b8b8d0e0a6f3c1d2e3f4 *node-v14.17.0-win-x64.zip
+";
+
+    #[test]
+    fn finds_checksum_for_file() {
+        assert_eq!(
+            find_checksum(SHASUMS, "node-v14.17.0-linux-x64.tar.gz"),
+            Some("7e2a3cf4b4c83fcd2d1c1bc7b38a5d2b9c6b2f8fb1c3c2b7fd1e4e0f0c8a2d11")
+        );
+        assert_eq!(
+            find_checksum(SHASUMS, "node-v14.17.0-win-x64.zip"),
+            Some("4c3e1f9b1b2fa0d0b6f55d4d1c4f6d5e3b0b5a2c8cf1b8b8d0e0a6f3c1d2e3f4")
+        );
+        assert_eq!(
+            find_checksum(SHASUMS, "node-v14.17.0-linux-arm64.tar.gz"),
+            None
+        );
+    }
+
+    #[test]
+    fn shasums_url_replaces_file_name() {
+        assert_eq!(
+            shasums_url("https://nodejs.org/dist/v14.17.0/node-v14.17.0-linux-x64.tar.gz"),
+            "https://nodejs.org/dist/v14.17.0/SHASUMS256.txt"
+        );
+        assert_eq!(
+            shasums_url("https://mirror.example.com/node/14.17.0/node.tgz"),
+            "https://mirror.example.com/node/14.17.0/SHASUMS256.txt"
+        );
+    }
+}
@@ -3,10 +3,12 @@
 use std::fs::{write, File};
 use std::path::Path;
 
-use super::super::download_tool_error;
+use super::super::integrity::{self, Integrity};
 use super::super::registry::public_registry_package;
+use super::super::{download_tool_archive, ArchiveDownload, StagedArchive};
+use super::resolve;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, create_staging_file, rename, set_executable};
+use crate::fs::{create_staging_dir, rename, set_executable};
 use crate::hook::ToolHooks;
 use crate::layout::volta_home;
 use crate::style::{progress_bar, tool_version};
@@ -17,11 +19,16 @@ use fs_utils::ensure_containing_dir_exists;
 use log::debug;
 use semver::Version;
 
-pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<()> {
+/// Fetch a version of npm, verifying it against `pinned` if the project pins its hash
+pub fn fetch(
+    version: &Version,
+    hooks: Option<&ToolHooks<Npm>>,
+    pinned: Option<&Integrity>,
+) -> Fallible<()> {
     let npm_dir = volta_home()?.npm_inventory_dir();
     let cache_file = npm_dir.join(Npm::archive_filename(&version.to_string()));
 
-    let (archive, staging) = match load_cached_distro(&cache_file) {
+    let (archive, staging) = match load_cached_distro(&cache_file, pinned) {
         Some(archive) => {
             debug!(
                 "Loading {} from cached archive at '{}'",
@@ -31,29 +38,58 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<()>
             (archive, None)
         }
         None => {
-            let staging = create_staging_file()?;
             let remote_url = determine_remote_url(version, hooks)?;
-            let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
-            (archive, Some(staging))
+            let integrity = match pinned {
+                Some(integrity) => Some(integrity.clone()),
+                None => integrity::published(
+                    "npm",
+                    "npm",
+                    Some(&resolve::index_url(hooks)?),
+                    version,
+                    hooks.map_or(true, |hooks| hooks.distro.is_none()),
+                )?,
+            };
+            let (archive, staging) = fetch_remote_distro(version, &remote_url, integrity.as_ref())?;
+            (archive, Some((staging, integrity)))
         }
     };
 
     unpack_archive(archive, version)?;
 
-    if let Some(staging_file) = staging {
+    if let Some((staging_file, integrity)) = staging {
         ensure_containing_dir_exists(&cache_file).with_context(|| {
             ErrorKind::ContainingDirError {
                 path: cache_file.clone(),
             }
         })?;
         staging_file
-            .persist(cache_file)
+            .persist(&cache_file)
             .with_context(|| ErrorKind::PersistInventoryError { tool: "npm".into() })?;
+        integrity::save(&cache_file, integrity.as_ref())?;
     }
 
     Ok(())
 }
 
+/// The archive that `fetch` will download, unless there is a valid one in the inventory already
+pub fn archive_download(
+    version: &Version,
+    hooks: Option<&ToolHooks<Npm>>,
+    pinned: Option<&Integrity>,
+) -> Fallible<Option<ArchiveDownload>> {
+    let file_name = Npm::archive_filename(&version.to_string());
+    let cache_file = volta_home()?.npm_inventory_dir().join(&file_name);
+    if load_cached_distro(&cache_file, pinned).is_some() {
+        return Ok(None);
+    }
+
+    Ok(Some(ArchiveDownload {
+        tool: tool::Spec::Npm(VersionSpec::Exact(version.clone())),
+        url: determine_remote_url(version, hooks)?,
+        file_name,
+    }))
+}
+
 /// Unpack the npm archive into the image directory so that it is ready for use
 fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()> {
     let temp = create_staging_dir()?;
@@ -106,10 +142,9 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
 }
 
 /// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-/// ISSUE(#134) - verify checksum
-fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
-    if file.is_file() {
+/// downloading, or no longer match the integrity it was verified against.
+fn load_cached_distro(file: &Path, pinned: Option<&Integrity>) -> Option<Box<dyn Archive>> {
+    if file.is_file() && integrity::cached_archive_is_valid(file, pinned) {
         let file = File::open(file).ok()?;
         Tarball::load(file).ok()
     } else {
@@ -129,21 +164,33 @@ fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fa
             let distro_file_name = Npm::archive_filename(&version_str);
             hook.resolve(version, &distro_file_name)
         }
-        _ => Ok(public_registry_package("npm", &version_str)),
+        _ => public_registry_package("npm", &version_str),
     }
 }
 
-/// Fetch the distro archive from the internet
+/// Fetch the distro archive from the internet, verifying it before it is unpacked
 fn fetch_remote_distro(
     version: &Version,
     url: &str,
-    staging_path: &Path,
-) -> Fallible<Box<dyn Archive>> {
-    debug!("Downloading {} from {}", tool_version("npm", version), url);
-    Tarball::fetch(url, staging_path).with_context(download_tool_error(
+    integrity: Option<&Integrity>,
+) -> Fallible<(Box<dyn Archive>, StagedArchive)> {
+    let staging = download_tool_archive(
         tool::Spec::Npm(VersionSpec::Exact(version.clone())),
         url,
-    ))
+        &Npm::archive_filename(&version.to_string()),
+    )?;
+
+    if let Some(integrity) = integrity {
+        integrity.verify("npm", version, staging.path())?;
+    }
+
+    let unpack_error = || ErrorKind::UnpackArchiveError {
+        tool: "npm".into(),
+        version: version.to_string(),
+    };
+    let file = File::open(staging.path()).with_context(unpack_error)?;
+    let archive = Tarball::load(file).with_context(unpack_error)?;
+    Ok((archive, staging))
 }
 
 /// Overwrite the launcher script
@@ -1,9 +1,10 @@
 use std::fmt::{self, Display};
 
 use super::node::load_default_npm_version;
+use super::package::PackageManager;
 use super::{
     check_fetched, debug_already_fetched, info_fetched, info_installed, info_pinned,
-    info_project_version, FetchStatus, Tool,
+    info_project_version, ArchiveDownload, FetchStatus, Tool,
 };
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::inventory::npm_available;
@@ -17,6 +18,7 @@ mod fetch;
 mod resolve;
 
 pub use resolve::resolve;
+pub(crate) use resolve::resolve_available;
 
 /// The Tool implementation for fetching and installing npm
 pub struct Npm {
@@ -42,7 +44,12 @@ impl Npm {
                 debug_already_fetched(self);
                 Ok(())
             }
-            FetchStatus::FetchNeeded(_lock) => fetch::fetch(&self.version, session.hooks()?.npm()),
+            FetchStatus::FetchNeeded(_lock) => {
+                let pinned = session.project()?.and_then(|project| {
+                    project.package_manager_integrity(PackageManager::Npm, &self.version)
+                });
+                fetch::fetch(&self.version, session.hooks()?.npm(), pinned)
+            }
         }
     }
 }
@@ -86,6 +93,16 @@ impl Tool for Npm {
             Err(ErrorKind::NotInPackage.into())
         }
     }
+    fn archive_download(&self, session: &Session) -> Fallible<Option<ArchiveDownload>> {
+        if npm_available(&self.version)? {
+            return Ok(None);
+        }
+
+        let pinned = session.project()?.and_then(|project| {
+            project.package_manager_integrity(PackageManager::Npm, &self.version)
+        });
+        fetch::archive_download(&self.version, session.hooks()?.npm(), pinned)
+    }
 }
 
 impl Display for Npm {
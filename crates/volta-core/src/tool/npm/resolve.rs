@@ -1,12 +1,19 @@
 //! Provides resolution of npm Version requirements into specific versions
 
+use super::super::http::{self, HttpContext};
+use super::super::integrity;
+use super::super::npmrc::authorize;
+use super::super::outdated::Available;
 use super::super::registry::{
     public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
     NPM_ABBREVIATED_ACCEPT_HEADER,
 };
 use super::super::registry_fetch_error;
-use crate::error::{Context, ErrorKind, Fallible};
+use crate::error::{ErrorKind, Fallible};
 use crate::hook::ToolHooks;
+use crate::inventory::npm_versions;
+use crate::offline::{ensure_online, is_offline, resolve_fetched};
+use crate::policy::find_fetched;
 use crate::session::Session;
 use crate::style::progress_spinner;
 use crate::tool::Npm;
@@ -19,37 +26,65 @@ use semver::{Version, VersionReq};
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Option<Version>> {
     let hooks = session.hooks()?.npm();
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks).map(Some),
+        VersionSpec::Tag(VersionTag::Custom(tag)) if tag == "bundled" => Ok(None),
+        matching if is_offline() => resolve_fetched("npm", matching, npm_versions()?).map(Some),
+        VersionSpec::Semver(requirement) => {
+            match find_fetched("npm", &requirement, npm_versions)? {
+                Some(version) => Ok(Some(version)),
+                None => resolve_semver(requirement, hooks).map(Some),
+            }
+        }
         VersionSpec::Exact(version) => Ok(Some(version)),
         VersionSpec::None | VersionSpec::Tag(VersionTag::Latest) => {
             resolve_tag("latest", hooks).map(Some)
         }
-        VersionSpec::Tag(VersionTag::Custom(tag)) if tag == "bundled" => Ok(None),
         VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), hooks).map(Some),
     }
 }
 
-fn fetch_npm_index(hooks: Option<&ToolHooks<Npm>>) -> Fallible<(String, PackageIndex)> {
-    let url = match hooks {
+/// Determine the newest npm versions that `current` can be upgraded to
+pub(crate) fn resolve_available(
+    current: &Version,
+    hooks: Option<&ToolHooks<Npm>>,
+) -> Fallible<Available> {
+    let (_, index) = fetch_npm_index(hooks)?;
+
+    index.available(current).ok_or_else(|| {
+        ErrorKind::NpmVersionNotFound {
+            matching: "latest".into(),
+        }
+        .into()
+    })
+}
+
+/// Determine the URL of the npm registry metadata, using the hooks if available
+pub(super) fn index_url(hooks: Option<&ToolHooks<Npm>>) -> Fallible<String> {
+    match hooks {
         Some(&ToolHooks {
             index: Some(ref hook),
             ..
         }) => {
             debug!("Using npm.index hook to determine npm index URL");
-            hook.resolve("npm")?
+            hook.resolve("npm")
         }
         _ => public_registry_index("npm"),
-    };
+    }
+}
+
+fn fetch_npm_index(hooks: Option<&ToolHooks<Npm>>) -> Fallible<(String, PackageIndex)> {
+    let url = index_url(hooks)?;
+    ensure_online(&url)?;
 
     let spinner = progress_spinner(format!("Fetching public registry: {}", url));
-    let metadata: RawPackageMetadata = attohttpc::get(&url)
+    let metadata: RawPackageMetadata = authorize(http::get(&url)?, &url)?
         .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
         .send()
         .and_then(Response::error_for_status)
         .and_then(Response::json)
-        .with_context(registry_fetch_error("npm", &url))?;
+        .with_http_context(&url, registry_fetch_error("npm", &url))?;
 
     spinner.finish_and_clear();
+    integrity::record_published(&metadata);
     Ok((url, metadata.into()))
 }
 
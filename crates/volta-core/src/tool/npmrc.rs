@@ -0,0 +1,313 @@
+//! Reads registry settings and credentials from the user's and project's `.npmrc` files, so that
+//! tools and packages are resolved from the same registry that npm uses.
+
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::read_file;
+use crate::project::find_closest_root;
+use attohttpc::header::AUTHORIZATION;
+use attohttpc::RequestBuilder;
+use double_checked_cell::DoubleCheckedCell;
+use lazy_static::lazy_static;
+use log::debug;
+use regex::{Captures, Regex};
+
+/// The settings that hold credentials for a registry
+const CREDENTIALS: [&str; 4] = ["_authToken", "_auth", "username", "_password"];
+
+lazy_static! {
+    static ref NPMRC: DoubleCheckedCell<Npmrc> = DoubleCheckedCell::new();
+    static ref ENV_REFERENCE: Regex = Regex::new(r"\$\{([^}]+)\}").unwrap();
+}
+
+/// Returns the merged settings from the project and user `.npmrc` files
+///
+/// The project file is the `.npmrc` next to the closest `package.json`, and takes precedence over
+/// the user file (`~/.npmrc`, or the file named by `NPM_CONFIG_USERCONFIG`).
+pub(crate) fn npmrc() -> Fallible<&'static Npmrc> {
+    NPMRC.get_or_try_init(|| {
+        let project_file = env::current_dir()
+            .ok()
+            .and_then(find_closest_root)
+            .map(|root| root.join(".npmrc"));
+
+        Npmrc::from_paths(project_file.into_iter().chain(user_npmrc_file()))
+    })
+}
+
+/// Adds the credentials that `.npmrc` configures for `url` to a request, if there are any
+pub(crate) fn authorize(request: RequestBuilder, url: &str) -> Fallible<RequestBuilder> {
+    npmrc().map(|npmrc| npmrc.authorize(request, url))
+}
+
+fn user_npmrc_file() -> Option<PathBuf> {
+    env::var_os("NPM_CONFIG_USERCONFIG")
+        .or_else(|| env::var_os("npm_config_userconfig"))
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".npmrc")))
+}
+
+/// npm configuration settings
+#[derive(Default)]
+pub(crate) struct Npmrc {
+    settings: HashMap<String, String>,
+    /// The file that each setting was read from
+    files: HashMap<String, PathBuf>,
+}
+
+impl Npmrc {
+    /// The value of a setting, such as `registry`
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.settings.get(key).map(String::as_str)
+    }
+
+    /// The value of a setting that names a file, such as `cafile`
+    ///
+    /// A relative path is resolved against the directory of the `.npmrc` file that sets it.
+    pub(crate) fn get_path(&self, key: &str) -> Option<PathBuf> {
+        let path = Path::new(self.get(key)?);
+        match self.files.get(key).and_then(|file| file.parent()) {
+            Some(dir) if path.is_relative() => Some(dir.join(path)),
+            _ => Some(path.to_path_buf()),
+        }
+    }
+
+    /// The registry that serves `package`, if one is configured
+    ///
+    /// Scoped packages use the `@scope:registry` setting for their scope, if there is one, and
+    /// otherwise the `registry` setting.
+    pub(crate) fn registry_for(&self, package: &str) -> Option<&str> {
+        let scoped = package
+            .split_once('/')
+            .filter(|(scope, _)| scope.starts_with('@'))
+            .and_then(|(scope, _)| self.settings.get(&format!("{}:registry", scope)));
+
+        scoped
+            .or_else(|| self.settings.get("registry"))
+            .map(|registry| registry.trim_end_matches('/'))
+    }
+
+    /// Adds the credentials configured for `url` to a request, if there are any
+    pub(crate) fn authorize(&self, request: RequestBuilder, url: &str) -> RequestBuilder {
+        match self.authorization(url) {
+            Some(authorization) => request.header(AUTHORIZATION, authorization),
+            None => request,
+        }
+    }
+
+    /// The value of the `Authorization` header for a request to `url`, if credentials are
+    /// configured for it
+    ///
+    /// Credentials are looked up like npm does, using the most specific `//host/path/:` prefix
+    /// that matches the URL. The unprefixed `_authToken` and `_auth` settings only apply to the
+    /// default registry.
+    pub(crate) fn authorization(&self, url: &str) -> Option<String> {
+        let location = match url.split_once("//") {
+            Some((_, rest)) => format!("//{}/", rest.trim_end_matches('/')),
+            None => return None,
+        };
+
+        let prefix = self
+            .settings
+            .keys()
+            .filter_map(|key| key.rsplit_once(':'))
+            .filter(|(prefix, name)| prefix.starts_with("//") && CREDENTIALS.contains(name))
+            .map(|(prefix, _)| prefix)
+            .filter(|prefix| location.starts_with(&format!("{}/", prefix.trim_end_matches('/'))))
+            .max_by_key(|prefix| prefix.len());
+
+        match prefix {
+            Some(prefix) => self.credentials(&format!("{}:", prefix)),
+            None => {
+                let registry = self.settings.get("registry")?;
+                let url = format!("{}/", url.trim_end_matches('/'));
+                if url.starts_with(&format!("{}/", registry.trim_end_matches('/'))) {
+                    self.credentials("")
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn credentials(&self, prefix: &str) -> Option<String> {
+        let setting = |name: &str| self.settings.get(&format!("{}{}", prefix, name));
+
+        if let Some(token) = setting("_authToken") {
+            return Some(format!("Bearer {}", token));
+        }
+        if let Some(auth) = setting("_auth") {
+            return Some(format!("Basic {}", auth));
+        }
+
+        // The password is stored base64-encoded, but the header needs `username:password`
+        let username = setting("username")?;
+        let password = base64::decode(setting("_password")?).ok()?;
+        let password = String::from_utf8(password).ok()?;
+        Some(format!(
+            "Basic {}",
+            base64::encode(format!("{}:{}", username, password))
+        ))
+    }
+
+    /// Returns the merged settings loaded from an iterator of potential `.npmrc` files
+    ///
+    /// `paths` should be sorted in order of descending precedence.
+    fn from_paths<P, I>(paths: I) -> Fallible<Self>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = P>,
+    {
+        let mut npmrc = Npmrc::default();
+        for path in paths {
+            let path = path.as_ref();
+            let contents = read_file(path).with_context(|| ErrorKind::ReadNpmrcError {
+                file: path.to_path_buf(),
+            })?;
+
+            if let Some(contents) = contents {
+                debug!("Loaded npm configuration from {}", path.display());
+                for (key, value) in parse(&contents) {
+                    if !npmrc.settings.contains_key(&key) {
+                        npmrc.files.insert(key.clone(), path.to_path_buf());
+                        npmrc.settings.insert(key, value);
+                    }
+                }
+            }
+        }
+
+        Ok(npmrc)
+    }
+}
+
+/// Parse the `key=value` lines of an `.npmrc` file, expanding `${VAR}` references to environment
+/// variables
+fn parse(contents: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.starts_with(';'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim().trim_matches('"');
+            let value = ENV_REFERENCE.replace_all(value, |captures: &Captures| {
+                env::var(&captures[1]).unwrap_or_else(|_| captures[0].to_string())
+            });
+
+            (key.trim().to_string(), value.into_owned())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_npmrc(contents: &str) -> Npmrc {
+        Npmrc {
+            settings: parse(contents).collect(),
+            ..Npmrc::default()
+        }
+    }
+
+    #[test]
+    fn test_relative_path_from_npmrc_dir() {
+        let project_dir = PathBuf::from("projects").join("app");
+        let mut npmrc = parse_npmrc("cafile=certs/ca.pem");
+        npmrc
+            .files
+            .insert("cafile".into(), project_dir.join(".npmrc"));
+
+        assert_eq!(
+            npmrc.get_path("cafile"),
+            Some(project_dir.join("certs/ca.pem"))
+        );
+        assert_eq!(npmrc.get_path("keyfile"), None);
+    }
+
+    #[test]
+    fn test_registry_for() {
+        let npmrc = parse_npmrc(
+            "; comment
+registry=https://registry.example.com/npm/
+@private:registry=https://private.example.com/",
+        );
+
+        assert_eq!(
+            npmrc.registry_for("typescript"),
+            Some("https://registry.example.com/npm")
+        );
+        assert_eq!(
+            npmrc.registry_for("@private/tool"),
+            Some("https://private.example.com")
+        );
+        assert_eq!(
+            npmrc.registry_for("@types/node"),
+            Some("https://registry.example.com/npm")
+        );
+        assert_eq!(parse_npmrc("").registry_for("typescript"), None);
+    }
+
+    #[test]
+    fn test_token_for_most_specific_prefix() {
+        let npmrc = parse_npmrc(
+            "//registry.example.com/:_authToken=outer
+//registry.example.com/npm/:_authToken=inner",
+        );
+
+        assert_eq!(
+            npmrc.authorization("https://registry.example.com/npm/yarn"),
+            Some("Bearer inner".into())
+        );
+        assert_eq!(
+            npmrc.authorization("https://registry.example.com/other/yarn"),
+            Some("Bearer outer".into())
+        );
+        assert_eq!(npmrc.authorization("https://registry.npmjs.org/yarn"), None);
+    }
+
+    #[test]
+    fn test_basic_auth() {
+        // "secret" encoded as base64
+        let npmrc = parse_npmrc(
+            "//registry.example.com/:username=volta
+//registry.example.com/:_password=c2VjcmV0",
+        );
+
+        assert_eq!(
+            npmrc.authorization("https://registry.example.com/npm"),
+            Some(format!("Basic {}", base64::encode("volta:secret")))
+        );
+    }
+
+    #[test]
+    fn test_unprefixed_token_applies_to_registry() {
+        let npmrc = parse_npmrc(
+            "registry=https://registry.example.com/
+_authToken=legacy",
+        );
+
+        assert_eq!(
+            npmrc.authorization("https://registry.example.com/npm"),
+            Some("Bearer legacy".into())
+        );
+        assert_eq!(npmrc.authorization("https://nodejs.org/dist"), None);
+        assert_eq!(
+            npmrc.authorization("https://registry.example.com.evil.com/npm"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expands_environment() {
+        env::set_var("VOLTA_TEST_NPM_TOKEN", "from-env");
+        let npmrc = parse_npmrc("//registry.example.com/:_authToken=${VOLTA_TEST_NPM_TOKEN}");
+
+        assert_eq!(
+            npmrc.authorization("https://registry.example.com/npm"),
+            Some("Bearer from-env".into())
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Compares the default tools and installed packages against the newest versions available.
+
+use super::registry::fetch_package_index;
+use super::{node, npm, pnpm, yarn};
+use crate::error::Fallible;
+use crate::inventory::package_configs;
+use crate::session::Session;
+use log::warn;
+use semver::Version;
+
+/// The kinds of item that `volta outdated` reports on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutdatedKind {
+    Runtime,
+    PackageManager,
+    Package,
+}
+
+/// A default tool or installed package with a newer version available
+pub struct Outdated {
+    pub kind: OutdatedKind,
+    /// The name of the tool or package, e.g. `node` or `typescript`
+    pub name: String,
+    /// The version that is currently installed
+    pub current: Version,
+    /// The newest version with the same major version as `current`
+    ///
+    /// Node release lines each have their own major version, so for Node this is the newest
+    /// release in the same LTS line.
+    pub wanted: Version,
+    /// The newest version overall
+    pub latest: Version,
+}
+
+/// The newest versions that can be upgraded to from a given version
+pub(crate) struct Available {
+    pub wanted: Version,
+    pub latest: Version,
+}
+
+impl Available {
+    /// Choose the wanted version from the published releases of a tool
+    ///
+    /// Prereleases are never wanted, and if no stable release in the same major version is newer
+    /// than `current`, then `current` is itself the wanted version.
+    pub(crate) fn new<'a, I>(current: &Version, releases: I, latest: Version) -> Self
+    where
+        I: IntoIterator<Item = &'a Version>,
+    {
+        let wanted = releases
+            .into_iter()
+            .filter(|version| version.major == current.major && !version.is_prerelease())
+            .max()
+            .filter(|&version| version > current)
+            .unwrap_or(current)
+            .clone();
+
+        Available { wanted, latest }
+    }
+
+    fn is_newer_than(&self, current: &Version) -> bool {
+        &self.wanted > current || &self.latest > current
+    }
+}
+
+/// Check the default platform and every installed package for newer versions
+///
+/// Packages whose registry metadata can't be fetched (e.g. because they were installed from a
+/// private registry) are skipped with a warning, rather than failing the whole check.
+pub fn check(session: &Session) -> Fallible<Vec<Outdated>> {
+    let hooks = session.hooks()?;
+    let mut outdated = Vec::new();
+
+    if let Some(platform) = session.default_platform()? {
+        let node = node::resolve_available(&platform.node, hooks.node())?;
+        push_if_newer(
+            &mut outdated,
+            OutdatedKind::Runtime,
+            "node",
+            &platform.node,
+            node,
+        );
+
+        if let Some(version) = &platform.npm {
+            let available = npm::resolve_available(version, hooks.npm())?;
+            push_if_newer(
+                &mut outdated,
+                OutdatedKind::PackageManager,
+                "npm",
+                version,
+                available,
+            );
+        }
+
+        if let Some(version) = &platform.pnpm {
+            let available = pnpm::resolve_available(version, hooks.pnpm())?;
+            push_if_newer(
+                &mut outdated,
+                OutdatedKind::PackageManager,
+                "pnpm",
+                version,
+                available,
+            );
+        }
+
+        if let Some(version) = &platform.yarn {
+            let available = yarn::resolve_available(version, hooks.yarn())?;
+            push_if_newer(
+                &mut outdated,
+                OutdatedKind::PackageManager,
+                "yarn",
+                version,
+                available,
+            );
+        }
+    }
+
+    for config in package_configs()? {
+        match fetch_package_index(&config.name) {
+            Ok(index) => {
+                if let Some(available) = index.available(&config.version) {
+                    push_if_newer(
+                        &mut outdated,
+                        OutdatedKind::Package,
+                        &config.name,
+                        &config.version,
+                        available,
+                    );
+                }
+            }
+            Err(error) => warn!(
+                "Could not check for newer versions of {}: {}",
+                config.name, error
+            ),
+        }
+    }
+
+    Ok(outdated)
+}
+
+fn push_if_newer(
+    outdated: &mut Vec<Outdated>,
+    kind: OutdatedKind,
+    name: &str,
+    current: &Version,
+    available: Available,
+) {
+    if available.is_newer_than(current) {
+        outdated.push(Outdated {
+            kind,
+            name: name.into(),
+            current: current.clone(),
+            wanted: available.wanted,
+            latest: available.latest,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(versions: &[&str]) -> Vec<Version> {
+        versions.iter().map(|v| v.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn wanted_is_newest_in_same_major() {
+        let current = "14.17.0".parse().unwrap();
+        let releases = versions(&["16.14.0", "14.19.1", "14.18.0", "14.17.0", "12.22.1"]);
+        let available = Available::new(&current, &releases, "16.14.0".parse().unwrap());
+
+        assert_eq!(available.wanted, "14.19.1".parse().unwrap());
+        assert_eq!(available.latest, "16.14.0".parse().unwrap());
+        assert!(available.is_newer_than(&current));
+    }
+
+    #[test]
+    fn wanted_skips_prereleases() {
+        let current = "7.0.0".parse().unwrap();
+        let releases = versions(&["7.1.0-beta.1", "7.0.0"]);
+        let available = Available::new(&current, &releases, "7.0.0".parse().unwrap());
+
+        assert_eq!(available.wanted, current);
+        assert!(!available.is_newer_than(&current));
+    }
+}
@@ -2,12 +2,15 @@ use std::path::PathBuf;
 
 use super::manager::PackageManager;
 use crate::command::create_command;
+use crate::config::registry_override;
 use crate::error::{Context, ErrorKind, Fallible};
+use crate::offline::is_offline;
 use crate::platform::Image;
 use crate::style::progress_spinner;
 use log::debug;
 
-/// Use `npm install --global` to install the package
+/// Use the global install command of `manager` (e.g. `npm install --global`) to install the
+/// package
 ///
 /// Sets the environment variable `npm_config_prefix` to redirect the install to the Volta
 /// data directory, taking advantage of the standard global install behavior with a custom
@@ -16,18 +19,42 @@ pub(super) fn run_global_install(
     package: String,
     staging_dir: PathBuf,
     platform_image: &Image,
+    manager: PackageManager,
 ) -> Fallible<()> {
-    let mut command = create_command("npm");
-    command.args(&[
-        "install",
-        "--global",
-        "--loglevel=warn",
-        "--no-update-notifier",
-        "--no-audit",
-    ]);
+    let mut command = match manager {
+        PackageManager::Npm => {
+            let mut command = create_command("npm");
+            command.args(&[
+                "install",
+                "--global",
+                "--loglevel=warn",
+                "--no-update-notifier",
+                "--no-audit",
+            ]);
+            command
+        }
+        PackageManager::Pnpm => {
+            let mut command = create_command("pnpm");
+            command.args(&["add", "--global"]);
+            command
+        }
+        PackageManager::Yarn => {
+            let mut command = create_command("yarn");
+            command.args(&["global", "add"]);
+            command
+        }
+    };
+    // The package managers can still install packages from their own caches, so offline mode
+    // only stops them from accessing the registry
+    if is_offline() {
+        command.arg("--offline");
+    }
+    if let Some(registry) = registry_override() {
+        command.env("npm_config_registry", registry);
+    }
     command.arg(&package);
     command.env("PATH", platform_image.path()?);
-    PackageManager::Npm.setup_global_command(&mut command, staging_dir);
+    manager.setup_global_command(&mut command, staging_dir);
 
     debug!("Installing {} with command: {:?}", package, command);
     let spinner = progress_spinner(format!("Installing {}", package));
@@ -1,9 +1,13 @@
-use std::fs::File;
+use std::env;
+use std::fs::{read_to_string, File};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use super::metadata::GlobalYarnManifest;
 use crate::fs::read_dir_eager;
+use crate::layout::volta_home;
+use crate::offline::is_offline;
+use crate::tool::npmrc::npmrc;
 
 /// The package manager used to install a given package
 #[derive(
@@ -11,9 +15,14 @@ use crate::fs::read_dir_eager;
 )]
 pub enum PackageManager {
     Npm,
+    Pnpm,
     Yarn,
 }
 
+/// The layout version of pnpm's global directory. pnpm nests the global `package.json` and
+/// `node_modules` inside a subdirectory named for this version.
+const PNPM_GLOBAL_LAYOUT_VERSION: &str = "5";
+
 impl PackageManager {
     /// Given the `package_root`, returns the directory where the source is stored for this
     /// package manager. This will include the top-level `node_modules`, where appropriate.
@@ -32,6 +41,11 @@ impl PackageManager {
         let mut path = package_root;
         path.push("lib");
 
+        // pnpm additionally nests its global install inside a layout version directory
+        if let PackageManager::Pnpm = self {
+            path.push(PNPM_GLOBAL_LAYOUT_VERSION);
+        }
+
         path
     }
 
@@ -47,6 +61,14 @@ impl PackageManager {
                 let mut path = package_root;
                 path.push("lib");
 
+                path
+            }
+            // pnpm uses the same layout on all platforms
+            PackageManager::Pnpm => {
+                let mut path = package_root;
+                path.push("lib");
+                path.push(PNPM_GLOBAL_LAYOUT_VERSION);
+
                 path
             }
         }
@@ -56,7 +78,7 @@ impl PackageManager {
     /// manager.
     #[cfg(unix)]
     pub fn binary_dir(self, package_root: PathBuf) -> PathBuf {
-        // On Unix, the binaries are always within a `bin` subdirectory for both npm and Yarn
+        // On Unix, the binaries are always within a `bin` subdirectory for all package managers
         let mut path = package_root;
         path.push("bin");
 
@@ -70,8 +92,8 @@ impl PackageManager {
         match self {
             // On Windows, npm leaves the binaries at the root of the `prefix` directory
             PackageManager::Npm => package_root,
-            // On Windows, Yarn still includes the `bin` subdirectory
-            PackageManager::Yarn => {
+            // On Windows, pnpm and Yarn still include the `bin` subdirectory
+            PackageManager::Pnpm | PackageManager::Yarn => {
                 let mut path = package_root;
                 path.push("bin");
 
@@ -80,12 +102,81 @@ impl PackageManager {
         }
     }
 
+    /// Given the root of the package cache, returns the directory this package manager caches
+    /// downloaded tarballs in, if it uses the shared cache.
+    pub fn cache_dir(self, cache_root: PathBuf) -> Option<PathBuf> {
+        match self {
+            // npm keeps its content-addressed cache in `_cacache` within the directory
+            PackageManager::Npm => Some(cache_root),
+            PackageManager::Yarn => {
+                let mut path = cache_root;
+                path.push("yarn");
+
+                Some(path)
+            }
+            // pnpm already shares its own content-addressable store between installs
+            PackageManager::Pnpm => None,
+        }
+    }
+
+    /// Whether the user has configured where this package manager caches tarballs, either in
+    /// the environment or in its configuration file
+    fn has_user_cache(self) -> bool {
+        match self {
+            PackageManager::Npm => {
+                env::var_os("npm_config_cache").is_some()
+                    || env::var_os("NPM_CONFIG_CACHE").is_some()
+                    || npmrc().map_or(false, |npmrc| npmrc.get("cache").is_some())
+            }
+            PackageManager::Yarn => {
+                env::var_os("YARN_CACHE_FOLDER").is_some() || yarnrc_sets_cache_folder()
+            }
+            PackageManager::Pnpm => false,
+        }
+    }
+
     /// Modify a given `Command` to be set up for global installs, given the package root
     pub fn setup_global_command(self, command: &mut Command, package_root: PathBuf) {
         command.env("npm_config_prefix", &package_root);
 
-        if let PackageManager::Yarn = self {
-            command.env("npm_config_global_folder", self.source_root(package_root));
+        // Point every install at the same cache, so that installing a package into a new image
+        // reuses the tarballs downloaded for previous installs. A cache that the user configured
+        // is left alone, and offline installs use the package manager's default cache, which
+        // likely has more packages than the shared one.
+        if !is_offline() && !self.has_user_cache() {
+            if let Some(cache_dir) = volta_home()
+                .ok()
+                .and_then(|home| self.cache_dir(home.package_cache_dir().to_owned()))
+            {
+                match self {
+                    PackageManager::Yarn => command.env("YARN_CACHE_FOLDER", cache_dir),
+                    _ => command.env("npm_config_cache", cache_dir),
+                };
+            }
+        }
+
+        match self {
+            PackageManager::Npm => {}
+            PackageManager::Yarn => {
+                command.env("npm_config_global_folder", self.source_root(package_root));
+            }
+            PackageManager::Pnpm => {
+                let bin_dir = self.binary_dir(package_root.clone());
+                command.env("npm_config_global_dir", package_root.join("lib"));
+                command.env("npm_config_global_bin_dir", &bin_dir);
+
+                // pnpm refuses to install globally unless the global bin directory is on the PATH
+                let path = command
+                    .get_envs()
+                    .find(|(key, _)| *key == "PATH")
+                    .and_then(|(_, value)| value.map(|v| v.to_owned()))
+                    .or_else(|| env::var_os("PATH"))
+                    .unwrap_or_default();
+                let paths = std::iter::once(bin_dir).chain(env::split_paths(&path));
+                if let Ok(new_path) = env::join_paths(paths) {
+                    command.env("PATH", new_path);
+                }
+            }
         }
     }
 
@@ -95,11 +186,26 @@ impl PackageManager {
     pub(super) fn get_installed_package(self, package_root: PathBuf) -> Option<String> {
         match self {
             PackageManager::Npm => get_npm_package_name(self.source_dir(package_root)),
-            PackageManager::Yarn => get_yarn_package_name(self.source_root(package_root)),
+            // pnpm, like Yarn, writes a `package.json` listing the global package as a dependency
+            PackageManager::Pnpm | PackageManager::Yarn => {
+                get_yarn_package_name(self.source_root(package_root))
+            }
         }
     }
 }
 
+/// Whether the user's `.yarnrc` sets the `cache-folder` of Yarn 1
+fn yarnrc_sets_cache_folder() -> bool {
+    dirs::home_dir()
+        .and_then(|home| read_to_string(home.join(".yarnrc")).ok())
+        .map_or(false, |contents| {
+            contents.lines().any(|line| {
+                let line = line.trim_start();
+                line.starts_with("cache-folder ") || line.starts_with("--cache-folder ")
+            })
+        })
+}
+
 /// Determine the package name for an npm global install
 ///
 /// npm doesn't hoist the packages inside of `node_modules`, so the only directory will be the
@@ -165,6 +165,8 @@ struct RawPlatformSpec {
     node: Version,
     #[serde(with = "option_version_serde")]
     npm: Option<Version>,
+    #[serde(default, with = "option_version_serde")]
+    pnpm: Option<Version>,
     #[serde(with = "option_version_serde")]
     yarn: Option<Version>,
 }
@@ -31,16 +31,27 @@ pub struct Package {
     name: String,
     version: VersionSpec,
     staging: TempDir,
+    manager: PackageManager,
 }
 
 impl Package {
     pub fn new(name: String, version: VersionSpec) -> Fallible<Self> {
-        let staging = setup_staging_directory(PackageManager::Npm, NeedsScope::No)?;
+        Package::with_manager(name, version, PackageManager::Npm)
+    }
+
+    /// Create a package that will be installed globally by the given package manager
+    pub fn with_manager(
+        name: String,
+        version: VersionSpec,
+        manager: PackageManager,
+    ) -> Fallible<Self> {
+        let staging = setup_staging_directory(manager, NeedsScope::No)?;
 
         Ok(Package {
             name,
             version,
             staging,
+            manager,
         })
     }
 
@@ -49,11 +60,12 @@ impl Package {
             self.to_string(),
             self.staging.path().to_owned(),
             platform_image,
+            self.manager,
         )
     }
 
     pub fn complete_install(self, image: &Image) -> Fallible<PackageManifest> {
-        let manager = PackageManager::Npm;
+        let manager = self.manager;
         let manifest =
             configure::parse_manifest(&self.name, self.staging.path().to_owned(), manager)?;
 
@@ -76,11 +88,22 @@ impl Tool for Package {
     fn install(self: Box<Self>, session: &mut Session) -> Fallible<()> {
         let _lock = VoltaLock::acquire();
 
-        let default_image = session
+        let default_platform = session
             .default_platform()?
             .map(PlatformSpec::as_default)
-            .ok_or(ErrorKind::NoPlatform)?
-            .checkout(session)?;
+            .ok_or(ErrorKind::NoPlatform)?;
+
+        match self.manager {
+            PackageManager::Pnpm if default_platform.pnpm.is_none() => {
+                return Err(ErrorKind::NoDefaultPnpm.into())
+            }
+            PackageManager::Yarn if default_platform.yarn.is_none() => {
+                return Err(ErrorKind::NoDefaultYarn.into())
+            }
+            _ => {}
+        }
+
+        let default_image = default_platform.checkout(session)?;
 
         self.run_install(&default_image)?;
         let manifest = self.complete_install(&default_image)?;
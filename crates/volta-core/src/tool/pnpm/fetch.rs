@@ -0,0 +1,251 @@
+//! Provides fetcher for pnpm distributions
+
+use std::fs::{write, File};
+use std::path::Path;
+
+use super::super::integrity::{self, Integrity};
+use super::super::registry::public_registry_package;
+use super::super::{download_tool_archive, ArchiveDownload, StagedArchive};
+use super::resolve;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::{create_staging_dir, rename, set_executable};
+use crate::hook::ToolHooks;
+use crate::layout::volta_home;
+use crate::style::{progress_bar, tool_version};
+use crate::tool::{self, Pnpm};
+use crate::version::VersionSpec;
+use archive::{Archive, Tarball};
+use fs_utils::ensure_containing_dir_exists;
+use log::debug;
+use semver::Version;
+
+/// Fetch a version of pnpm, verifying it against `pinned` if the project pins its hash
+pub fn fetch(
+    version: &Version,
+    hooks: Option<&ToolHooks<Pnpm>>,
+    pinned: Option<&Integrity>,
+) -> Fallible<()> {
+    let pnpm_dir = volta_home()?.pnpm_inventory_dir();
+    let cache_file = pnpm_dir.join(Pnpm::archive_filename(&version.to_string()));
+
+    let (archive, staging) = match load_cached_distro(&cache_file, pinned) {
+        Some(archive) => {
+            debug!(
+                "Loading {} from cached archive at '{}'",
+                tool_version("pnpm", &version),
+                cache_file.display()
+            );
+            (archive, None)
+        }
+        None => {
+            let remote_url = determine_remote_url(version, hooks)?;
+            let integrity = match pinned {
+                Some(integrity) => Some(integrity.clone()),
+                None => integrity::published(
+                    "pnpm",
+                    "pnpm",
+                    Some(&resolve::index_url(hooks)?),
+                    version,
+                    hooks.map_or(true, |hooks| hooks.distro.is_none()),
+                )?,
+            };
+            let (archive, staging) = fetch_remote_distro(version, &remote_url, integrity.as_ref())?;
+            (archive, Some((staging, integrity)))
+        }
+    };
+
+    unpack_archive(archive, version)?;
+
+    if let Some((staging_file, integrity)) = staging {
+        ensure_containing_dir_exists(&cache_file).with_context(|| {
+            ErrorKind::ContainingDirError {
+                path: cache_file.clone(),
+            }
+        })?;
+        staging_file
+            .persist(&cache_file)
+            .with_context(|| ErrorKind::PersistInventoryError {
+                tool: "pnpm".into(),
+            })?;
+        integrity::save(&cache_file, integrity.as_ref())?;
+    }
+
+    Ok(())
+}
+
+/// The archive that `fetch` will download, unless there is a valid one in the inventory already
+pub fn archive_download(
+    version: &Version,
+    hooks: Option<&ToolHooks<Pnpm>>,
+    pinned: Option<&Integrity>,
+) -> Fallible<Option<ArchiveDownload>> {
+    let file_name = Pnpm::archive_filename(&version.to_string());
+    let cache_file = volta_home()?.pnpm_inventory_dir().join(&file_name);
+    if load_cached_distro(&cache_file, pinned).is_some() {
+        return Ok(None);
+    }
+
+    Ok(Some(ArchiveDownload {
+        tool: tool::Spec::Pnpm(VersionSpec::Exact(version.clone())),
+        url: determine_remote_url(version, hooks)?,
+        file_name,
+    }))
+}
+
+/// Unpack the pnpm archive into the image directory so that it is ready for use
+fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()> {
+    let temp = create_staging_dir()?;
+    debug!("Unpacking pnpm into '{}'", temp.path().display());
+
+    let progress = progress_bar(
+        archive.origin(),
+        &tool_version("pnpm", version),
+        archive
+            .uncompressed_size()
+            .unwrap_or_else(|| archive.compressed_size()),
+    );
+    let version_string = version.to_string();
+
+    archive
+        .unpack(temp.path(), &mut |_, read| {
+            progress.inc(read as u64);
+        })
+        .with_context(|| ErrorKind::UnpackArchiveError {
+            tool: "pnpm".into(),
+            version: version_string.clone(),
+        })?;
+
+    // Unlike npm and Yarn, the pnpm package doesn't ship with shell launchers, only the
+    // JavaScript entry points, so we write our own for both `pnpm` and `pnpx`
+    let bin_path = temp.path().join("package").join("bin");
+    write_launcher(&bin_path, "pnpm")?;
+    write_launcher(&bin_path, "pnpx")?;
+
+    #[cfg(windows)]
+    {
+        write_cmd_launcher(&bin_path, "pnpm")?;
+        write_cmd_launcher(&bin_path, "pnpx")?;
+    }
+
+    let dest = volta_home()?.pnpm_image_dir(&version_string);
+    ensure_containing_dir_exists(&dest)
+        .with_context(|| ErrorKind::ContainingDirError { path: dest.clone() })?;
+
+    rename(temp.path().join("package"), &dest).with_context(|| ErrorKind::SetupToolImageError {
+        tool: "pnpm".into(),
+        version: version_string.clone(),
+        dir: dest.clone(),
+    })?;
+
+    progress.finish_and_clear();
+
+    // Note: We write this after the progress bar is finished to avoid display bugs with re-renders of the progress
+    debug!("Installing pnpm in '{}'", dest.display());
+
+    Ok(())
+}
+
+/// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
+/// downloading, or no longer match the integrity it was verified against.
+fn load_cached_distro(file: &Path, pinned: Option<&Integrity>) -> Option<Box<dyn Archive>> {
+    if file.is_file() && integrity::cached_archive_is_valid(file, pinned) {
+        let file = File::open(file).ok()?;
+        Tarball::load(file).ok()
+    } else {
+        None
+    }
+}
+
+/// Determine the remote URL to download from, using the hooks if available
+fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<String> {
+    let version_str = version.to_string();
+    match hooks {
+        Some(&ToolHooks {
+            distro: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using pnpm.distro hook to determine download URL");
+            let distro_file_name = Pnpm::archive_filename(&version_str);
+            hook.resolve(version, &distro_file_name)
+        }
+        _ => public_registry_package("pnpm", &version_str),
+    }
+}
+
+/// Fetch the distro archive from the internet, verifying it before it is unpacked
+fn fetch_remote_distro(
+    version: &Version,
+    url: &str,
+    integrity: Option<&Integrity>,
+) -> Fallible<(Box<dyn Archive>, StagedArchive)> {
+    let staging = download_tool_archive(
+        tool::Spec::Pnpm(VersionSpec::Exact(version.clone())),
+        url,
+        &Pnpm::archive_filename(&version.to_string()),
+    )?;
+
+    if let Some(integrity) = integrity {
+        integrity.verify("pnpm", version, staging.path())?;
+    }
+
+    let unpack_error = || ErrorKind::UnpackArchiveError {
+        tool: "pnpm".into(),
+        version: version.to_string(),
+    };
+    let file = File::open(staging.path()).with_context(unpack_error)?;
+    let archive = Tarball::load(file).with_context(unpack_error)?;
+    Ok((archive, staging))
+}
+
+/// Determine the JavaScript entry point for the given tool within the pnpm `bin` directory
+///
+/// pnpm 6 and later publish `.cjs` entry points, while earlier versions used plain `.js` files.
+fn entry_point(base_path: &Path, tool: &str) -> String {
+    let cjs = format!("{}.cjs", tool);
+    if base_path.join(&cjs).is_file() {
+        cjs
+    } else {
+        format!("{}.js", tool)
+    }
+}
+
+/// Write the launcher script
+fn write_launcher(base_path: &Path, tool: &str) -> Fallible<()> {
+    let path = base_path.join(tool);
+    write(
+        &path,
+        // Note: Matches the launcher we write for npm, so that Node is found from the PATH
+        format!(
+            r#"#!/bin/sh
+(set -o igncr) 2>/dev/null && set -o igncr; # cygwin encoding fix
+
+basedir=`dirname "$0"`
+
+case `uname` in
+    *CYGWIN*) basedir=`cygpath -w "$basedir"`;;
+esac
+
+node "$basedir/{}" "$@"
+"#,
+            entry_point(base_path, tool)
+        ),
+    )
+    .and_then(|_| set_executable(&path))
+    .with_context(|| ErrorKind::WriteLauncherError { tool: tool.into() })
+}
+
+/// Write the CMD launcher
+#[cfg(windows)]
+fn write_cmd_launcher(base_path: &Path, tool: &str) -> Fallible<()> {
+    write(
+        base_path.join(format!("{}.cmd", tool)),
+        format!(
+            r#"@ECHO OFF
+
+node "%~dp0\{}" %*
+"#,
+            entry_point(base_path, tool)
+        ),
+    )
+    .with_context(|| ErrorKind::WriteLauncherError { tool: tool.into() })
+}
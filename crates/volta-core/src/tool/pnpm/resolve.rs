@@ -0,0 +1,122 @@
+//! Provides resolution of pnpm requirements into specific versions
+
+use super::super::http::{self, HttpContext};
+use super::super::integrity;
+use super::super::npmrc::authorize;
+use super::super::outdated::Available;
+use super::super::registry::{
+    public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
+    NPM_ABBREVIATED_ACCEPT_HEADER,
+};
+use super::super::registry_fetch_error;
+use crate::error::{ErrorKind, Fallible};
+use crate::hook::ToolHooks;
+use crate::inventory::pnpm_versions;
+use crate::offline::{ensure_online, is_offline, resolve_fetched};
+use crate::policy::find_fetched;
+use crate::session::Session;
+use crate::style::progress_spinner;
+use crate::tool::Pnpm;
+use crate::version::{VersionSpec, VersionTag};
+use attohttpc::header::ACCEPT;
+use attohttpc::Response;
+use log::debug;
+use semver::{Version, VersionReq};
+
+pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
+    let hooks = session.hooks()?.pnpm();
+    match matching {
+        matching if is_offline() => resolve_fetched("pnpm", matching, pnpm_versions()?),
+        VersionSpec::Semver(requirement) => {
+            match find_fetched("pnpm", &requirement, pnpm_versions)? {
+                Some(version) => Ok(version),
+                None => resolve_semver(requirement, hooks),
+            }
+        }
+        VersionSpec::Exact(version) => Ok(version),
+        VersionSpec::None => resolve_tag(VersionTag::Latest, hooks),
+        VersionSpec::Tag(tag) => resolve_tag(tag, hooks),
+    }
+}
+
+/// Determine the newest pnpm versions that `current` can be upgraded to
+pub(crate) fn resolve_available(
+    current: &Version,
+    hooks: Option<&ToolHooks<Pnpm>>,
+) -> Fallible<Available> {
+    let (_, index) = fetch_pnpm_index(hooks)?;
+
+    index.available(current).ok_or_else(|| {
+        ErrorKind::PnpmVersionNotFound {
+            matching: "latest".into(),
+        }
+        .into()
+    })
+}
+
+/// Determine the URL of the pnpm registry metadata, using the hooks if available
+pub(super) fn index_url(hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<String> {
+    match hooks {
+        Some(&ToolHooks {
+            index: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using pnpm.index hook to determine pnpm index URL");
+            hook.resolve("pnpm")
+        }
+        _ => public_registry_index("pnpm"),
+    }
+}
+
+fn fetch_pnpm_index(hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<(String, PackageIndex)> {
+    let url = index_url(hooks)?;
+    ensure_online(&url)?;
+
+    let spinner = progress_spinner(format!("Fetching public registry: {}", url));
+    let metadata: RawPackageMetadata = authorize(http::get(&url)?, &url)?
+        .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::json)
+        .with_http_context(&url, registry_fetch_error("pnpm", &url))?;
+
+    spinner.finish_and_clear();
+    integrity::record_published(&metadata);
+    Ok((url, metadata.into()))
+}
+
+fn resolve_tag(tag: VersionTag, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<Version> {
+    let (url, mut index) = fetch_pnpm_index(hooks)?;
+    let tag = tag.to_string();
+
+    match index.tags.remove(&tag) {
+        Some(version) => {
+            debug!("Found pnpm@{} matching tag '{}' from {}", version, tag, url);
+            Ok(version)
+        }
+        None => Err(ErrorKind::PnpmVersionNotFound { matching: tag }.into()),
+    }
+}
+
+fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<Version> {
+    let (url, index) = fetch_pnpm_index(hooks)?;
+
+    let details_opt = index
+        .entries
+        .into_iter()
+        .find(|PackageDetails { version, .. }| matching.matches(version));
+
+    match details_opt {
+        Some(details) => {
+            debug!(
+                "Found pnpm@{} matching requirement '{}' from {}",
+                details.version, matching, url
+            );
+            Ok(details.version)
+        }
+        None => Err(ErrorKind::PnpmVersionNotFound {
+            matching: matching.to_string(),
+        }
+        .into()),
+    }
+}
@@ -0,0 +1,337 @@
+//! Finds and removes tool versions in the inventory that nothing references anymore.
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use super::package::PackageManager;
+use super::uninstall::InventoryTool;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::{
+    disk_usage, ok_if_not_found, read_dir_eager, remove_dir_if_exists, remove_file_if_exists,
+};
+use crate::inventory::package_configs;
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::project::recent::recent_platforms;
+use crate::session::Session;
+use crate::sync::VoltaLock;
+use log::debug;
+use semver::Version;
+use walkdir::WalkDir;
+
+/// Staging entries older than this are assumed to be left over from an interrupted fetch
+const STALE_STAGING_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+const PRUNABLE_TOOLS: [InventoryTool; 4] = [
+    InventoryTool::Node,
+    InventoryTool::Npm,
+    InventoryTool::Pnpm,
+    InventoryTool::Yarn,
+];
+
+/// A fetched tool version that is not referenced by any platform
+pub struct UnusedVersion {
+    tool: InventoryTool,
+    /// The fetched version
+    pub version: Version,
+    /// The disk space used by the version, in bytes
+    pub size: u64,
+}
+
+impl UnusedVersion {
+    /// The name of the tool, e.g. `node`
+    pub fn name(&self) -> &'static str {
+        self.tool.name()
+    }
+}
+
+/// A leftover file or directory in the Volta tmp directory
+pub struct StaleStaging {
+    pub path: PathBuf,
+    /// The disk space used by the entry, in bytes
+    pub size: u64,
+}
+
+/// A tarball, or unpacked package, in the shared package cache
+pub struct CachedPackage {
+    pub path: PathBuf,
+    /// The disk space used by the entry, in bytes
+    pub size: u64,
+    modified: SystemTime,
+}
+
+/// Everything that `volta prune` would remove
+pub struct PrunePlan {
+    pub versions: Vec<UnusedVersion>,
+    pub staging: Vec<StaleStaging>,
+    pub cache: Vec<CachedPackage>,
+}
+
+impl PrunePlan {
+    /// Determine which fetched versions are unreferenced and which staging entries are stale
+    ///
+    /// A version is referenced if it is part of the default platform, the platform of an
+    /// installed package, or the pins of a recently used project.
+    ///
+    /// If `max_cache_size` is set, the plan also includes the oldest entries of the package cache
+    /// that need to be removed to bring it down to that many bytes.
+    pub fn new(session: &Session, max_cache_size: Option<u64>) -> Fallible<Self> {
+        let mut platforms: Vec<PlatformSpec> = package_configs()?
+            .into_iter()
+            .map(|config| config.platform)
+            .collect();
+        platforms.extend(recent_platforms()?);
+        if let Some(default) = session.default_platform()? {
+            platforms.push(default.clone());
+        }
+
+        let mut versions = Vec::new();
+        for &tool in PRUNABLE_TOOLS.iter() {
+            for version in tool.fetched_versions()? {
+                if is_referenced(tool, &version, &platforms) {
+                    continue;
+                }
+
+                let size = tool
+                    .inventory_paths(&version)?
+                    .iter()
+                    .map(|path| disk_usage(path))
+                    .sum();
+                versions.push(UnusedVersion {
+                    tool,
+                    version,
+                    size,
+                });
+            }
+        }
+
+        let cache = match max_cache_size {
+            Some(max_size) => cache_overflow(cached_packages()?, max_size),
+            None => Vec::new(),
+        };
+
+        Ok(PrunePlan {
+            versions,
+            staging: stale_staging()?,
+            cache,
+        })
+    }
+
+    /// The total disk space, in bytes, that executing this plan would free
+    pub fn reclaimable(&self) -> u64 {
+        self.versions.iter().map(|unused| unused.size).sum::<u64>()
+            + self.staging.iter().map(|stale| stale.size).sum::<u64>()
+            + self.cache.iter().map(|cached| cached.size).sum::<u64>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.versions.is_empty() && self.staging.is_empty() && self.cache.is_empty()
+    }
+
+    /// Remove every unused version, stale staging entry and overflowing cache entry
+    pub fn execute(self) -> Fallible<()> {
+        // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+        let _lock = VoltaLock::acquire();
+
+        for unused in self.versions {
+            unused.tool.remove(&unused.version)?;
+        }
+
+        let paths = self.staging.into_iter().map(|stale| stale.path);
+        for path in paths.chain(self.cache.into_iter().map(|cached| cached.path)) {
+            if path.is_dir() {
+                remove_dir_if_exists(&path)?;
+            } else {
+                remove_file_if_exists(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_referenced(tool: InventoryTool, version: &Version, platforms: &[PlatformSpec]) -> bool {
+    platforms
+        .iter()
+        .any(|platform| tool.version_in(platform) == Some(version))
+}
+
+/// Find entries in the tmp directory that haven't been modified recently enough to belong to a
+/// fetch that is still in progress
+fn stale_staging() -> Fallible<Vec<StaleStaging>> {
+    let tmp_dir = volta_home()?.tmp_dir();
+    let now = SystemTime::now();
+
+    // The staging directory isn't created until something is fetched
+    let entries: Vec<_> = read_dir_eager(tmp_dir)
+        .map(Iterator::collect)
+        .or_else(ok_if_not_found)
+        .with_context(|| ErrorKind::ReadDirError {
+            dir: tmp_dir.to_owned(),
+        })?;
+
+    Ok(entries
+        .into_iter()
+        .filter(|(entry, metadata)| match metadata.modified() {
+            Ok(modified) => now
+                .duration_since(modified)
+                .map(|age| age > STALE_STAGING_AGE)
+                .unwrap_or(false),
+            Err(error) => {
+                debug!(
+                    "Could not read modified time of '{}': {}",
+                    entry.path().display(),
+                    error
+                );
+                false
+            }
+        })
+        .map(|(entry, _)| {
+            let path = entry.path();
+            let size = disk_usage(&path);
+            StaleStaging { path, size }
+        })
+        .collect())
+}
+
+/// List the entries of the package cache that can be removed individually
+///
+/// For npm, these are the tarballs in its content-addressed store, which npm downloads again if
+/// its index refers to one that is missing. For Yarn, these are the unpacked packages, each in a
+/// directory named for its hash.
+fn cached_packages() -> Fallible<Vec<CachedPackage>> {
+    let cache_root = volta_home()?.package_cache_dir().to_owned();
+    let npm_content = PackageManager::Npm
+        .cache_dir(cache_root.clone())
+        .map(|dir| dir.join("_cacache").join("content-v2"));
+    let yarn_cache = PackageManager::Yarn.cache_dir(cache_root);
+
+    let npm_entries = npm_content
+        .into_iter()
+        .flat_map(|dir| WalkDir::new(dir).into_iter().filter_map(Result::ok))
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path());
+
+    // Yarn nests its cache in a directory for its cache format, e.g. `v6`
+    let yarn_entries = yarn_cache
+        .into_iter()
+        .flat_map(|dir| WalkDir::new(dir).min_depth(2).max_depth(2).into_iter())
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_dir() && entry.file_name() != ".tmp")
+        .map(|entry| entry.into_path());
+
+    Ok(npm_entries
+        .chain(yarn_entries)
+        .filter_map(|path| {
+            let modified = path.metadata().and_then(|metadata| metadata.modified());
+            match modified {
+                Ok(modified) => {
+                    let size = disk_usage(&path);
+                    Some(CachedPackage {
+                        path,
+                        size,
+                        modified,
+                    })
+                }
+                Err(error) => {
+                    debug!(
+                        "Could not read modified time of '{}': {}",
+                        path.display(),
+                        error
+                    );
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// Select the oldest entries that need to be removed to bring the cache down to `max_size` bytes
+fn cache_overflow(mut entries: Vec<CachedPackage>, max_size: u64) -> Vec<CachedPackage> {
+    let mut total: u64 = entries.iter().map(|cached| cached.size).sum();
+    entries.sort_by_key(|cached| cached.modified);
+
+    entries
+        .into_iter()
+        .take_while(|cached| {
+            let overflowing = total > max_size;
+            total = total.saturating_sub(cached.size);
+            overflowing
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn platform(node: &str, yarn: Option<&str>) -> PlatformSpec {
+        PlatformSpec {
+            node: Version::parse(node).unwrap(),
+            npm: None,
+            pnpm: None,
+            yarn: yarn.map(|v| Version::parse(v).unwrap()),
+        }
+    }
+
+    #[test]
+    fn node_referenced_by_any_platform() {
+        let platforms = [platform("12.22.1", None), platform("14.17.0", None)];
+
+        assert!(is_referenced(
+            InventoryTool::Node,
+            &Version::parse("14.17.0").unwrap(),
+            &platforms
+        ));
+        assert!(!is_referenced(
+            InventoryTool::Node,
+            &Version::parse("10.24.1").unwrap(),
+            &platforms
+        ));
+    }
+
+    #[test]
+    fn cache_overflow_removes_oldest_first() {
+        let epoch = SystemTime::UNIX_EPOCH;
+        let cached = |name: &str, size: u64, age: u64| CachedPackage {
+            path: PathBuf::from(name),
+            size,
+            modified: epoch + Duration::from_secs(1000 - age),
+        };
+        let entries = vec![
+            cached("newest", 40, 1),
+            cached("oldest", 30, 300),
+            cached("middle", 50, 200),
+        ];
+
+        let removed: Vec<_> = cache_overflow(entries, 60)
+            .into_iter()
+            .map(|cached| cached.path)
+            .collect();
+        assert_eq!(
+            removed,
+            vec![PathBuf::from("oldest"), PathBuf::from("middle")]
+        );
+    }
+
+    #[test]
+    fn cache_overflow_keeps_cache_within_limit() {
+        let entries = vec![CachedPackage {
+            path: PathBuf::from("tarball"),
+            size: 100,
+            modified: SystemTime::UNIX_EPOCH,
+        }];
+
+        assert!(cache_overflow(entries, 100).is_empty());
+    }
+
+    #[test]
+    fn package_managers_only_match_their_own_field() {
+        let platforms = [platform("1.22.10", Some("1.22.10"))];
+        let version = Version::parse("1.22.10").unwrap();
+
+        assert!(is_referenced(InventoryTool::Yarn, &version, &platforms));
+        assert!(!is_referenced(InventoryTool::Npm, &version, &platforms));
+        assert!(!is_referenced(InventoryTool::Pnpm, &version, &platforms));
+    }
+}
@@ -1,9 +1,18 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use super::http::{self, HttpContext};
+use super::npmrc::{authorize, npmrc};
+use super::outdated::Available;
+use super::registry_fetch_error;
+use crate::config::registry_override;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::read_dir_eager;
+use crate::offline::ensure_online;
+use crate::style::progress_spinner;
 use crate::version::{hashmap_version_serde, version_serde};
+use attohttpc::header::ACCEPT;
+use attohttpc::Response;
 use cfg_if::cfg_if;
 use semver::Version;
 use serde::Deserialize;
@@ -20,23 +29,46 @@ cfg_if! {
         // we can't use `mockito::server_url()`, which relies on shared memory.
         #[allow(deprecated)]
         const SERVER_URL: &str = mockito::SERVER_URL;
-        pub fn public_registry_index(package: &str) -> String {
-            format!("{}/{}", SERVER_URL, package)
+        fn public_registry_root() -> String {
+            SERVER_URL.to_string()
         }
     } else {
-        pub fn public_registry_index(package: &str) -> String {
-            format!("https://registry.npmjs.org/{}", package)
+        fn public_registry_root() -> String {
+            "https://registry.npmjs.org".to_string()
         }
     }
 }
 
-pub fn public_registry_package(package: &str, version: &str) -> String {
-    format!(
+/// The URL of the registry metadata for `package`
+///
+/// A scoped registry from `.npmrc` takes precedence, followed by the `registry` setting of Volta
+/// and then the `registry` from `.npmrc`.
+pub fn public_registry_index(package: &str) -> Fallible<String> {
+    let npmrc = npmrc()?;
+    let scoped = if package.starts_with('@') {
+        npmrc.registry_for(package).map(str::to_string)
+    } else {
+        None
+    };
+
+    let root = scoped
+        .or_else(registry_override)
+        .or_else(|| npmrc.registry_for(package).map(str::to_string))
+        .unwrap_or_else(public_registry_root);
+    Ok(format!("{}/{}", root, package))
+}
+
+/// The URL of the tarball for `version` of `package`
+///
+/// Tarballs of scoped packages are named without the scope, e.g. `@yarnpkg/cli-dist/-/cli-dist-3.2.4.tgz`
+pub fn public_registry_package(package: &str, version: &str) -> Fallible<String> {
+    let name = package.rsplit('/').next().unwrap_or(package);
+    Ok(format!(
         "{}/-/{}-{}.tgz",
-        public_registry_index(package),
-        package,
+        public_registry_index(package)?,
+        name,
         version
-    )
+    ))
 }
 
 /// Figure out the unpacked package directory name dynamically
@@ -69,6 +101,32 @@ pub struct PackageIndex {
     pub entries: Vec<PackageDetails>,
 }
 
+impl PackageIndex {
+    /// The versions that `current` can be upgraded to, if the package has a `latest` tag
+    pub(crate) fn available(&self, current: &Version) -> Option<Available> {
+        let latest = self.tags.get("latest")?.clone();
+        let releases = self.entries.iter().map(|details| &details.version);
+
+        Some(Available::new(current, releases, latest))
+    }
+}
+
+/// Fetch the index of versions of a package from the public npm Registry
+pub fn fetch_package_index(package: &str) -> Fallible<PackageIndex> {
+    let url = public_registry_index(package)?;
+    ensure_online(&url)?;
+    let spinner = progress_spinner(format!("Fetching public registry: {}", url));
+    let metadata: RawPackageMetadata = authorize(http::get(&url)?, &url)?
+        .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::json)
+        .with_http_context(&url, registry_fetch_error(package, &url))?;
+
+    spinner.finish_and_clear();
+    Ok(metadata.into())
+}
+
 /// Package Metadata Response
 ///
 /// See npm registry API doc:
@@ -96,6 +154,9 @@ pub struct RawPackageVersionInfo {
 pub struct RawDistInfo {
     pub shasum: String,
     pub tarball: String,
+    /// Subresource Integrity string for the tarball, only published by newer registries
+    #[serde(default)]
+    pub integrity: Option<String>,
 }
 
 impl From<RawPackageMetadata> for PackageIndex {
@@ -20,6 +20,7 @@ impl Spec {
         match tool_name {
             "node" => Spec::Node(version),
             "npm" => Spec::Npm(version),
+            "pnpm" => Spec::Pnpm(version),
             "yarn" => Spec::Yarn(version),
             package => Spec::Package(package.to_string(), version),
         }
@@ -53,6 +54,7 @@ impl Spec {
         Ok(match name {
             "node" => Spec::Node(version),
             "npm" => Spec::Npm(version),
+            "pnpm" => Spec::Pnpm(version),
             "yarn" => Spec::Yarn(version),
             package => Spec::Package(package.into(), version),
         })
@@ -133,7 +135,7 @@ impl Spec {
     ///
     /// We want to preserve the original order as much as possible, so we treat tools in
     /// the same tool category as equal. We still need to pull Node to the front of the
-    /// list, followed by Npm / Yarn, and then Packages last.
+    /// list, followed by Npm / pnpm / Yarn, and then Packages last.
     fn sort_comparator(left: &Spec, right: &Spec) -> Ordering {
         match (left, right) {
             (Spec::Node(_), Spec::Node(_)) => Ordering::Equal,
@@ -142,6 +144,9 @@ impl Spec {
             (Spec::Npm(_), Spec::Npm(_)) => Ordering::Equal,
             (Spec::Npm(_), _) => Ordering::Less,
             (_, Spec::Npm(_)) => Ordering::Greater,
+            (Spec::Pnpm(_), Spec::Pnpm(_)) => Ordering::Equal,
+            (Spec::Pnpm(_), _) => Ordering::Less,
+            (_, Spec::Pnpm(_)) => Ordering::Greater,
             (Spec::Yarn(_), Spec::Yarn(_)) => Ordering::Equal,
             (Spec::Yarn(_), _) => Ordering::Less,
             (_, Spec::Yarn(_)) => Ordering::Greater,
@@ -256,6 +261,34 @@ mod tests {
             );
         }
 
+        #[test]
+        fn parses_bare_pnpm() {
+            assert_eq!(
+                Spec::try_from_str("pnpm").expect("succeeds"),
+                Spec::Pnpm(VersionSpec::default())
+            );
+        }
+
+        #[test]
+        fn parses_pnpm_with_valid_versions() {
+            let tool = "pnpm";
+
+            assert_eq!(
+                Spec::try_from_str(&versioned_tool!(tool, MAJOR)).expect("succeeds"),
+                Spec::Pnpm(VersionSpec::from_str(MAJOR).expect("`VersionSpec` has its own tests"))
+            );
+
+            assert_eq!(
+                Spec::try_from_str(&versioned_tool!(tool, PATCH)).expect("succeeds"),
+                Spec::Pnpm(VersionSpec::from_str(PATCH).expect("`VersionSpec` has its own tests"))
+            );
+
+            assert_eq!(
+                Spec::try_from_str(&versioned_tool!(tool, LATEST)).expect("succeeds"),
+                Spec::Pnpm(VersionSpec::Tag(VersionTag::Latest))
+            );
+        }
+
         #[test]
         fn parses_bare_packages() {
             let package = "ember-cli";
@@ -484,6 +517,26 @@ mod tests {
             assert_eq!(Spec::from_strings(&multiple, PIN).expect("is ok"), expected);
         }
 
+        #[test]
+        fn sorts_pnpm_between_npm_and_yarn() {
+            let multiple = [
+                "ember-cli@3".to_owned(),
+                "yarn".to_owned(),
+                "pnpm@7".to_owned(),
+                "npm@5".to_owned(),
+            ];
+            let expected = [
+                Spec::Npm(VersionSpec::from_str("5").expect("requirement is valid")),
+                Spec::Pnpm(VersionSpec::from_str("7").expect("requirement is valid")),
+                Spec::Yarn(VersionSpec::default()),
+                Spec::Package(
+                    "ember-cli".to_owned(),
+                    VersionSpec::from_str("3").expect("requirement is valid"),
+                ),
+            ];
+            assert_eq!(Spec::from_strings(&multiple, PIN).expect("is ok"), expected);
+        }
+
         #[test]
         fn keeps_package_order_unchanged() {
             let packages_with_node = ["typescript@latest", "ember-cli@3", "node@lts", "mocha"];
@@ -0,0 +1,202 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use super::integrity::integrity_file;
+use super::{node, Node, Npm, Pnpm, Yarn};
+use crate::error::{ErrorKind, Fallible};
+use crate::fs::{remove_dir_if_exists, remove_file_if_exists};
+use crate::inventory::{
+    node_channel_versions, node_versions, npm_versions, package_configs, pnpm_versions,
+    yarn_versions,
+};
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::session::Session;
+use crate::style::{success_prefix, tool_version};
+use crate::sync::VoltaLock;
+use crate::version::VersionSpec;
+use log::{info, warn};
+use semver::Version;
+
+/// The kinds of tool that are stored as versioned images in the Volta inventory
+#[derive(Clone, Copy)]
+pub(super) enum InventoryTool {
+    Node,
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+impl InventoryTool {
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            InventoryTool::Node => "node",
+            InventoryTool::Npm => "npm",
+            InventoryTool::Pnpm => "pnpm",
+            InventoryTool::Yarn => "yarn",
+        }
+    }
+
+    pub(super) fn fetched_versions(self) -> Fallible<BTreeSet<Version>> {
+        match self {
+            InventoryTool::Node => {
+                let mut versions = node_versions()?;
+                versions.extend(node_channel_versions()?);
+                Ok(versions)
+            }
+            InventoryTool::Npm => npm_versions(),
+            InventoryTool::Pnpm => pnpm_versions(),
+            InventoryTool::Yarn => yarn_versions(),
+        }
+    }
+
+    pub(super) fn version_in(self, platform: &PlatformSpec) -> Option<&Version> {
+        match self {
+            InventoryTool::Node => Some(&platform.node),
+            InventoryTool::Npm => platform.npm.as_ref(),
+            InventoryTool::Pnpm => platform.pnpm.as_ref(),
+            InventoryTool::Yarn => platform.yarn.as_ref(),
+        }
+    }
+
+    /// The files and directories stored in the inventory for a single version: the unpacked
+    /// image, the cached archive, and either the bundled npm version file (for Node) or the
+    /// recorded integrity of the archive (for package managers)
+    pub(super) fn inventory_paths(self, version: &Version) -> Fallible<Vec<PathBuf>> {
+        let home = volta_home()?;
+        let version_str = version.to_string();
+
+        Ok(match self {
+            InventoryTool::Node => vec![
+                node::image_dir(home, version),
+                node::inventory_dir(home, version).join(Node::archive_filename(version)),
+                node::npm_version_file(home, version),
+            ],
+            InventoryTool::Npm => {
+                let archive = home
+                    .npm_inventory_dir()
+                    .join(Npm::archive_filename(&version_str));
+                vec![
+                    home.npm_image_dir(&version_str),
+                    integrity_file(&archive),
+                    archive,
+                ]
+            }
+            InventoryTool::Pnpm => {
+                let archive = home
+                    .pnpm_inventory_dir()
+                    .join(Pnpm::archive_filename(&version_str));
+                vec![
+                    home.pnpm_image_dir(&version_str),
+                    integrity_file(&archive),
+                    archive,
+                ]
+            }
+            InventoryTool::Yarn => {
+                let archive = home
+                    .yarn_inventory_dir()
+                    .join(Yarn::archive_filename(&version_str));
+                vec![
+                    home.yarn_image_dir(&version_str),
+                    integrity_file(&archive),
+                    archive,
+                ]
+            }
+        })
+    }
+
+    /// Removes everything stored in the inventory for a single version
+    pub(super) fn remove(self, version: &Version) -> Fallible<()> {
+        for path in self.inventory_paths(version)? {
+            if path.is_dir() {
+                remove_dir_if_exists(path)?;
+            } else {
+                remove_file_if_exists(path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Uninstalls fetched versions of a runtime or package manager from the local inventory.
+///
+/// An exact version removes only that version, while a semver range removes every fetched version
+/// that matches. Versions that are the user's default, or that an installed package runs on, are
+/// only removed when `force` is set.
+pub(super) fn uninstall(
+    tool: InventoryTool,
+    version: VersionSpec,
+    session: &Session,
+    force: bool,
+) -> Fallible<()> {
+    let name = tool.name();
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+
+    let fetched = tool.fetched_versions()?;
+    let matching: Vec<Version> = match version {
+        VersionSpec::Exact(version) => fetched.into_iter().filter(|v| *v == version).collect(),
+        VersionSpec::Semver(req) => fetched.into_iter().filter(|v| req.matches(v)).collect(),
+        VersionSpec::None | VersionSpec::Tag(_) => {
+            return Err(ErrorKind::UninstallVersionRequired { tool: name.into() }.into());
+        }
+    };
+
+    if matching.is_empty() {
+        warn!(
+            "No fetched version of {} matches the requested version",
+            name
+        );
+        return Ok(());
+    }
+
+    let default_platform = session.default_platform()?;
+    let packages = package_configs()?;
+
+    // Check every version before removing any, so that a version in use doesn't leave the
+    // range partially uninstalled
+    let mut removals = Vec::new();
+    for version in matching {
+        let mut users = Vec::new();
+        if default_platform.and_then(|p| tool.version_in(p)) == Some(&version) {
+            users.push("your default platform".to_string());
+        }
+        users.extend(
+            packages
+                .iter()
+                .filter(|config| tool.version_in(&config.platform) == Some(&version))
+                .map(|config| format!("the package '{}'", config.name)),
+        );
+
+        if !users.is_empty() && !force {
+            return Err(ErrorKind::UninstallVersionInUse {
+                tool: name.into(),
+                version: version.to_string(),
+                users,
+            }
+            .into());
+        }
+
+        removals.push((version, users));
+    }
+
+    for (version, users) in removals {
+        if !users.is_empty() {
+            warn!(
+                "{} is still used by {}, removing anyway",
+                tool_version(name, &version),
+                users.join(", ")
+            );
+        }
+
+        tool.remove(&version)?;
+        info!(
+            "{} uninstalled {}",
+            success_prefix(),
+            tool_version(name, &version)
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,210 @@
+//! Upgrades the default runtime and package managers, and reinstalls global packages at their
+//! latest versions.
+
+use super::outdated::Available;
+use super::package::PackageManager;
+use super::registry::{fetch_package_index, PackageIndex};
+use super::{node, npm, pnpm, yarn};
+use super::{Node, Npm, Package, PackageConfig, Pnpm, Tool, Yarn};
+use crate::error::{ErrorKind, Fallible};
+use crate::inventory::package_configs;
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::session::Session;
+use crate::sync::VoltaLock;
+use crate::version::{VersionSpec, VersionTag};
+use log::warn;
+use semver::Version;
+
+/// A tool or package that was moved to a newer version
+pub struct Upgraded {
+    /// The name of the tool or package, e.g. `node` or `typescript`
+    pub name: String,
+    pub from: Version,
+    pub to: Version,
+}
+
+/// Upgrade a default tool or an installed package
+///
+/// The default runtime and package managers move to the newest release with the same major
+/// version (for Node, the same LTS line). Packages are reinstalled at their latest version, using
+/// the platform that they were installed with unless `repin` is set, in which case they use the
+/// current default platform.
+///
+/// Returns `None` if the tool or package is already up to date.
+pub fn upgrade(name: &str, repin: bool, session: &mut Session) -> Fallible<Option<Upgraded>> {
+    match name {
+        "node" => upgrade_node(session),
+        "npm" => upgrade_npm(session),
+        "pnpm" => upgrade_pnpm(session),
+        "yarn" => upgrade_yarn(session),
+        package => {
+            let config = PackageConfig::from_file_if_exists(
+                volta_home()?.default_package_config_file(package),
+            )?
+            .ok_or_else(|| ErrorKind::UpgradePackageNotFound {
+                package: package.into(),
+                manager: PackageManager::Npm,
+            })?;
+
+            upgrade_package(config, repin, session)
+        }
+    }
+}
+
+/// Reinstall every installed package at its latest version
+///
+/// Packages whose registry can't be reached (such as private packages) can't be checked for a
+/// newer version, so they are skipped with a warning.
+pub fn upgrade_all_packages(repin: bool, session: &mut Session) -> Fallible<Vec<Upgraded>> {
+    let mut upgraded = Vec::new();
+
+    for config in package_configs()? {
+        let index = match fetch_package_index(&config.name) {
+            Ok(index) => index,
+            Err(error) => {
+                warn!(
+                    "Skipping '{}', which could not be checked for newer versions: {}",
+                    config.name, error
+                );
+                continue;
+            }
+        };
+
+        upgraded.extend(reinstall_latest(config, index, repin, session)?);
+    }
+
+    Ok(upgraded)
+}
+
+fn upgrade_node(session: &mut Session) -> Fallible<Option<Upgraded>> {
+    let current = default_platform(session)?.node;
+    let available = node::resolve_available(&current, session.hooks()?.node())?;
+
+    install_if_newer("node", current, available, session, |version| {
+        Box::new(Node::new(version))
+    })
+}
+
+fn upgrade_npm(session: &mut Session) -> Fallible<Option<Upgraded>> {
+    let platform = default_platform(session)?;
+    let current = match platform.npm {
+        Some(npm) => npm,
+        None => node::load_default_npm_version(&platform.node)?,
+    };
+    let available = npm::resolve_available(&current, session.hooks()?.npm())?;
+
+    install_if_newer("npm", current, available, session, |version| {
+        Box::new(Npm::new(version))
+    })
+}
+
+fn upgrade_pnpm(session: &mut Session) -> Fallible<Option<Upgraded>> {
+    let current = default_platform(session)?
+        .pnpm
+        .ok_or(ErrorKind::NoDefaultPnpm)?;
+    let available = pnpm::resolve_available(&current, session.hooks()?.pnpm())?;
+
+    install_if_newer("pnpm", current, available, session, |version| {
+        Box::new(Pnpm::new(version))
+    })
+}
+
+fn upgrade_yarn(session: &mut Session) -> Fallible<Option<Upgraded>> {
+    let current = default_platform(session)?
+        .yarn
+        .ok_or(ErrorKind::NoDefaultYarn)?;
+    let available = yarn::resolve_available(&current, session.hooks()?.yarn())?;
+
+    install_if_newer("yarn", current, available, session, |version| {
+        Box::new(Yarn::new(version))
+    })
+}
+
+fn default_platform(session: &Session) -> Fallible<PlatformSpec> {
+    session
+        .default_platform()?
+        .cloned()
+        .ok_or_else(|| ErrorKind::NoPlatform.into())
+}
+
+fn install_if_newer<F>(
+    name: &str,
+    current: Version,
+    available: Available,
+    session: &mut Session,
+    tool: F,
+) -> Fallible<Option<Upgraded>>
+where
+    F: FnOnce(Version) -> Box<dyn Tool>,
+{
+    if available.wanted <= current {
+        return Ok(None);
+    }
+
+    tool(available.wanted.clone()).install(session)?;
+
+    Ok(Some(Upgraded {
+        name: name.into(),
+        from: current,
+        to: available.wanted,
+    }))
+}
+
+fn upgrade_package(
+    config: PackageConfig,
+    repin: bool,
+    session: &mut Session,
+) -> Fallible<Option<Upgraded>> {
+    let index = fetch_package_index(&config.name)?;
+    reinstall_latest(config, index, repin, session)
+}
+
+/// Reinstall a package at the version tagged `latest` in its registry index, if that is newer
+///
+/// The package is reinstalled by the package manager that it was installed with.
+fn reinstall_latest(
+    config: PackageConfig,
+    mut index: PackageIndex,
+    repin: bool,
+    session: &mut Session,
+) -> Fallible<Option<Upgraded>> {
+    // Without a `latest` tag in the registry we can't tell whether the package is up to date,
+    // so we leave the choice of version to npm
+    let latest = index.tags.remove("latest");
+    let version = match latest {
+        Some(latest) if latest <= config.version => return Ok(None),
+        Some(latest) => VersionSpec::Exact(latest),
+        None => VersionSpec::Tag(VersionTag::Latest),
+    };
+
+    let _lock = VoltaLock::acquire();
+    let platform = if repin {
+        session
+            .default_platform()?
+            .map(PlatformSpec::as_default)
+            .ok_or(ErrorKind::NoPlatform)?
+    } else {
+        config.platform.as_default()
+    };
+    match config.manager {
+        PackageManager::Pnpm if platform.pnpm.is_none() => {
+            return Err(ErrorKind::NoDefaultPnpm.into())
+        }
+        PackageManager::Yarn if platform.yarn.is_none() => {
+            return Err(ErrorKind::NoDefaultYarn.into())
+        }
+        _ => {}
+    }
+    let image = platform.checkout(session)?;
+
+    let package = Package::with_manager(config.name.clone(), version, config.manager)?;
+    package.run_install(&image)?;
+    let manifest = package.complete_install(&image)?;
+
+    Ok(Some(Upgraded {
+        name: config.name,
+        from: config.version,
+        to: manifest.version,
+    }))
+}
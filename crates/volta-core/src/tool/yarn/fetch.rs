@@ -1,12 +1,14 @@
 //! Provides fetcher for Yarn distributions
 
-use std::fs::File;
+use std::fs::{write, File};
 use std::path::Path;
 
-use super::super::download_tool_error;
+use super::super::integrity::{self, Integrity};
 use super::super::registry::{find_unpack_dir, public_registry_package};
+use super::super::{download_tool_archive, ArchiveDownload, StagedArchive};
+use super::{registry_package, resolve, BERRY_PACKAGE};
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, create_staging_file, rename};
+use crate::fs::{create_staging_dir, rename, set_executable};
 use crate::hook::ToolHooks;
 use crate::layout::volta_home;
 use crate::style::{progress_bar, tool_version};
@@ -17,11 +19,16 @@ use fs_utils::ensure_containing_dir_exists;
 use log::debug;
 use semver::Version;
 
-pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<()> {
+/// Fetch a version of Yarn, verifying it against `pinned` if the project pins its hash
+pub fn fetch(
+    version: &Version,
+    hooks: Option<&ToolHooks<Yarn>>,
+    pinned: Option<&Integrity>,
+) -> Fallible<()> {
     let yarn_dir = volta_home()?.yarn_inventory_dir();
     let cache_file = yarn_dir.join(Yarn::archive_filename(&version.to_string()));
 
-    let (archive, staging) = match load_cached_distro(&cache_file) {
+    let (archive, staging) = match load_cached_distro(&cache_file, pinned) {
         Some(archive) => {
             debug!(
                 "Loading {} from cached archive at '{}'",
@@ -31,31 +38,60 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<()>
             (archive, None)
         }
         None => {
-            let staging = create_staging_file()?;
             let remote_url = determine_remote_url(version, hooks)?;
-            let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
-            (archive, Some(staging))
+            let integrity = match pinned {
+                Some(integrity) => Some(integrity.clone()),
+                None => integrity::published(
+                    "yarn",
+                    registry_package(version),
+                    resolve::integrity_index_url(hooks, version)?.as_deref(),
+                    version,
+                    hooks.map_or(true, |hooks| hooks.distro.is_none()),
+                )?,
+            };
+            let (archive, staging) = fetch_remote_distro(version, &remote_url, integrity.as_ref())?;
+            (archive, Some((staging, integrity)))
         }
     };
 
     unpack_archive(archive, version)?;
 
-    if let Some(staging_file) = staging {
+    if let Some((staging_file, integrity)) = staging {
         ensure_containing_dir_exists(&cache_file).with_context(|| {
             ErrorKind::ContainingDirError {
                 path: cache_file.clone(),
             }
         })?;
         staging_file
-            .persist(cache_file)
+            .persist(&cache_file)
             .with_context(|| ErrorKind::PersistInventoryError {
                 tool: "Yarn".into(),
             })?;
+        integrity::save(&cache_file, integrity.as_ref())?;
     }
 
     Ok(())
 }
 
+/// The archive that `fetch` will download, unless there is a valid one in the inventory already
+pub fn archive_download(
+    version: &Version,
+    hooks: Option<&ToolHooks<Yarn>>,
+    pinned: Option<&Integrity>,
+) -> Fallible<Option<ArchiveDownload>> {
+    let file_name = Yarn::archive_filename(&version.to_string());
+    let cache_file = volta_home()?.yarn_inventory_dir().join(&file_name);
+    if load_cached_distro(&cache_file, pinned).is_some() {
+        return Ok(None);
+    }
+
+    Ok(Some(ArchiveDownload {
+        tool: tool::Spec::Yarn(VersionSpec::Exact(version.clone())),
+        url: determine_remote_url(version, hooks)?,
+        file_name,
+    }))
+}
+
 /// Unpack the yarn archive into the image directory so that it is ready for use
 fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()> {
     let temp = create_staging_dir()?;
@@ -84,6 +120,16 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
         .with_context(|| ErrorKind::ContainingDirError { path: dest.clone() })?;
 
     let unpack_dir = find_unpack_dir(temp.path())?;
+
+    // Yarn 2 and later are only published as a bundle without launchers, so we add our own
+    if registry_package(version) == BERRY_PACKAGE {
+        let bin_path = unpack_dir.join("bin");
+        write_launcher(&bin_path)?;
+
+        #[cfg(windows)]
+        write_cmd_launcher(&bin_path)?;
+    }
+
     rename(unpack_dir, &dest).with_context(|| ErrorKind::SetupToolImageError {
         tool: "Yarn".into(),
         version: version_string.clone(),
@@ -99,10 +145,9 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
 }
 
 /// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-// ISSUE(#134) - verify checksum
-fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
-    if file.is_file() {
+/// downloading, or no longer match the integrity it was verified against.
+fn load_cached_distro(file: &Path, pinned: Option<&Integrity>) -> Option<Box<dyn Archive>> {
+    if file.is_file() && integrity::cached_archive_is_valid(file, pinned) {
         let file = File::open(file).ok()?;
         Tarball::load(file).ok()
     } else {
@@ -122,19 +167,70 @@ fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> F
             let distro_file_name = Yarn::archive_filename(&version_str);
             hook.resolve(version, &distro_file_name)
         }
-        _ => Ok(public_registry_package("yarn", &version_str)),
+        _ => public_registry_package(registry_package(version), &version_str),
     }
 }
 
-/// Fetch the distro archive from the internet
+/// Fetch the distro archive from the internet, verifying it before it is unpacked
 fn fetch_remote_distro(
     version: &Version,
     url: &str,
-    staging_path: &Path,
-) -> Fallible<Box<dyn Archive>> {
-    debug!("Downloading {} from {}", tool_version("yarn", version), url);
-    Tarball::fetch(url, staging_path).with_context(download_tool_error(
+    integrity: Option<&Integrity>,
+) -> Fallible<(Box<dyn Archive>, StagedArchive)> {
+    let staging = download_tool_archive(
         tool::Spec::Yarn(VersionSpec::Exact(version.clone())),
         url,
-    ))
+        &Yarn::archive_filename(&version.to_string()),
+    )?;
+
+    if let Some(integrity) = integrity {
+        integrity.verify("yarn", version, staging.path())?;
+    }
+
+    let unpack_error = || ErrorKind::UnpackArchiveError {
+        tool: "Yarn".into(),
+        version: version.to_string(),
+    };
+    let file = File::open(staging.path()).with_context(unpack_error)?;
+    let archive = Tarball::load(file).with_context(unpack_error)?;
+    Ok((archive, staging))
+}
+
+/// Write the launcher script for the `yarn.js` bundle of Yarn 2 and later
+fn write_launcher(base_path: &Path) -> Fallible<()> {
+    let path = base_path.join("yarn");
+    write(
+        &path,
+        // Note: Matches the launchers we write for npm, without detection of the Node location
+        r#"#!/bin/sh
+(set -o igncr) 2>/dev/null && set -o igncr; # cygwin encoding fix
+
+basedir=`dirname "$0"`
+
+case `uname` in
+    *CYGWIN*) basedir=`cygpath -w "$basedir"`;;
+esac
+
+node "$basedir/yarn.js" "$@"
+"#,
+    )
+    .and_then(|_| set_executable(&path))
+    .with_context(|| ErrorKind::WriteLauncherError {
+        tool: "yarn".into(),
+    })
+}
+
+/// Write the CMD launcher for the `yarn.js` bundle of Yarn 2 and later
+#[cfg(windows)]
+fn write_cmd_launcher(base_path: &Path) -> Fallible<()> {
+    write(
+        base_path.join("yarn.cmd"),
+        r#"@ECHO OFF
+
+node "%~dp0\yarn.js" %*
+"#,
+    )
+    .with_context(|| ErrorKind::WriteLauncherError {
+        tool: "yarn".into(),
+    })
 }
@@ -1,21 +1,83 @@
 use std::fmt::{self, Display};
 
+use super::package::PackageManager;
 use super::{
     check_fetched, debug_already_fetched, info_fetched, info_installed, info_pinned,
-    info_project_version, FetchStatus, Tool,
+    info_project_version, ArchiveDownload, FetchStatus, Tool,
 };
 use crate::error::{ErrorKind, Fallible};
 use crate::inventory::yarn_available;
 use crate::session::Session;
 use crate::style::tool_version;
 use crate::sync::VoltaLock;
-use semver::Version;
+use semver::{Version, VersionReq};
 
 mod fetch;
 mod metadata;
 mod resolve;
 
 pub use resolve::resolve;
+pub(crate) use resolve::resolve_available;
+
+/// The npm package that Yarn 1 (classic) is published as
+const CLASSIC_PACKAGE: &str = "yarn";
+
+/// The npm package that Yarn 2 and later (berry) are published as, a single `yarn.js` bundle
+const BERRY_PACKAGE: &str = "@yarnpkg/cli-dist";
+
+/// Determine the npm package that a version of Yarn is published as
+fn registry_package(version: &Version) -> &'static str {
+    if version.major >= 2 {
+        BERRY_PACKAGE
+    } else {
+        CLASSIC_PACKAGE
+    }
+}
+
+/// Determine the npm packages that versions of Yarn matching a requirement may be published as
+///
+/// The requirement is only inspected through its written form, so the classic package is skipped
+/// only when every alternative (separated by `||`) has a lower bound of 2.0.0 or above.
+fn registry_packages(matching: &VersionReq) -> &'static [&'static str] {
+    let excludes_classic = matching
+        .to_string()
+        .split("||")
+        .all(|range| lower_bound_major(range).map_or(false, |major| major >= 2));
+
+    if excludes_classic {
+        &[BERRY_PACKAGE]
+    } else {
+        &[CLASSIC_PACKAGE, BERRY_PACKAGE]
+    }
+}
+
+/// Find the highest major version that a range of comparators requires as a lower bound, if any
+fn lower_bound_major(range: &str) -> Option<u64> {
+    // Only the start of a hyphen range (`2 - 3`) is a lower bound
+    let range = range.split(" - ").next().unwrap_or(range);
+    let mut comparators = Vec::new();
+    let mut operator = String::new();
+
+    for token in range.split(|c: char| c == ',' || c.is_whitespace()) {
+        let version = token.trim_start_matches(|c| "<>=^~".contains(c));
+        operator.push_str(&token[..token.len() - version.len()]);
+        if !version.is_empty() {
+            comparators.push((std::mem::take(&mut operator), version));
+        }
+    }
+
+    comparators
+        .into_iter()
+        .filter(|(operator, _)| !operator.contains('<'))
+        .filter_map(|(_, version)| {
+            version
+                .trim_start_matches('v')
+                .split('.')
+                .next()
+                .and_then(|major| major.parse().ok())
+        })
+        .max()
+}
 
 /// The Tool implementation for fetching and installing Yarn
 pub struct Yarn {
@@ -41,7 +103,12 @@ impl Yarn {
                 debug_already_fetched(self);
                 Ok(())
             }
-            FetchStatus::FetchNeeded(_lock) => fetch::fetch(&self.version, session.hooks()?.yarn()),
+            FetchStatus::FetchNeeded(_lock) => {
+                let pinned = session.project()?.and_then(|project| {
+                    project.package_manager_integrity(PackageManager::Yarn, &self.version)
+                });
+                fetch::fetch(&self.version, session.hooks()?.yarn(), pinned)
+            }
         }
     }
 }
@@ -85,6 +152,16 @@ impl Tool for Yarn {
             Err(ErrorKind::NotInPackage.into())
         }
     }
+    fn archive_download(&self, session: &Session) -> Fallible<Option<ArchiveDownload>> {
+        if yarn_available(&self.version)? {
+            return Ok(None);
+        }
+
+        let pinned = session.project()?.and_then(|project| {
+            project.package_manager_integrity(PackageManager::Yarn, &self.version)
+        });
+        fetch::archive_download(&self.version, session.hooks()?.yarn(), pinned)
+    }
 }
 
 impl Display for Yarn {
@@ -96,6 +173,7 @@ impl Display for Yarn {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::version::parse_requirements;
 
     #[test]
     fn test_yarn_archive_basename() {
@@ -106,4 +184,30 @@ mod tests {
     fn test_yarn_archive_filename() {
         assert_eq!(Yarn::archive_filename("1.2.3"), "yarn-v1.2.3.tar.gz");
     }
+
+    #[test]
+    fn test_yarn_registry_package() {
+        let package = |version| registry_package(&Version::parse(version).unwrap());
+
+        assert_eq!(package("1.22.19"), "yarn");
+        assert_eq!(package("2.4.3"), "@yarnpkg/cli-dist");
+        assert_eq!(package("4.0.0-rc.22"), "@yarnpkg/cli-dist");
+    }
+
+    #[test]
+    fn test_yarn_registry_packages() {
+        let packages = |src| registry_packages(&parse_requirements(src).unwrap());
+        let both = [CLASSIC_PACKAGE, BERRY_PACKAGE];
+
+        assert_eq!(packages("3"), [BERRY_PACKAGE]);
+        assert_eq!(packages("^2.4"), [BERRY_PACKAGE]);
+        assert_eq!(packages(">= 2.0.0 <4"), [BERRY_PACKAGE]);
+        assert_eq!(packages("2 - 4 || 3.x"), [BERRY_PACKAGE]);
+        assert_eq!(packages("1"), both);
+        assert_eq!(packages(">=1.22"), both);
+        assert_eq!(packages("<2"), both);
+        assert_eq!(packages("1 - 3"), both);
+        assert_eq!(packages("1 || 3"), both);
+        assert_eq!(packages("*"), both);
+    }
 }
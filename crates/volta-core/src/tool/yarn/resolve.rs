@@ -1,17 +1,25 @@
 //! Provides resolution of Yarn requirements into specific versions
 
+use super::super::http::{self, HttpContext};
+use super::super::integrity;
+use super::super::npmrc::authorize;
+use super::super::outdated::Available;
 use super::super::registry::{
     public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
     NPM_ABBREVIATED_ACCEPT_HEADER,
 };
 use super::super::registry_fetch_error;
 use super::metadata::{RawYarnIndex, YarnIndex};
-use crate::error::{Context, ErrorKind, Fallible};
+use super::{registry_package, registry_packages, BERRY_PACKAGE, CLASSIC_PACKAGE};
+use crate::error::{ErrorKind, Fallible};
 use crate::hook::ToolHooks;
+use crate::inventory::yarn_versions;
+use crate::offline::{ensure_online, is_offline, resolve_fetched};
+use crate::policy::find_fetched;
 use crate::session::Session;
 use crate::style::progress_spinner;
 use crate::tool::Yarn;
-use crate::version::{parse_version, VersionSpec, VersionTag};
+use crate::version::{parse_requirements, parse_version, VersionSpec, VersionTag};
 use attohttpc::header::ACCEPT;
 use attohttpc::Response;
 use log::debug;
@@ -20,13 +28,53 @@ use semver::{Version, VersionReq};
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
     let hooks = session.hooks()?.yarn();
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks),
+        matching if is_offline() => resolve_fetched("yarn", matching, yarn_versions()?),
+        VersionSpec::Semver(requirement) => {
+            match find_fetched("yarn", &requirement, yarn_versions)? {
+                Some(version) => Ok(version),
+                None => resolve_semver(requirement, hooks),
+            }
+        }
         VersionSpec::Exact(version) => Ok(version),
         VersionSpec::None => resolve_tag(VersionTag::Latest, hooks),
         VersionSpec::Tag(tag) => resolve_tag(tag, hooks),
     }
 }
 
+/// Determine the newest Yarn versions that `current` can be upgraded to
+///
+/// Without hooks, both versions come from a single fetch of the registry metadata of the package
+/// that `current` was published as. Hooks may point at the legacy index formats, so with hooks we
+/// resolve each version as `volta install` would.
+pub(crate) fn resolve_available(
+    current: &Version,
+    hooks: Option<&ToolHooks<Yarn>>,
+) -> Fallible<Available> {
+    match hooks {
+        None
+        | Some(&ToolHooks {
+            latest: None,
+            index: None,
+            ..
+        }) => {
+            let (_, index) = fetch_yarn_index(registry_package(current))?;
+            index.available(current).ok_or_else(|| {
+                ErrorKind::YarnVersionNotFound {
+                    matching: "latest".into(),
+                }
+                .into()
+            })
+        }
+        _ => {
+            let same_major = parse_requirements(format!("^{}", current.major))?;
+            let wanted = resolve_semver(same_major, hooks)?;
+            let latest = resolve_tag(VersionTag::Latest, hooks)?;
+
+            Ok(Available::new(current, Some(&wanted), latest))
+        }
+    }
+}
+
 fn resolve_tag(tag: VersionTag, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Version> {
     // This triage is complicated because we need to maintain the legacy behavior of hooks
     // First, if the tag is 'latest' and we have a 'latest' hook, we use the old behavior
@@ -69,38 +117,74 @@ fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Yarn>>) -> Fall
     }
 }
 
-fn fetch_yarn_index() -> Fallible<(String, PackageIndex)> {
-    let url = public_registry_index("yarn");
+/// Determine the URL of the registry metadata that has the integrity of a version of Yarn, if any
+///
+/// A `yarn.index` hook points at the legacy releases format, which doesn't include hashes. If the
+/// tarball is still downloaded from the registry, it is verified against the registry metadata,
+/// but a tarball from a `yarn.distro` hook has no metadata to be verified against.
+pub(super) fn integrity_index_url(
+    hooks: Option<&ToolHooks<Yarn>>,
+    version: &Version,
+) -> Fallible<Option<String>> {
+    match hooks {
+        Some(&ToolHooks {
+            index: Some(_),
+            distro: Some(_),
+            ..
+        }) => Ok(None),
+        _ => public_registry_index(registry_package(version)).map(Some),
+    }
+}
+
+fn fetch_yarn_index(package: &str) -> Fallible<(String, PackageIndex)> {
+    let url = public_registry_index(package)?;
+    ensure_online(&url)?;
     let spinner = progress_spinner(format!("Fetching public registry: {}", url));
-    let metadata: RawPackageMetadata = attohttpc::get(&url)
+    let metadata: RawPackageMetadata = authorize(http::get(&url)?, &url)?
         .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
         .send()
         .and_then(Response::error_for_status)
         .and_then(Response::json)
-        .with_context(registry_fetch_error("Yarn", &url))?;
+        .with_http_context(&url, registry_fetch_error("Yarn", &url))?;
 
     spinner.finish_and_clear();
+    integrity::record_published(&metadata);
     Ok((url, metadata.into()))
 }
 
+/// Resolve a tag from the registry
+///
+/// `stable` and `canary` are the channels of Yarn 2 and later, where `stable` is published as the
+/// `latest` tag. Any other tag is looked up for Yarn 1 first, so `latest` keeps resolving to it.
 fn resolve_custom_tag(tag: String) -> Fallible<Version> {
-    let (url, mut index) = fetch_yarn_index()?;
+    let searches = match tag.as_str() {
+        "stable" => vec![(BERRY_PACKAGE, "latest")],
+        "canary" => vec![(BERRY_PACKAGE, "canary")],
+        _ => vec![
+            (CLASSIC_PACKAGE, tag.as_str()),
+            (BERRY_PACKAGE, tag.as_str()),
+        ],
+    };
 
-    match index.tags.remove(&tag) {
-        Some(version) => {
+    for (package, dist_tag) in searches {
+        let (url, mut index) = fetch_yarn_index(package)?;
+
+        if let Some(version) = index.tags.remove(dist_tag) {
             debug!("Found yarn@{} matching tag '{}' from {}", version, tag, url);
-            Ok(version)
+            return Ok(version);
         }
-        None => Err(ErrorKind::YarnVersionNotFound { matching: tag }.into()),
     }
+
+    Err(ErrorKind::YarnVersionNotFound { matching: tag }.into())
 }
 
 fn resolve_latest_legacy(url: String) -> Fallible<Version> {
-    let response_text = attohttpc::get(&url)
+    ensure_online(&url)?;
+    let response_text = http::get(&url)?
         .send()
         .and_then(Response::error_for_status)
         .and_then(Response::text)
-        .with_context(|| ErrorKind::YarnLatestFetchError {
+        .with_http_context(&url, || ErrorKind::YarnLatestFetchError {
             from_url: url.clone(),
         })?;
 
@@ -108,36 +192,43 @@ fn resolve_latest_legacy(url: String) -> Fallible<Version> {
     parse_version(response_text)
 }
 
+/// Resolve a requirement from the registry
+///
+/// Yarn 1 is searched first, and only requirements that it can't satisfy are resolved from the
+/// releases of Yarn 2 and later. Requirements that clearly exclude Yarn 1, such as `>=2`, skip
+/// fetching its index entirely.
 fn resolve_semver_from_registry(matching: VersionReq) -> Fallible<Version> {
-    let (url, index) = fetch_yarn_index()?;
+    for &package in registry_packages(&matching) {
+        let (url, index) = fetch_yarn_index(package)?;
 
-    let details_opt = index
-        .entries
-        .into_iter()
-        .find(|PackageDetails { version, .. }| matching.matches(version));
+        let details_opt = index
+            .entries
+            .into_iter()
+            .find(|PackageDetails { version, .. }| matching.matches(version));
 
-    match details_opt {
-        Some(details) => {
+        if let Some(details) = details_opt {
             debug!(
                 "Found yarn@{} matching requirement '{}' from {}",
                 details.version, matching, url
             );
-            Ok(details.version)
-        }
-        None => Err(ErrorKind::YarnVersionNotFound {
-            matching: matching.to_string(),
+            return Ok(details.version);
         }
-        .into()),
     }
+
+    Err(ErrorKind::YarnVersionNotFound {
+        matching: matching.to_string(),
+    }
+    .into())
 }
 
 fn resolve_semver_legacy(matching: VersionReq, url: String) -> Fallible<Version> {
+    ensure_online(&url)?;
     let spinner = progress_spinner(format!("Fetching public registry: {}", url));
-    let releases: RawYarnIndex = attohttpc::get(&url)
+    let releases: RawYarnIndex = http::get(&url)?
         .send()
         .and_then(Response::error_for_status)
         .and_then(Response::json)
-        .with_context(registry_fetch_error("Yarn", &url))?;
+        .with_http_context(&url, registry_fetch_error("Yarn", &url))?;
     let index = YarnIndex::from(releases);
     let releases = index.entries;
     spinner.finish_and_clear();
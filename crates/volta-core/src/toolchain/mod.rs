@@ -75,6 +75,7 @@ impl Toolchain {
                 self.platform = Some(PlatformSpec {
                     node: node_version.clone(),
                     npm: None,
+                    pnpm: None,
                     yarn: None,
                 });
                 dirty = true;
@@ -105,6 +106,23 @@ impl Toolchain {
         Ok(())
     }
 
+    /// Set the active pnpm version in the default platform file.
+    pub fn set_active_pnpm(&mut self, pnpm: Option<Version>) -> Fallible<()> {
+        if let Some(platform) = self.platform.as_mut() {
+            if platform.pnpm != pnpm {
+                platform.pnpm = pnpm;
+                self.save()?;
+            }
+        } else if pnpm.is_some() {
+            return Err(ErrorKind::NoDefaultNodeVersion {
+                tool: "pnpm".into(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
     /// Set the active Npm version in the default platform file.
     pub fn set_active_npm(&mut self, npm: Option<Version>) -> Fallible<()> {
         if let Some(platform) = self.platform.as_mut() {
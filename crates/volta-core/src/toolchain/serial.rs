@@ -17,6 +17,11 @@ pub struct NodeVersion {
 pub struct Platform {
     #[serde(default)]
     pub node: Option<NodeVersion>,
+    // Note: pnpm is omitted when unset so that existing platform files are unchanged
+    #[serde(default)]
+    #[serde(with = "option_version_serde")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pnpm: Option<Version>,
     #[serde(default)]
     #[serde(with = "option_version_serde")]
     pub yarn: Option<Version>,
@@ -29,6 +34,7 @@ impl Platform {
                 runtime: source.node.clone(),
                 npm: source.npm.clone(),
             }),
+            pnpm: source.pnpm.clone(),
             yarn: source.yarn.clone(),
         }
     }
@@ -54,10 +60,12 @@ impl TryFrom<String> for Platform {
 
 impl From<Platform> for Option<PlatformSpec> {
     fn from(platform: Platform) -> Option<PlatformSpec> {
+        let pnpm = platform.pnpm;
         let yarn = platform.yarn;
         platform.node.map(|node_version| PlatformSpec {
             node: node_version.runtime,
             npm: node_version.npm,
+            pnpm,
             yarn,
         })
     }
@@ -78,6 +86,7 @@ pub mod tests {
     "runtime": "4.5.6",
     "npm": "7.8.9"
   },
+  "pnpm": "7.9.0",
   "yarn": "1.2.3"
 }"#;
 
@@ -86,6 +95,7 @@ pub mod tests {
         let json_str = BASIC_JSON_STR.to_string();
         let platform = Platform::try_from(json_str).expect("could not parse JSON string");
         let expected_platform = Platform {
+            pnpm: Some(Version::parse("7.9.0").expect("could not parse version")),
             yarn: Some(Version::parse("1.2.3").expect("could not parse version")),
             node: Some(NodeVersion {
                 runtime: Version::parse("4.5.6").expect("could not parse version"),
@@ -101,6 +111,7 @@ pub mod tests {
         let platform = Platform::try_from(json_str).expect("could not parse JSON string");
         let expected_platform = Platform {
             node: None,
+            pnpm: None,
             yarn: None,
         };
         assert_eq!(platform, expected_platform);
@@ -109,6 +120,7 @@ pub mod tests {
     #[test]
     fn test_into_json() {
         let platform_spec = platform::PlatformSpec {
+            pnpm: Some(Version::parse("7.9.0").expect("could not parse version")),
             yarn: Some(Version::parse("1.2.3").expect("could not parse version")),
             node: Version::parse("4.5.6").expect("could not parse version"),
             npm: Some(Version::parse("7.8.9").expect("could not parse version")),
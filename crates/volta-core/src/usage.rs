@@ -0,0 +1,316 @@
+//! Provides the local usage history, which records the tools that are run along with the Node
+//! version and project they were run with, so that `volta stats` can report what is actually used.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::event::ResolvedVersion;
+use crate::fs::rename;
+use crate::layout::volta_home;
+use crate::sync::VoltaLock;
+use crate::tool::PackageConfig;
+use chrono::{Local, TimeZone};
+use fs_utils::ensure_containing_dir_exists;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+/// Environment variable that enables the usage history, in addition to the `usageHistory` setting
+pub const USAGE_HISTORY_VAR: &str = "VOLTA_USAGE_HISTORY";
+
+/// The size at which the history is rotated, keeping the previous file in place of the oldest
+const MAX_HISTORY_SIZE: u64 = 1024 * 1024;
+
+/// Determine whether runs of tools are added to the usage history
+pub fn is_enabled() -> bool {
+    env::var_os(USAGE_HISTORY_VAR).is_some() || config::usage_history()
+}
+
+/// A single run of a tool, stored as one line of JSON in the usage history
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct UsageRecord {
+    pub timestamp: u64,
+    pub tool: String,
+    pub node: Option<ResolvedVersion>,
+    /// The root of the project the tool was run in, if any
+    pub project: Option<PathBuf>,
+    pub duration_ms: u64,
+    pub exit_code: i32,
+}
+
+/// Add a record to the usage history, rotating it once it grows too large
+///
+/// Failures are only logged, since a missing record isn't worth failing the tool that was run.
+pub(crate) fn append(record: &UsageRecord) {
+    if let Err(error) = try_append(record) {
+        debug!("Could not write to the usage history: {}", error);
+    }
+}
+
+fn try_append(record: &UsageRecord) -> Result<(), Box<dyn std::error::Error>> {
+    let home = volta_home()?;
+    let history_file = home.usage_history_file();
+    ensure_containing_dir_exists(&history_file)?;
+
+    // The size is checked again under the lock, so that runs finishing at the same time can't
+    // both rotate the history and overwrite the previous file with a nearly empty one. If the
+    // lock is held elsewhere, the history is left for a later run to rotate.
+    if history_size(history_file) > MAX_HISTORY_SIZE {
+        if let Some(_lock) = VoltaLock::try_acquire() {
+            if history_size(history_file) > MAX_HISTORY_SIZE {
+                rename(history_file, home.rotated_usage_history_file())?;
+            }
+        }
+    }
+
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+
+    // The whole line is written at once, so that shims running at the same time don't interleave
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_file)?
+        .write_all(&line)?;
+    Ok(())
+}
+
+fn history_size(file: &Path) -> u64 {
+    file.metadata().map_or(0, |metadata| metadata.len())
+}
+
+/// Read every record in the usage history, oldest first
+pub fn read_history() -> Fallible<Vec<UsageRecord>> {
+    let home = volta_home()?;
+    let mut records = Vec::new();
+
+    for file in [home.rotated_usage_history_file(), home.usage_history_file()] {
+        read_records(file, &mut records).with_context(|| ErrorKind::ReadUsageHistoryError {
+            file: file.to_path_buf(),
+        })?;
+    }
+
+    Ok(records)
+}
+
+fn read_records(file: &Path, records: &mut Vec<UsageRecord>) -> io::Result<()> {
+    let reader = match File::open(file) {
+        Ok(file) => BufReader::new(file),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error),
+    };
+
+    for line in reader.lines() {
+        // A line can be cut short if a shim is interrupted while writing it
+        match serde_json::from_str(&line?) {
+            Ok(record) => records.push(record),
+            Err(error) => debug!(
+                "Skipping invalid usage record in {}: {}",
+                file.display(),
+                error
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// A summary of the usage history
+pub struct UsageStats {
+    /// The number of runs in the history
+    pub runs: usize,
+    /// The date of the oldest run in the history, e.g. `2022-10-14`
+    pub since: Option<String>,
+    pub node: Vec<NodeUsage>,
+    pub packages: Vec<PackageUsage>,
+    /// The installed packages that none of the runs in the history used
+    pub unused_packages: Vec<String>,
+}
+
+/// The runs of a Node version and the projects that they were in
+pub struct NodeUsage {
+    pub version: String,
+    pub runs: usize,
+    pub projects: BTreeSet<PathBuf>,
+}
+
+/// The runs of the binaries of an installed package and the projects that they were in
+pub struct PackageUsage {
+    pub name: String,
+    pub runs: usize,
+    pub projects: BTreeSet<PathBuf>,
+}
+
+impl UsageStats {
+    /// Summarize `records`, attributing runs of binaries to the `packages` that installed them
+    pub fn new(records: &[UsageRecord], packages: &BTreeSet<PackageConfig>) -> Self {
+        let package_of_bin: BTreeMap<&str, &str> = packages
+            .iter()
+            .flat_map(|package| {
+                package
+                    .bins
+                    .iter()
+                    .map(move |bin| (bin.as_str(), package.name.as_str()))
+            })
+            .collect();
+
+        let mut node: BTreeMap<&str, NodeUsage> = BTreeMap::new();
+        let mut used_packages: BTreeMap<&str, PackageUsage> = BTreeMap::new();
+
+        for record in records {
+            if let Some(version) = &record.node {
+                let usage = node.entry(&version.version).or_insert_with(|| NodeUsage {
+                    version: version.version.clone(),
+                    runs: 0,
+                    projects: BTreeSet::new(),
+                });
+                usage.runs += 1;
+                usage.projects.extend(record.project.clone());
+            }
+
+            if let Some(&package) = package_of_bin.get(record.tool.as_str()) {
+                let usage = used_packages
+                    .entry(package)
+                    .or_insert_with(|| PackageUsage {
+                        name: package.to_string(),
+                        runs: 0,
+                        projects: BTreeSet::new(),
+                    });
+                usage.runs += 1;
+                usage.projects.extend(record.project.clone());
+            }
+        }
+
+        let unused_packages = packages
+            .iter()
+            .filter(|package| !used_packages.contains_key(package.name.as_str()))
+            .map(|package| package.name.clone())
+            .collect();
+
+        UsageStats {
+            runs: records.len(),
+            since: records
+                .iter()
+                .map(|record| record.timestamp)
+                .min()
+                .map(format_date),
+            node: sorted_by_runs(node.into_values(), |usage| usage.runs),
+            packages: sorted_by_runs(used_packages.into_values(), |usage| usage.runs),
+            unused_packages,
+        }
+    }
+}
+
+/// Sort the most used items first, keeping ties in their original order
+fn sorted_by_runs<T>(items: impl Iterator<Item = T>, runs: fn(&T) -> usize) -> Vec<T> {
+    let mut items: Vec<T> = items.collect();
+    items.sort_by_key(|item| std::cmp::Reverse(runs(item)));
+    items
+}
+
+fn format_date(timestamp: u64) -> String {
+    Local
+        .timestamp_millis(timestamp as i64)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::PlatformSpec;
+    use crate::tool::package::PackageManager;
+    use semver::Version;
+
+    fn record(tool: &str, node: &str, project: Option<&str>) -> UsageRecord {
+        UsageRecord {
+            timestamp: 1_665_700_000_000,
+            tool: tool.into(),
+            node: Some(ResolvedVersion {
+                version: node.into(),
+                source: if project.is_some() {
+                    "project".into()
+                } else {
+                    "default".into()
+                },
+            }),
+            project: project.map(PathBuf::from),
+            duration_ms: 100,
+            exit_code: 0,
+        }
+    }
+
+    fn package(name: &str, bins: &[&str]) -> PackageConfig {
+        PackageConfig {
+            name: name.into(),
+            version: Version::parse("1.0.0").unwrap(),
+            platform: PlatformSpec {
+                node: Version::parse("16.18.0").unwrap(),
+                npm: None,
+                pnpm: None,
+                yarn: None,
+            },
+            bins: bins.iter().map(|bin| bin.to_string()).collect(),
+            manager: PackageManager::Npm,
+        }
+    }
+
+    #[test]
+    fn summarizes_node_versions() {
+        let records = vec![
+            record("node", "12.22.12", Some("/projects/legacy")),
+            record("node", "16.18.0", None),
+            record("npm", "16.18.0", Some("/projects/app")),
+        ];
+        let stats = UsageStats::new(&records, &BTreeSet::new());
+
+        assert_eq!(stats.runs, 3);
+        assert_eq!(stats.node.len(), 2);
+        assert_eq!(stats.node[0].version, "16.18.0");
+        assert_eq!(stats.node[0].runs, 2);
+        assert_eq!(
+            stats.node[0].projects,
+            BTreeSet::from([PathBuf::from("/projects/app")])
+        );
+        assert_eq!(stats.node[1].version, "12.22.12");
+        assert_eq!(
+            stats.node[1].projects,
+            BTreeSet::from([PathBuf::from("/projects/legacy")])
+        );
+    }
+
+    #[test]
+    fn attributes_binaries_to_packages() {
+        let records = vec![
+            record("tsc", "16.18.0", Some("/projects/app")),
+            record("tsserver", "16.18.0", None),
+            record("node", "16.18.0", None),
+        ];
+        let packages = BTreeSet::from([
+            package("typescript", &["tsc", "tsserver"]),
+            package("cowsay", &["cowsay"]),
+        ]);
+        let stats = UsageStats::new(&records, &packages);
+
+        assert_eq!(stats.packages.len(), 1);
+        assert_eq!(stats.packages[0].name, "typescript");
+        assert_eq!(stats.packages[0].runs, 2);
+        assert_eq!(stats.unused_packages, vec!["cowsay".to_string()]);
+    }
+
+    #[test]
+    fn parses_record() {
+        let record: UsageRecord = serde_json::from_str(
+            r#"{"timestamp":1665700000000,"tool":"tsc","node":{"version":"16.18.0","source":"project"},"project":"/projects/app","duration_ms":812,"exit_code":2}"#,
+        )
+        .unwrap();
+
+        assert_eq!(record.tool, "tsc");
+        assert_eq!(record.exit_code, 2);
+        assert_eq!(record.project, Some(PathBuf::from("/projects/app")));
+    }
+}
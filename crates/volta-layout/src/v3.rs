@@ -12,18 +12,27 @@ layout! {
                 "index.json": node_index_file;
                 "index.json.expires": node_index_expiry_file;
             }
+            "packages": package_cache_dir {}
+            "recent-projects.json": recent_projects_file;
         }
         "bin": shim_dir {}
-        "log": log_dir {}
+        "log": log_dir {
+            "usage.jsonl": usage_history_file;
+            "usage.1.jsonl": rotated_usage_history_file;
+        }
         "tools": tools_dir {
             "inventory": inventory_dir {
                 "node": node_inventory_dir {}
+                "node-channels": node_channel_inventory_dir {}
                 "npm": npm_inventory_dir {}
+                "pnpm": pnpm_inventory_dir {}
                 "yarn": yarn_inventory_dir {}
             }
             "image": image_dir {
                 "node": node_image_root_dir {}
+                "node-channels": node_channel_image_root_dir {}
                 "npm": npm_image_root_dir {}
+                "pnpm": pnpm_image_root_dir {}
                 "yarn": yarn_image_root_dir {}
                 "packages": package_image_root_dir {}
             }
@@ -35,6 +44,7 @@ layout! {
             }
         }
         "tmp": tmp_dir {}
+        "config.json": config_file;
         "hooks.json": default_hooks_file;
         "layout.v3": layout_file;
     }
@@ -45,6 +55,10 @@ impl VoltaHome {
         path_buf!(self.node_image_root_dir.clone(), node)
     }
 
+    pub fn node_channel_image_dir(&self, node: &str) -> PathBuf {
+        path_buf!(self.node_channel_image_root_dir.clone(), node)
+    }
+
     pub fn npm_image_dir(&self, npm: &str) -> PathBuf {
         path_buf!(self.npm_image_root_dir.clone(), npm)
     }
@@ -53,6 +67,14 @@ impl VoltaHome {
         path_buf!(self.npm_image_dir(npm), "bin")
     }
 
+    pub fn pnpm_image_dir(&self, version: &str) -> PathBuf {
+        path_buf!(self.pnpm_image_root_dir.clone(), version)
+    }
+
+    pub fn pnpm_image_bin_dir(&self, version: &str) -> PathBuf {
+        path_buf!(self.pnpm_image_dir(version), "bin")
+    }
+
     pub fn yarn_image_dir(&self, version: &str) -> PathBuf {
         path_buf!(self.yarn_image_root_dir.clone(), version)
     }
@@ -83,6 +105,13 @@ impl VoltaHome {
         )
     }
 
+    pub fn node_channel_npm_version_file(&self, version: &str) -> PathBuf {
+        path_buf!(
+            self.node_channel_inventory_dir.clone(),
+            format!("node-v{}-npm", version)
+        )
+    }
+
     pub fn shim_file(&self, toolname: &str) -> PathBuf {
         path_buf!(self.shim_dir.clone(), executable(toolname))
     }
@@ -101,6 +130,10 @@ impl VoltaHome {
     pub fn node_image_bin_dir(&self, node: &str) -> PathBuf {
         self.node_image_dir(node)
     }
+
+    pub fn node_channel_image_bin_dir(&self, node: &str) -> PathBuf {
+        self.node_channel_image_dir(node)
+    }
 }
 
 #[cfg(unix)]
@@ -108,4 +141,8 @@ impl VoltaHome {
     pub fn node_image_bin_dir(&self, node: &str) -> PathBuf {
         path_buf!(self.node_image_dir(node), "bin")
     }
+
+    pub fn node_channel_image_bin_dir(&self, node: &str) -> PathBuf {
+        path_buf!(self.node_channel_image_dir(node), "bin")
+    }
 }
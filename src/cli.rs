@@ -36,6 +36,20 @@ pub(crate) struct Volta {
     )]
     pub(crate) quiet: bool,
 
+    #[structopt(
+        long = "offline",
+        help = "Resolves versions only from the local inventory, without accessing the network",
+        global = true
+    )]
+    pub(crate) offline: bool,
+
+    #[structopt(
+        long = "prefer-local",
+        help = "Satisfies version ranges from already-fetched versions before checking the index",
+        global = true
+    )]
+    pub(crate) prefer_local: bool,
+
     #[structopt(
         short = "v",
         long = "version",
@@ -80,10 +94,38 @@ pub(crate) enum Subcommand {
     #[structopt(name = "pin", author = "", version = "")]
     Pin(command::Pin),
 
+    /// Upgrades your default tools or installed packages to newer versions
+    #[structopt(name = "upgrade", author = "", version = "")]
+    Upgrade(command::Upgrade),
+
+    /// Removes fetched tool versions that are no longer used
+    #[structopt(name = "prune", author = "", version = "")]
+    Prune(command::Prune),
+
     /// Displays the current toolchain
     #[structopt(name = "list", alias = "ls", author = "", version = "")]
     List(command::List),
 
+    /// Reports newer versions of your default tools and installed packages
+    #[structopt(name = "outdated", author = "", version = "")]
+    Outdated(command::Outdated),
+
+    /// Reads and changes Volta configuration settings
+    #[structopt(name = "config", author = "", version = "")]
+    Config(command::Config),
+
+    /// Writes your default toolchain and installed packages to `stdout` as a portable file
+    #[structopt(name = "export", author = "", version = "")]
+    Export(command::Export),
+
+    /// Installs the toolchain and packages from a file written by `volta export`
+    #[structopt(name = "import", author = "", version = "")]
+    Import(command::Import),
+
+    /// Summarizes which Node versions and packages you have used, from the usage history
+    #[structopt(name = "stats", author = "", version = "")]
+    Stats(command::Stats),
+
     /// Generates Volta completions
     #[structopt(
         name = "completions",
@@ -136,7 +178,14 @@ impl Subcommand {
             Subcommand::Install(install) => install.run(session),
             Subcommand::Uninstall(uninstall) => uninstall.run(session),
             Subcommand::Pin(pin) => pin.run(session),
+            Subcommand::Upgrade(upgrade) => upgrade.run(session),
+            Subcommand::Prune(prune) => prune.run(session),
             Subcommand::List(list) => list.run(session),
+            Subcommand::Outdated(outdated) => outdated.run(session),
+            Subcommand::Config(config) => config.run(session),
+            Subcommand::Export(export) => export.run(session),
+            Subcommand::Import(import) => import.run(session),
+            Subcommand::Stats(stats) => stats.run(session),
             Subcommand::Completions(completions) => completions.run(session),
             Subcommand::Which(which) => which.run(session),
             Subcommand::Use(r#use) => r#use.run(session),
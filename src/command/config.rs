@@ -0,0 +1,74 @@
+use log::info;
+use structopt::StructOpt;
+
+use volta_core::config::{self, CONFIG_KEYS};
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{note_prefix, success_prefix};
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) enum Config {
+    /// Prints the value of a setting, if it is set
+    #[structopt(name = "get", author = "", version = "")]
+    Get {
+        /// The setting to print, e.g. `registry`
+        key: String,
+    },
+
+    /// Changes a setting in your user configuration file
+    #[structopt(name = "set", author = "", version = "")]
+    Set {
+        /// The setting to change, e.g. `registry`
+        key: String,
+
+        /// The new value of the setting
+        value: String,
+    },
+
+    /// Lists the settings that are in effect, including those from project configuration files
+    #[structopt(name = "list", alias = "ls", author = "", version = "")]
+    List,
+}
+
+impl Command for Config {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Config);
+
+        match self {
+            Config::Get { key } => {
+                if let Some(value) = session.config()?.get(&key)? {
+                    println!("{}", value);
+                }
+            }
+            Config::Set { key, value } => {
+                config::set(&key, &value)?;
+                info!("{} set {} to {}", success_prefix(), key, value);
+            }
+            Config::List => {
+                let settings = format_settings(session.config()?)?;
+                if settings.is_empty() {
+                    info!("{} no settings are configured", note_prefix());
+                } else {
+                    println!("{}", settings);
+                }
+            }
+        }
+
+        session.add_event_end(ActivityKind::Config, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// Format the settings that are set as `key = value` lines
+fn format_settings(config: &config::Config) -> Fallible<String> {
+    let mut lines = Vec::new();
+    for key in CONFIG_KEYS.iter() {
+        if let Some(value) = config.get(key)? {
+            lines.push(format!("{} = {}", key, value));
+        }
+    }
+
+    Ok(lines.join("\n"))
+}
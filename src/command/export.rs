@@ -0,0 +1,22 @@
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::tool::export::ToolchainExport;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Export {}
+
+impl Command for Export {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Export);
+
+        let export = ToolchainExport::current(session)?;
+        println!("{}", export.to_json()?);
+
+        session.add_event_end(ActivityKind::Export, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
@@ -17,8 +17,14 @@ impl Command for Fetch {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Fetch);
 
-        for tool in tool::Spec::from_strings(&self.tools, "fetch")? {
-            tool.resolve(session)?.fetch(session)?;
+        let tools = tool::Spec::from_strings(&self.tools, "fetch")?
+            .into_iter()
+            .map(|tool| tool.resolve(session))
+            .collect::<Fallible<Vec<_>>>()?;
+
+        tool::prefetch(&tools, session)?;
+        for tool in tools {
+            tool.fetch(session)?;
         }
 
         session.add_event_end(ActivityKind::Fetch, ExitCode::Success);
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use log::info;
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{note_prefix, success_prefix};
+use volta_core::tool::export::ToolchainExport;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Import {
+    /// The file written by `volta export`
+    #[structopt(parse(from_os_str))]
+    file: PathBuf,
+
+    /// Only report how the current toolchain differs from the file, without changing anything
+    #[structopt(long = "dry-run")]
+    dry_run: bool,
+}
+
+impl Command for Import {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Import);
+
+        let export = ToolchainExport::from_file(&self.file)?;
+        let drift = export.drift(session)?;
+
+        if drift.is_empty() {
+            info!(
+                "{} your toolchain already matches {}",
+                note_prefix(),
+                self.file.display()
+            );
+        } else {
+            info!(
+                "{} your toolchain differs from {}:",
+                note_prefix(),
+                self.file.display()
+            );
+            for difference in &drift {
+                info!("    {}", difference);
+            }
+
+            if self.dry_run {
+                info!(
+                    "{} Run `volta import {}` to apply the changes above.",
+                    note_prefix(),
+                    self.file.display()
+                );
+            } else {
+                export.import(session)?;
+                info!(
+                    "{} imported toolchain from {}",
+                    success_prefix(),
+                    self.file.display()
+                );
+            }
+        }
+
+        session.add_event_end(ActivityKind::Import, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
@@ -2,7 +2,7 @@ use structopt::StructOpt;
 
 use volta_core::error::{ExitCode, Fallible};
 use volta_core::session::{ActivityKind, Session};
-use volta_core::tool::Spec;
+use volta_core::tool::{prefetch, Spec};
 
 use crate::command::Command;
 
@@ -17,8 +17,15 @@ impl Command for Install {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Install);
 
-        for tool in Spec::from_strings(&self.tools, "install")? {
-            tool.resolve(session)?.install(session)?;
+        let tools = Spec::from_strings(&self.tools, "install")?
+            .into_iter()
+            .map(|tool| tool.resolve(session))
+            .collect::<Fallible<Vec<_>>>()?;
+
+        // Download the tools up front, since installing them has to happen one at a time
+        prefetch(&tools, session)?;
+        for tool in tools {
+            tool.install(session)?;
         }
 
         session.add_event_end(ActivityKind::Install, ExitCode::Success);
@@ -306,6 +306,7 @@ fn format_package_manager(package_manager: &PackageManager) -> String {
 fn format_package_manager_kind(kind: PackageManagerKind) -> String {
     match kind {
         PackageManagerKind::Npm => "npm".into(),
+        PackageManagerKind::Pnpm => "pnpm".into(),
         PackageManagerKind::Yarn => "Yarn".into(),
     }
 }
@@ -777,6 +778,9 @@ See options for more detailed reports by running `volta list --help`.";
                         version: Version::from((3, 0, 1)),
                     },
                     node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
                     tools: vec!["create-react-app".to_string()],
                 },
                 Package::Default {
@@ -785,6 +789,9 @@ See options for more detailed reports by running `volta list --help`.";
                         version: Version::from((3, 4, 3)),
                     },
                     node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
                 },
             ];
@@ -836,6 +843,9 @@ See options for more detailed reports by running `volta list --help`.";
                         version: Version::from((3, 4, 3)),
                     },
                     node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
                 },
             ];
@@ -959,6 +969,19 @@ See `volta help install` for details and more options.";
             );
         }
 
+        #[test]
+        fn none_installed_pnpm() {
+            let expected = "⚡️ No pnpm versions installed.
+
+You can install a pnpm version by running `volta install pnpm`.
+See `volta help install` for details and more options.";
+
+            assert_eq!(
+                display_package_managers(PackageManagerKind::Pnpm, &[]),
+                expected
+            );
+        }
+
         #[test]
         fn single_default_npm() {
             let expected = "⚡️ Custom npm versions in your toolchain:
@@ -1163,6 +1186,9 @@ See `volta help install` for details and more options.";
                     version: Version::from((3, 10, 1)),
                 },
                 node: NODE_12.clone(),
+                npm: None,
+                pnpm: None,
+                yarn: None,
                 tools: vec!["ember".to_string()],
             }];
 
@@ -1245,6 +1271,9 @@ See `volta help install` for details and more options.";
                         version: Version::from((3, 10, 1)),
                     },
                     node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
                     tools: vec!["ember".to_string()],
                 },
                 Package::Project {
@@ -1289,6 +1318,9 @@ See `volta help install` for details and more options.";
                     version: Version::from((3, 10, 1)),
                 },
                 node: NODE_12.clone(),
+                npm: None,
+                pnpm: None,
+                yarn: None,
                 tools: vec!["ember".to_string()],
             }];
 
@@ -1371,6 +1403,9 @@ See `volta help install` for details and more options.";
                         version: Version::from((3, 10, 1)),
                     },
                     node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
                     tools: vec!["ember".to_string()],
                 },
                 Package::Project {
@@ -1606,6 +1641,9 @@ See `volta help install` for details and more options.";
                         version: Version::from((3, 4, 3)),
                     },
                     node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
                 },
                 Package::Project {
@@ -1624,6 +1662,9 @@ See `volta help install` for details and more options.";
                         version: Version::from((3, 8, 2)),
                     },
                     node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
                     tools: vec!["ember".to_string()],
                 },
             ];
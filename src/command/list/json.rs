@@ -0,0 +1,305 @@
+//! Define the "json" format style for list commands.
+//!
+//! The output is intended for scripts, so its shape is part of the public interface: any
+//! backwards-incompatible change must increment `SCHEMA_VERSION`.
+
+use std::path::Path;
+use std::slice;
+
+use semver::Version;
+use serde::Serialize;
+
+use super::{Node, Package, PackageManager, Source, Toolchain};
+
+/// The version of the JSON output schema
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Output<'a> {
+    schema_version: u32,
+    runtimes: Vec<Runtime<'a>>,
+    package_managers: Vec<Manager<'a>>,
+    packages: Vec<PackageEntry<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum SourceEntry<'a> {
+    Project { path: &'a Path },
+    Default,
+    None,
+}
+
+impl<'a> From<&'a Source> for SourceEntry<'a> {
+    fn from(source: &'a Source) -> Self {
+        match source {
+            Source::Project(path) => SourceEntry::Project { path },
+            Source::Default => SourceEntry::Default,
+            Source::None => SourceEntry::None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Runtime<'a> {
+    name: &'static str,
+    version: String,
+    source: SourceEntry<'a>,
+}
+
+#[derive(Serialize)]
+struct Manager<'a> {
+    name: String,
+    version: String,
+    source: SourceEntry<'a>,
+}
+
+/// The platform a package was installed with, where a package manager that wasn't part of it is
+/// `null`
+#[derive(Serialize)]
+struct Platform {
+    node: String,
+    npm: Option<String>,
+    pnpm: Option<String>,
+    yarn: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PackageEntry<'a> {
+    name: &'a str,
+    /// The installed version, which is unknown for project-local packages
+    version: Option<String>,
+    source: SourceEntry<'a>,
+    /// The platform the package was installed with, if it was installed globally
+    platform: Option<Platform>,
+    bins: &'a [String],
+}
+
+pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
+    let mut output = Output {
+        schema_version: SCHEMA_VERSION,
+        runtimes: Vec::new(),
+        package_managers: Vec::new(),
+        packages: Vec::new(),
+    };
+
+    match toolchain {
+        Toolchain::Node(runtimes) => output.runtimes = describe_runtimes(runtimes),
+        Toolchain::PackageManagers { managers, .. } => {
+            output.package_managers = describe_package_managers(managers)
+        }
+        Toolchain::Packages(packages) => output.packages = describe_packages(packages),
+        Toolchain::Tool { host_packages, .. } => output.packages = describe_packages(host_packages),
+        Toolchain::Active {
+            runtime,
+            package_managers,
+            packages,
+        } => {
+            if let Some(runtime) = runtime {
+                output.runtimes = describe_runtimes(slice::from_ref(&**runtime));
+            }
+            output.package_managers = describe_package_managers(package_managers);
+            output.packages = describe_packages(packages);
+        }
+        Toolchain::All {
+            runtimes,
+            package_managers,
+            packages,
+        } => {
+            output.runtimes = describe_runtimes(runtimes);
+            output.package_managers = describe_package_managers(package_managers);
+            output.packages = describe_packages(packages);
+        }
+    }
+
+    serde_json::to_string_pretty(&output).ok()
+}
+
+fn describe_runtimes(runtimes: &[Node]) -> Vec<Runtime<'_>> {
+    runtimes
+        .iter()
+        .map(|runtime| Runtime {
+            name: "node",
+            version: runtime.version.to_string(),
+            source: (&runtime.source).into(),
+        })
+        .collect()
+}
+
+fn describe_package_managers(package_managers: &[PackageManager]) -> Vec<Manager<'_>> {
+    package_managers
+        .iter()
+        .map(|manager| Manager {
+            name: manager.kind.to_string(),
+            version: manager.version.to_string(),
+            source: (&manager.source).into(),
+        })
+        .collect()
+}
+
+fn describe_packages(packages: &[Package]) -> Vec<PackageEntry<'_>> {
+    packages.iter().map(describe_package).collect()
+}
+
+fn describe_package(package: &Package) -> PackageEntry<'_> {
+    match package {
+        Package::Default {
+            details,
+            node,
+            npm,
+            pnpm,
+            yarn,
+            tools,
+        } => PackageEntry {
+            name: &details.name,
+            version: Some(details.version.to_string()),
+            source: SourceEntry::Default,
+            platform: Some(Platform {
+                node: node.to_string(),
+                npm: npm.as_ref().map(Version::to_string),
+                pnpm: pnpm.as_ref().map(Version::to_string),
+                yarn: yarn.as_ref().map(Version::to_string),
+            }),
+            bins: tools,
+        },
+        Package::Project { name, tools, path } => PackageEntry {
+            name,
+            version: None,
+            source: SourceEntry::Project { path },
+            platform: None,
+            bins: tools,
+        },
+        Package::Fetched(details) => PackageEntry {
+            name: &details.name,
+            version: Some(details.version.to_string()),
+            source: SourceEntry::None,
+            platform: None,
+            bins: &[],
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use lazy_static::lazy_static;
+    use semver::Version;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::command::list::{PackageDetails, PackageManagerKind};
+
+    lazy_static! {
+        static ref NODE_VERSION: Version = Version::from((12, 4, 0));
+        static ref TYPESCRIPT_VERSION: Version = Version::from((3, 4, 1));
+        static ref YARN_VERSION: Version = Version::from((1, 16, 0));
+        static ref PROJECT_PATH: PathBuf = PathBuf::from("/a/b/c");
+    }
+
+    fn parse(toolchain: &Toolchain) -> Value {
+        serde_json::from_str(&format(toolchain).expect("always produces output")).unwrap()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            parse(&Toolchain::Packages(vec![])),
+            json!({
+                "schemaVersion": 1,
+                "runtimes": [],
+                "packageManagers": [],
+                "packages": []
+            })
+        );
+    }
+
+    #[test]
+    fn active() {
+        let toolchain = Toolchain::Active {
+            runtime: Some(Box::new(Node {
+                source: Source::Project(PROJECT_PATH.clone()),
+                version: NODE_VERSION.clone(),
+            })),
+            package_managers: vec![PackageManager {
+                kind: PackageManagerKind::Yarn,
+                source: Source::Default,
+                version: YARN_VERSION.clone(),
+            }],
+            packages: vec![
+                Package::Default {
+                    details: PackageDetails {
+                        name: "typescript".into(),
+                        version: TYPESCRIPT_VERSION.clone(),
+                    },
+                    node: NODE_VERSION.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: Some(YARN_VERSION.clone()),
+                    tools: vec!["tsc".into(), "tsserver".into()],
+                },
+                Package::Project {
+                    name: "ember-cli".into(),
+                    tools: vec!["ember".into()],
+                    path: PROJECT_PATH.clone(),
+                },
+            ],
+        };
+
+        assert_eq!(
+            parse(&toolchain),
+            json!({
+                "schemaVersion": 1,
+                "runtimes": [{
+                    "name": "node",
+                    "version": "12.4.0",
+                    "source": { "type": "project", "path": "/a/b/c" }
+                }],
+                "packageManagers": [{
+                    "name": "yarn",
+                    "version": "1.16.0",
+                    "source": { "type": "default" }
+                }],
+                "packages": [
+                    {
+                        "name": "typescript",
+                        "version": "3.4.1",
+                        "source": { "type": "default" },
+                        "platform": {
+                            "node": "12.4.0",
+                            "npm": null,
+                            "pnpm": null,
+                            "yarn": "1.16.0"
+                        },
+                        "bins": ["tsc", "tsserver"]
+                    },
+                    {
+                        "name": "ember-cli",
+                        "version": null,
+                        "source": { "type": "project", "path": "/a/b/c" },
+                        "platform": null,
+                        "bins": ["ember"]
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn fetched_runtime() {
+        let toolchain = Toolchain::Node(vec![Node {
+            source: Source::None,
+            version: NODE_VERSION.clone(),
+        }]);
+
+        assert_eq!(
+            parse(&toolchain)["runtimes"],
+            json!([{
+                "name": "node",
+                "version": "12.4.0",
+                "source": { "type": "none" }
+            }])
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod human;
+mod json;
 mod plain;
 mod toolchain;
 
@@ -19,6 +20,7 @@ use volta_core::tool::PackageConfig;
 enum Format {
     Human,
     Plain,
+    Json,
 }
 
 impl FromStr for Format {
@@ -28,6 +30,7 @@ impl FromStr for Format {
         match s {
             "human" => Ok(Format::Human),
             "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
             _ => Err("No".into()),
         }
     }
@@ -89,6 +92,12 @@ enum Package {
         details: PackageDetails,
         /// The version of Node the package is installed against.
         node: Version,
+        /// The version of npm the package is installed against, if not the bundled one.
+        npm: Option<Version>,
+        /// The version of pnpm the package is installed against, if any.
+        pnpm: Option<Version>,
+        /// The version of Yarn the package is installed against, if any.
+        yarn: Option<Version>,
         /// The names of the tools associated with the package.
         tools: Vec<String>,
     },
@@ -112,6 +121,9 @@ impl Package {
             Source::Default => Package::Default {
                 details,
                 node: config.platform.node.clone(),
+                npm: config.platform.npm.clone(),
+                pnpm: config.platform.pnpm.clone(),
+                yarn: config.platform.yarn.clone(),
                 tools: config.bins.clone(),
             },
             Source::Project(path) => Package::Project {
@@ -154,6 +166,7 @@ struct Node {
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum PackageManagerKind {
     Npm,
+    Pnpm,
     Yarn,
 }
 
@@ -164,6 +177,7 @@ impl fmt::Display for PackageManagerKind {
             "{}",
             match self {
                 PackageManagerKind::Npm => "npm",
+                PackageManagerKind::Pnpm => "pnpm",
                 PackageManagerKind::Yarn => "yarn",
             }
         )
@@ -201,14 +215,18 @@ pub(crate) struct List {
     // `Option<Subcommand>` with `impl FromStr for Subcommand` for `StructOpt`
     // because StructOpt does not currently support custom parsing for enum
     // variants (as detailed in commit 5f9214ae).
-    /// The tool to lookup - `all`, `node`, `yarn`, or the name of a package or binary.
+    /// The tool to lookup - `all`, `node`, `npm`, `pnpm`, `yarn`, or the name of a package or binary.
     #[structopt(name = "tool")]
     subcommand: Option<String>,
 
     /// Specify the output format.
     ///
-    /// Defaults to `human` for TTYs, `plain` otherwise.
-    #[structopt(long = "format", raw(possible_values = r#"&["human", "plain"]"#))]
+    /// Defaults to `human` for TTYs, `plain` otherwise. The `json` format is
+    /// intended for scripts and includes a `schemaVersion` field.
+    #[structopt(
+        long = "format",
+        raw(possible_values = r#"&["human", "plain", "json"]"#)
+    )]
     format: Option<Format>,
 
     /// Show the currently-active tool(s).
@@ -233,6 +251,9 @@ enum Subcommand {
     /// Show locally cached npm versions.
     Npm,
 
+    /// Show locally cached pnpm versions.
+    Pnpm,
+
     /// Show locally cached Yarn versions.
     Yarn,
 
@@ -246,6 +267,7 @@ impl From<&str> for Subcommand {
             "all" => Subcommand::All,
             "node" => Subcommand::Node,
             "npm" => Subcommand::Npm,
+            "pnpm" => Subcommand::Pnpm,
             "yarn" => Subcommand::Yarn,
             s => Subcommand::PackageOrTool { name: s.into() },
         }
@@ -279,6 +301,7 @@ impl Command for List {
         let format = match self.output_format() {
             Format::Human => human::format,
             Format::Plain => plain::format,
+            Format::Json => json::format,
         };
 
         let filter = match (self.current, self.default) {
@@ -294,6 +317,7 @@ impl Command for List {
             Some(Subcommand::All) => Toolchain::all(project, default_platform)?,
             Some(Subcommand::Node) => Toolchain::node(project, default_platform, &filter)?,
             Some(Subcommand::Npm) => Toolchain::npm(project, default_platform, &filter)?,
+            Some(Subcommand::Pnpm) => Toolchain::pnpm(project, default_platform, &filter)?,
             Some(Subcommand::Yarn) => Toolchain::yarn(project, default_platform, &filter)?,
             Some(Subcommand::PackageOrTool { name }) => {
                 Toolchain::package_or_tool(&name, project, &filter)?
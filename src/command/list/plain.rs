@@ -351,6 +351,9 @@ mod tests {
                         version: TYPESCRIPT_VERSION.clone(),
                     },
                     node: NODE_VERSION.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
                     tools: vec!["tsc".into(), "tsserver".into()]
                 }])
                 .expect("Should always return a `String` if given a non-empty set")
@@ -388,6 +391,9 @@ mod tests {
                             version: Version::from((3, 10, 0)),
                         },
                         node: NODE_VERSION.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
                         tools: vec!["ember".into()],
                     },
                     Package::Fetched(PackageDetails {
@@ -432,6 +438,9 @@ mod tests {
                             version: TYPESCRIPT_VERSION.clone(),
                         },
                         node: NODE_VERSION.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
                         tools: vec!["tsc".into(), "tsserver".into()],
                     }
                 )
@@ -521,6 +530,9 @@ mod tests {
                                 version: Version::from((3, 10, 2)),
                             },
                             node: NODE_VERSION.clone(),
+                            npm: None,
+                            pnpm: None,
+                            yarn: None,
                             tools: vec!["ember".into()]
                         },
                         Package::Project {
@@ -534,6 +546,9 @@ mod tests {
                                 version: TYPESCRIPT_VERSION.clone(),
                             },
                             node: NODE_VERSION.clone(),
+                            npm: None,
+                            pnpm: None,
+                            yarn: None,
                             tools: vec!["tsc".into(), "tsserver".into()]
                         }
                     ]
@@ -1,8 +1,13 @@
+use std::path::Path;
+
 use super::{Filter, Node, Package, PackageManager, Source};
 use crate::command::list::PackageManagerKind;
 use semver::Version;
 use volta_core::error::Fallible;
-use volta_core::inventory::{node_versions, npm_versions, package_configs, yarn_versions};
+use volta_core::inventory::{
+    node_channel_versions, node_versions, npm_versions, package_configs, pnpm_versions,
+    yarn_versions,
+};
 use volta_core::platform::PlatformSpec;
 use volta_core::project::Project;
 use volta_core::tool::PackageConfig;
@@ -36,6 +41,8 @@ enum Lookup {
     Runtime,
     /// Look up the npm package manager
     Npm,
+    /// Look up the pnpm package manager
+    Pnpm,
     /// Look up the Yarn package manager
     Yarn,
 }
@@ -45,10 +52,19 @@ impl Lookup {
         move |spec| match self {
             Lookup::Runtime => Some(spec.node.clone()),
             Lookup::Npm => spec.npm.clone(),
+            Lookup::Pnpm => spec.pnpm.clone(),
             Lookup::Yarn => spec.yarn.clone(),
         }
     }
 
+    /// The file in a project that pins this kind of tool
+    fn project_file<'a>(&self, project: &'a Project) -> &'a Path {
+        match self {
+            Lookup::Runtime => project.node_source_file(),
+            Lookup::Npm | Lookup::Pnpm | Lookup::Yarn => project.manifest_file(),
+        }
+    }
+
     fn version_source(
         self,
         project: Option<&Project>,
@@ -61,7 +77,7 @@ impl Lookup {
                     .and_then(self.version_from_spec())
                     .and_then(|project_version| {
                         if &project_version == version {
-                            Some(Source::Project(proj.manifest_file().to_owned()))
+                            Some(Source::Project(self.project_file(proj).to_owned()))
                         } else {
                             None
                         }
@@ -91,7 +107,7 @@ impl Lookup {
             .and_then(|proj| {
                 proj.platform()
                     .and_then(self.version_from_spec())
-                    .map(|version| (Source::Project(proj.manifest_file().to_owned()), version))
+                    .map(|version| (Source::Project(self.project_file(proj).to_owned()), version))
             })
             .or_else(|| {
                 default
@@ -133,6 +149,13 @@ impl Toolchain {
                     version,
                 })
                 .into_iter()
+                .chain(Lookup::Pnpm.active_tool(project, default_platform).map(
+                    |(source, version)| PackageManager {
+                        kind: PackageManagerKind::Pnpm,
+                        source,
+                        version,
+                    },
+                ))
                 .chain(Lookup::Yarn.active_tool(project, default_platform).map(
                     |(source, version)| PackageManager {
                         kind: PackageManagerKind::Yarn,
@@ -157,6 +180,7 @@ impl Toolchain {
     ) -> Fallible<Toolchain> {
         let runtimes = node_versions()?
             .iter()
+            .chain(node_channel_versions()?.iter())
             .map(|version| Node {
                 source: Lookup::Runtime.version_source(project, default_platform, version),
                 version: version.clone(),
@@ -170,6 +194,11 @@ impl Toolchain {
                 source: Lookup::Npm.version_source(project, default_platform, version),
                 version: version.clone(),
             })
+            .chain(pnpm_versions()?.iter().map(|version| PackageManager {
+                kind: PackageManagerKind::Pnpm,
+                source: Lookup::Pnpm.version_source(project, default_platform, version),
+                version: version.clone(),
+            }))
             .chain(yarn_versions()?.iter().map(|version| PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Lookup::Yarn.version_source(project, default_platform, version),
@@ -193,6 +222,7 @@ impl Toolchain {
     ) -> Fallible<Toolchain> {
         let runtimes = node_versions()?
             .iter()
+            .chain(node_channel_versions()?.iter())
             .filter_map(|version| {
                 let source = Lookup::Runtime.version_source(project, default_platform, version);
                 if source.allowed_with(filter) {
@@ -234,6 +264,33 @@ impl Toolchain {
         })
     }
 
+    pub(super) fn pnpm(
+        project: Option<&Project>,
+        default_platform: Option<&PlatformSpec>,
+        filter: &Filter,
+    ) -> Fallible<Toolchain> {
+        let managers = pnpm_versions()?
+            .iter()
+            .filter_map(|version| {
+                let source = Lookup::Pnpm.version_source(project, default_platform, version);
+                if source.allowed_with(filter) {
+                    Some(PackageManager {
+                        kind: PackageManagerKind::Pnpm,
+                        source,
+                        version: version.clone(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(Toolchain::PackageManagers {
+            kind: PackageManagerKind::Pnpm,
+            managers,
+        })
+    }
+
     pub(super) fn yarn(
         project: Option<&Project>,
         default_platform: Option<&PlatformSpec>,
@@ -1,24 +1,38 @@
 pub(crate) mod completions;
+pub(crate) mod config;
+pub(crate) mod export;
 pub(crate) mod fetch;
+pub(crate) mod import;
 pub(crate) mod install;
 pub(crate) mod list;
+pub(crate) mod outdated;
 pub(crate) mod pin;
+pub(crate) mod prune;
 pub(crate) mod run;
 pub(crate) mod setup;
+pub(crate) mod stats;
 pub(crate) mod uninstall;
+pub(crate) mod upgrade;
 pub(crate) mod r#use;
 pub(crate) mod which;
 
 pub(crate) use self::which::Which;
 pub(crate) use completions::Completions;
+pub(crate) use config::Config;
+pub(crate) use export::Export;
 pub(crate) use fetch::Fetch;
+pub(crate) use import::Import;
 pub(crate) use install::Install;
 pub(crate) use list::List;
+pub(crate) use outdated::Outdated;
 pub(crate) use pin::Pin;
+pub(crate) use prune::Prune;
 pub(crate) use r#use::Use;
 pub(crate) use run::Run;
 pub(crate) use setup::Setup;
+pub(crate) use stats::Stats;
 pub(crate) use uninstall::Uninstall;
+pub(crate) use upgrade::Upgrade;
 
 use volta_core::error::{ExitCode, Fallible};
 use volta_core::session::Session;
@@ -30,3 +44,33 @@ pub(crate) trait Command: Sized {
     /// should return `e.exit_code()`.
     fn run(self, session: &mut Session) -> Fallible<ExitCode>;
 }
+
+/// Format rows of text as a table with left-aligned columns, below a row of headers
+pub(crate) fn format_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let headers: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+    let all_rows: Vec<&Vec<String>> = std::iter::once(&headers).chain(rows).collect();
+
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            all_rows
+                .iter()
+                .map(|row| row.get(column).map_or(0, String::len))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    all_rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
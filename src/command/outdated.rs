@@ -0,0 +1,180 @@
+use std::str::FromStr;
+
+use log::info;
+use serde::Serialize;
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{note_prefix, success_prefix};
+use volta_core::tool::outdated::{self, Outdated as OutdatedItem, OutdatedKind};
+
+use crate::command::{self, Command};
+
+/// The version of the JSON output schema
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Format {
+    Human,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err("No".into()),
+        }
+    }
+}
+
+#[derive(StructOpt)]
+pub(crate) struct Outdated {
+    /// Specify the output format.
+    ///
+    /// The `json` format is intended for scripts and includes a `schemaVersion` field.
+    #[structopt(
+        long = "format",
+        default_value = "human",
+        raw(possible_values = r#"&["human", "json"]"#)
+    )]
+    format: Format,
+}
+
+impl Command for Outdated {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Outdated);
+
+        let outdated = outdated::check(session)?;
+
+        match self.format {
+            Format::Human if outdated.is_empty() => {
+                info!(
+                    "{} your default tools and packages are up to date",
+                    success_prefix()
+                );
+            }
+            Format::Human => {
+                println!("{}", format_table(&outdated));
+                info!("\n{} run `volta upgrade <tool>` to upgrade", note_prefix());
+            }
+            Format::Json => println!("{}", format_json(&outdated)),
+        }
+
+        session.add_event_end(ActivityKind::Outdated, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// Format the outdated items as a table, with a column for each version
+fn format_table(outdated: &[OutdatedItem]) -> String {
+    let rows: Vec<_> = outdated
+        .iter()
+        .map(|item| {
+            vec![
+                item.name.clone(),
+                item.current.to_string(),
+                item.wanted.to_string(),
+                item.latest.to_string(),
+            ]
+        })
+        .collect();
+
+    command::format_table(&["Tool", "Current", "Wanted", "Latest"], &rows)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonOutput<'a> {
+    schema_version: u32,
+    outdated: Vec<JsonItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonItem<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    current: String,
+    wanted: String,
+    latest: String,
+}
+
+fn format_json(outdated: &[OutdatedItem]) -> String {
+    let output = JsonOutput {
+        schema_version: SCHEMA_VERSION,
+        outdated: outdated
+            .iter()
+            .map(|item| JsonItem {
+                name: &item.name,
+                kind: match item.kind {
+                    OutdatedKind::Runtime => "runtime",
+                    OutdatedKind::PackageManager => "packageManager",
+                    OutdatedKind::Package => "package",
+                },
+                current: item.current.to_string(),
+                wanted: item.wanted.to_string(),
+                latest: item.latest.to_string(),
+            })
+            .collect(),
+    };
+
+    // Serializing plain strings and numbers can't fail
+    serde_json::to_string_pretty(&output).expect("outdated report is valid JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn outdated() -> Vec<OutdatedItem> {
+        vec![
+            OutdatedItem {
+                kind: OutdatedKind::Runtime,
+                name: "node".into(),
+                current: "14.17.0".parse().unwrap(),
+                wanted: "14.19.1".parse().unwrap(),
+                latest: "16.14.0".parse().unwrap(),
+            },
+            OutdatedItem {
+                kind: OutdatedKind::Package,
+                name: "typescript".into(),
+                current: "3.4.1".parse().unwrap(),
+                wanted: "3.9.10".parse().unwrap(),
+                latest: "4.6.2".parse().unwrap(),
+            },
+        ]
+    }
+
+    #[test]
+    fn formats_table() {
+        assert_eq!(
+            format_table(&outdated()),
+            "Tool        Current  Wanted   Latest
+node        14.17.0  14.19.1  16.14.0
+typescript  3.4.1    3.9.10   4.6.2"
+        );
+    }
+
+    #[test]
+    fn formats_json() {
+        let parsed: serde_json::Value = serde_json::from_str(&format_json(&outdated())).unwrap();
+
+        assert_eq!(parsed["schemaVersion"], json!(1));
+        assert_eq!(
+            parsed["outdated"][1],
+            json!({
+                "name": "typescript",
+                "type": "package",
+                "current": "3.4.1",
+                "wanted": "3.9.10",
+                "latest": "4.6.2"
+            })
+        );
+    }
+}
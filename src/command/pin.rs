@@ -11,6 +11,10 @@ pub(crate) struct Pin {
     /// Tools to pin, like `node@lts` or `yarn@^1.14`.
     #[structopt(name = "tool[@version]", required = true, min_values = 1)]
     tools: Vec<String>,
+
+    /// Pin the versions even if they don't satisfy the `engines` in package.json
+    #[structopt(long = "force", short = "f")]
+    force: bool,
 }
 
 impl Command for Pin {
@@ -18,7 +22,7 @@ impl Command for Pin {
         session.add_event_start(ActivityKind::Pin);
 
         for tool in Spec::from_strings(&self.tools, "pin")? {
-            tool.resolve(session)?.pin(session)?;
+            tool.pin(session, self.force)?;
         }
 
         session.add_event_end(ActivityKind::Pin, ExitCode::Success);
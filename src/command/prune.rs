@@ -0,0 +1,169 @@
+use std::io::{self, Write};
+
+use log::info;
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{note_prefix, success_prefix, tool_version};
+use volta_core::tool::prune::PrunePlan;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Prune {
+    /// Only report what would be removed, without deleting anything or asking for confirmation
+    #[structopt(long = "dry-run", conflicts_with = "yes")]
+    dry_run: bool,
+
+    /// Delete the unused versions without asking for confirmation
+    #[structopt(long = "yes", short = "y", conflicts_with = "dry_run")]
+    yes: bool,
+
+    /// Also trim the package cache down to this size, e.g. `500MiB` or `2GiB`, removing the oldest
+    /// entries first. Global installs only share this cache if npm or Yarn isn't configured with
+    /// a cache of its own
+    #[structopt(long = "max-cache-size", value_name = "size", parse(try_from_str = parse_size))]
+    max_cache_size: Option<u64>,
+}
+
+impl Command for Prune {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Prune);
+
+        let plan = PrunePlan::new(session, self.max_cache_size)?;
+
+        if plan.is_empty() {
+            info!("{} nothing to prune", note_prefix());
+        } else {
+            info!(
+                "{} the following items are not used by any platform:",
+                note_prefix()
+            );
+            for unused in &plan.versions {
+                info!(
+                    "    {} ({})",
+                    tool_version(unused.name(), &unused.version),
+                    format_size(unused.size)
+                );
+            }
+            for stale in &plan.staging {
+                info!("    {} ({})", stale.path.display(), format_size(stale.size));
+            }
+            if !plan.cache.is_empty() {
+                info!(
+                    "    {} oldest entries of the package cache ({})",
+                    plan.cache.len(),
+                    format_size(plan.cache.iter().map(|cached| cached.size).sum())
+                );
+            }
+
+            let reclaimable = format_size(plan.reclaimable());
+            if self.yes || (!self.dry_run && confirm(&reclaimable)) {
+                plan.execute()?;
+                info!("{} pruned {}", success_prefix(), reclaimable);
+            } else {
+                info!(
+                    "{} {} can be reclaimed. Run `volta prune --yes` to remove the items above.",
+                    note_prefix(),
+                    reclaimable
+                );
+            }
+        }
+
+        session.add_event_end(ActivityKind::Prune, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// Ask whether to remove the items that were listed, if there is a terminal to ask at
+///
+/// Without a terminal, e.g. in scripts, nothing is removed unless `--yes` was passed.
+fn confirm(reclaimable: &str) -> bool {
+    if !atty::is(atty::Stream::Stdin) || !atty::is(atty::Stream::Stdout) {
+        return false;
+    }
+
+    print!("Remove the items above and reclaim {}? [y/N] ", reclaimable);
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+
+    let mut answer = String::new();
+    match io::stdin().read_line(&mut answer) {
+        Ok(_) => matches!(answer.trim(), "y" | "Y" | "yes" | "Yes"),
+        Err(_) => false,
+    }
+}
+
+/// Format a number of bytes for display, using binary units
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+
+    format!("{:.1} {}", size, unit)
+}
+
+/// Parse a number of bytes, with an optional binary unit like the ones `format_size` uses
+fn parse_size(value: &str) -> Result<u64, String> {
+    const UNITS: [(&str, u64); 5] = [
+        ("TiB", 1 << 40),
+        ("GiB", 1 << 30),
+        ("MiB", 1 << 20),
+        ("KiB", 1 << 10),
+        ("B", 1),
+    ];
+
+    let value = value.trim();
+    let (number, multiplier) = UNITS
+        .iter()
+        .find_map(|(unit, multiplier)| {
+            value
+                .strip_suffix(unit)
+                .map(|number| (number.trim(), *multiplier))
+        })
+        .unwrap_or((value, 1));
+
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid size '{}', expected e.g. 500MiB", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_size, parse_size};
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_size("1024"), Ok(1024));
+        assert_eq!(parse_size("12B"), Ok(12));
+        assert_eq!(parse_size("500MiB"), Ok(500 * 1024 * 1024));
+        assert_eq!(parse_size("2 GiB"), Ok(2 * 1024 * 1024 * 1024));
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("-1KiB").is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use log::info;
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::inventory::package_configs;
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::note_prefix;
+use volta_core::usage::{self, NodeUsage, PackageUsage, UsageStats};
+
+use crate::command::{self, Command};
+
+#[derive(StructOpt)]
+pub(crate) struct Stats {}
+
+impl Command for Stats {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Stats);
+
+        let stats = UsageStats::new(&usage::read_history()?, &package_configs()?);
+
+        if stats.runs == 0 {
+            if usage::is_enabled() {
+                info!("{} no tools have been run yet", note_prefix());
+            } else {
+                info!(
+                    "{} the usage history is disabled. Run `volta config set usageHistory true` to record which tools you run.",
+                    note_prefix()
+                );
+            }
+        } else {
+            if let Some(since) = &stats.since {
+                info!("Usage in {} runs since {}\n", stats.runs, since);
+            }
+            println!("{}", format_node(&stats.node));
+            if !stats.packages.is_empty() {
+                println!("\n{}", format_packages(&stats.packages));
+            }
+            if !stats.unused_packages.is_empty() {
+                info!(
+                    "\n{} these installed packages have not been used: {}",
+                    note_prefix(),
+                    stats.unused_packages.join(", ")
+                );
+            }
+        }
+
+        session.add_event_end(ActivityKind::Stats, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+fn format_node(node: &[NodeUsage]) -> String {
+    let rows: Vec<_> = node
+        .iter()
+        .map(|usage| {
+            vec![
+                usage.version.clone(),
+                usage.runs.to_string(),
+                format_projects(&usage.projects),
+            ]
+        })
+        .collect();
+
+    command::format_table(&["Node", "Runs", "Projects"], &rows)
+}
+
+fn format_packages(packages: &[PackageUsage]) -> String {
+    let rows: Vec<_> = packages
+        .iter()
+        .map(|usage| {
+            vec![
+                usage.name.clone(),
+                usage.runs.to_string(),
+                format_projects(&usage.projects),
+            ]
+        })
+        .collect();
+
+    command::format_table(&["Package", "Runs", "Projects"], &rows)
+}
+
+fn format_projects(projects: &BTreeSet<PathBuf>) -> String {
+    if projects.is_empty() {
+        "-".into()
+    } else {
+        projects
+            .iter()
+            .map(|project| project.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_node_table() {
+        let node = vec![
+            NodeUsage {
+                version: "16.18.0".into(),
+                runs: 12,
+                projects: BTreeSet::from([
+                    PathBuf::from("/projects/app"),
+                    PathBuf::from("/projects/web"),
+                ]),
+            },
+            NodeUsage {
+                version: "12.22.12".into(),
+                runs: 3,
+                projects: BTreeSet::new(),
+            },
+        ];
+
+        assert_eq!(
+            format_node(&node),
+            "Node      Runs  Projects
+16.18.0   12    /projects/app, /projects/web
+12.22.12  3     -"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use log::info;
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{note_prefix, success_prefix};
+use volta_core::tool::upgrade::{self, Upgraded};
+
+use crate::command::{self, Command};
+
+#[derive(StructOpt)]
+pub(crate) struct Upgrade {
+    /// Tools to upgrade: `node`, `npm`, `pnpm`, `yarn`, or the name of an installed package
+    #[structopt(name = "tool", required_unless = "all", conflicts_with = "all")]
+    tools: Vec<String>,
+
+    /// Upgrade every installed package
+    #[structopt(long = "all")]
+    all: bool,
+
+    /// Reinstall packages with the current default platform, instead of the platform they were
+    /// installed with
+    #[structopt(long = "repin")]
+    repin: bool,
+}
+
+impl Command for Upgrade {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Upgrade);
+
+        let upgraded = if self.all {
+            upgrade::upgrade_all_packages(self.repin, session)?
+        } else {
+            let mut upgraded = Vec::new();
+            for tool in &self.tools {
+                match upgrade::upgrade(tool, self.repin, session)? {
+                    Some(item) => upgraded.push(item),
+                    None => info!("{} {} is already up to date", note_prefix(), tool),
+                }
+            }
+            upgraded
+        };
+
+        if upgraded.is_empty() {
+            info!("{} nothing to upgrade", success_prefix());
+        } else {
+            info!(
+                "{} upgraded:\n{}",
+                success_prefix(),
+                format_summary(&upgraded)
+            );
+        }
+
+        session.add_event_end(ActivityKind::Upgrade, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// Format the upgraded items as a table of their old and new versions
+fn format_summary(upgraded: &[Upgraded]) -> String {
+    let rows: Vec<_> = upgraded
+        .iter()
+        .map(|item| {
+            vec![
+                item.name.clone(),
+                item.from.to_string(),
+                item.to.to_string(),
+            ]
+        })
+        .collect();
+
+    command::format_table(&["Tool", "Old", "New"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_summary() {
+        let upgraded = vec![
+            Upgraded {
+                name: "node".into(),
+                from: "14.17.0".parse().unwrap(),
+                to: "14.19.1".parse().unwrap(),
+            },
+            Upgraded {
+                name: "typescript".into(),
+                from: "3.4.1".parse().unwrap(),
+                to: "4.6.2".parse().unwrap(),
+            },
+        ];
+
+        assert_eq!(
+            format_summary(&upgraded),
+            "Tool        Old      New
+node        14.17.0  14.19.1
+typescript  3.4.1    4.6.2"
+        );
+    }
+}
@@ -2,10 +2,16 @@
 mod command;
 mod cli;
 
+use std::env;
+
+use log::warn;
 use structopt::StructOpt;
 
+use volta_core::config;
 use volta_core::error::report_error;
 use volta_core::log::{LogContext, LogVerbosity, Logger};
+use volta_core::offline::OFFLINE_VAR;
+use volta_core::policy::PREFER_LOCAL_VAR;
 use volta_core::session::{ActivityKind, Session};
 
 mod common;
@@ -22,8 +28,24 @@ pub fn main() {
             "StructOpt should prevent the user from providing both --verbose and --quiet"
         ),
     };
+
+    // Offline mode and the resolution policy are read from the environment, so that they also
+    // apply to any tools we launch, and take precedence over the configuration files
+    if volta.offline {
+        env::set_var(OFFLINE_VAR, "1");
+    }
+    if volta.prefer_local {
+        env::set_var(PREFER_LOCAL_VAR, "1");
+    }
+
     Logger::init(LogContext::Volta, verbosity).expect("Only a single logger should be initialized");
 
+    // The configuration files are read lazily, but loading them for the log level means that any
+    // error in them has already been found
+    if let Err(err) = config::current() {
+        warn!("Could not load configuration settings: {}", err);
+    }
+
     let mut session = Session::init();
     session.add_event_start(ActivityKind::Volta);
 
@@ -1,6 +1,8 @@
 mod common;
 
 use common::{ensure_layout, Error, IntoResult};
+use log::warn;
+use volta_core::config;
 use volta_core::error::{report_error, ExitCode};
 use volta_core::log::{LogContext, LogVerbosity, Logger};
 use volta_core::run::execute_shim;
@@ -12,6 +14,10 @@ pub fn main() {
         .expect("Only a single Logger should be initialized");
     setup_signal_handler();
 
+    if let Err(err) = config::current() {
+        warn!("Could not load configuration settings: {}", err);
+    }
+
     let mut session = Session::init();
     session.add_event_start(ActivityKind::Tool);
 
@@ -0,0 +1,78 @@
+use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const NODE_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "10.99.1040",
+    compressed_size: 273,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+#[test]
+fn missing_extra_ca_certs_warns() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("NODE_EXTRA_CA_CERTS", "does-not-exist.pem")
+        .env("NPM_CONFIG_USERCONFIG", "does-not-exist")
+        .env("VOLTA_LOGLEVEL", "warn")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Ignoring NODE_EXTRA_CA_CERTS: Could not read certificate authorities"
+            )
+            .with_stderr_contains("[..]does-not-exist.pem")
+    );
+}
+
+#[test]
+fn invalid_npmrc_cafile() {
+    let s = sandbox()
+        .package_json(r#"{ "name": "test-package" }"#)
+        .node_available_versions(NODE_VERSION_INFO)
+        .project_file(".npmrc", "cafile=corporate.pem\n")
+        .project_file("corporate.pem", "not a certificate\n")
+        .env("NPM_CONFIG_USERCONFIG", "does-not-exist")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Could not parse certificate authorities")
+            .with_stderr_contains("[..]corporate.pem")
+    );
+}
+
+#[test]
+fn npmrc_cafile_is_relative_to_npmrc() {
+    let s = sandbox()
+        .package_json(r#"{ "name": "test-package" }"#)
+        .node_available_versions(NODE_VERSION_INFO)
+        .project_file(".npmrc", "cafile=certs/corporate.pem\n")
+        .project_file("certs/corporate.pem", "not a certificate\n")
+        .project_file("subdir/index.js", "")
+        .env("NPM_CONFIG_USERCONFIG", "does-not-exist")
+        .build();
+
+    // Run from a subdirectory, so that the file is only found relative to the `.npmrc`
+    assert_that!(
+        s.volta("install node@10").cwd(s.root().join("subdir")),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Could not parse certificate authorities")
+            .with_stderr_contains("[..]certs[..]corporate.pem")
+    );
+}
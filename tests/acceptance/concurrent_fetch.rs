@@ -0,0 +1,95 @@
+use crate::support::sandbox::{sandbox, DistroMetadata, NpmFixture, YarnFixture};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+use volta_core::tool::Yarn;
+
+const NPM_VERSION_INFO: &str = r#"{
+    "name":"npm",
+    "dist-tags": { "latest":"8.1.5" },
+    "versions": {
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+const YARN_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest": "1.2.42" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+const NPM_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "8.1.5",
+    compressed_size: 239,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+const YARN_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "1.2.42",
+    compressed_size: 174,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+#[test]
+fn fetches_multiple_tools() {
+    let s = sandbox()
+        .npm_available_versions(NPM_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch npm@8.1.5 yarn@1.2.42"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]fetched npm@8.1.5")
+            .with_stdout_contains("[..]fetched yarn@1.2.42")
+    );
+
+    assert!(s.npm_inventory_archive_exists("8.1.5"));
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn reports_failed_download_of_one_tool() {
+    // Without a mock for the Yarn archive, its download fails while the npm download succeeds
+    let s = sandbox()
+        .npm_available_versions(NPM_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch npm@8.1.5 yarn@1.2.42"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download yarn@1.2.42")
+    );
+
+    assert!(!s.npm_inventory_archive_exists("8.1.5"));
+    assert!(!s.yarn_inventory_archive_exists("1.2.42"));
+    assert!(!s.tmp_file_exists("npm-8.1.5.tgz"));
+}
+
+#[test]
+fn ignores_archive_left_in_tmp_dir() {
+    let s = sandbox()
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .build();
+    s.tmp_file(&Yarn::archive_filename("1.2.42"), b"not the Yarn archive");
+
+    assert_that!(
+        s.volta("fetch yarn@1.2.42"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]fetched yarn@1.2.42")
+    );
+
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+}
@@ -1,10 +1,11 @@
-use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, YarnFixture};
+use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, Sandbox, YarnFixture};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use semver::Version;
 use test_support::matchers::execs;
 
 use volta_core::error::ExitCode;
+use volta_core::tool::Node;
 
 const NODE_VERSION_INFO: &str = r#"[
 {"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
@@ -30,10 +31,26 @@ const YARN_VERSION_INFO: &str = r#"{
     "dist-tags": { "latest": "1.2.42" },
     "versions": {
         "0.0.1": { "version":"0.0.1", "dist": { "shasum":"", "tarball":"" }},
-        "1.2.42": { "version":"1.2.42", "dist": { "shasum:"", "tarball":"" }}
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"", "tarball":"" }}
     }
 }"#;
 
+fn yarn_version_info_with_integrity(integrity: &str) -> String {
+    format!(
+        r#"{{
+    "name":"yarn",
+    "dist-tags": {{ "latest": "1.2.42" }},
+    "versions": {{
+        "1.2.42": {{ "version":"1.2.42", "dist": {{ "shasum":"", "tarball":"", "integrity":"{}" }}}}
+    }}
+}}"#,
+        integrity
+    )
+}
+
+const YARN_1_2_42_INTEGRITY: &str =
+    "sha512-F0sPBsZOIl2BLybQCXk4qUyLPZm5Y6XqXuoM8ptqZmUGJURfscez+JfmJazc3Jd6G6VxIKUzHHIibug+mmTFBw==";
+
 const YARN_VERSION_FIXTURES: [DistroMetadata; 2] = [
     DistroMetadata {
         version: "0.0.1",
@@ -77,6 +94,151 @@ fn install_valid_node_saves_to_inventory() {
     assert!(s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
 }
 
+#[test]
+fn install_node_with_mismatched_checksum_leaves_inventory_unchanged() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .node_shasums(
+            "10.99.1040",
+            &format!(
+                "{}  {}\n",
+                "0".repeat(64),
+                Node::archive_filename(&Version::new(10, 99, 1040))
+            ),
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]does not match its published checksum[..]")
+    );
+
+    assert!(!s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
+}
+
+#[test]
+fn install_node_missing_from_checksums_leaves_inventory_unchanged() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .node_shasums("10.99.1040", "")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not find a checksum for[..]")
+    );
+
+    assert!(!s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
+}
+
+#[test]
+fn install_node_without_published_checksums_warns() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .node_shasums_missing("10.99.1040")
+        .env("VOLTA_LOGLEVEL", "warn")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Could not verify the download of node@10.99.1040: no checksums were found[..]"
+            )
+    );
+
+    assert!(s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
+}
+
+#[test]
+fn install_node_without_published_checksums_fails() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .node_shasums_missing("10.99.1040")
+        .env_remove("VOLTA_ALLOW_UNVERIFIED")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download checksums from[..]")
+    );
+
+    assert!(!s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
+}
+
+#[test]
+fn install_node_replaces_cached_archive_that_fails_verification() {
+    let archive = format!(
+        ".volta/tools/inventory/node/{}",
+        Node::archive_filename(&Version::new(10, 99, 1040))
+    );
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .file(&archive, "tampered")
+        .file(&format!("{}.integrity", archive), "sha256-3q2+7w==")
+        .env_remove("VOLTA_ALLOW_UNVERIFIED")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
+    assert!(!Sandbox::read_file(&format!("{}.integrity", archive)).contains("3q2+7w=="));
+}
+
+#[test]
+fn install_node_replaces_cached_archive_without_recorded_integrity() {
+    let archive = format!(
+        ".volta/tools/inventory/node/{}",
+        Node::archive_filename(&Version::new(10, 99, 1040))
+    );
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .file(&archive, "tampered")
+        .env_remove("VOLTA_ALLOW_UNVERIFIED")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
+    assert!(Sandbox::path_exists(&format!("{}.integrity", archive)));
+}
+
+#[test]
+fn install_node_without_checksums_fails_when_signature_required() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .node_shasums_missing("10.99.1040")
+        .env("VOLTA_SHASUMS_SIGNATURE", "gpg")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::NetworkError as i32)
+    );
+
+    assert!(!s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
+}
+
 #[test]
 fn install_corrupted_yarn_leaves_inventory_unchanged() {
     let s = sandbox()
@@ -111,3 +273,146 @@ fn install_valid_yarn_saves_to_inventory() {
 
     assert!(s.yarn_inventory_archive_exists("1.2.42"));
 }
+
+#[test]
+fn install_yarn_verifies_registry_integrity() {
+    let s = sandbox()
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(&yarn_version_info_with_integrity(YARN_1_2_42_INTEGRITY))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("install yarn@1.2.42"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+    assert!(s.yarn_inventory_integrity_exists("1.2.42"));
+}
+
+#[test]
+fn install_yarn_with_mismatched_integrity_leaves_inventory_unchanged() {
+    let s = sandbox()
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(&yarn_version_info_with_integrity(
+            "sha512-AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA==",
+        ))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("install yarn@1.2.42"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]does not match its published checksum[..]")
+    );
+
+    assert!(!s.yarn_inventory_archive_exists("1.2.42"));
+    assert!(!s.yarn_inventory_integrity_exists("1.2.42"));
+}
+
+fn package_json_with_package_manager(package_manager: &str) -> String {
+    format!(
+        r#"{{
+    "name": "corepack-project",
+    "engines": {{ "node": "10.99.1040" }},
+    "packageManager": "{}"
+}}"#,
+        package_manager
+    )
+}
+
+#[test]
+fn fetch_yarn_verifies_package_manager_hash() {
+    let s = sandbox()
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        .package_json(&package_json_with_package_manager(
+            "yarn@1.2.42+sha256.6cda7ff0ed8c9024f2b5ebf673663d6305289effb69df61d5c70942c722f5d3a",
+        ))
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .env("VOLTA_FEATURE_ENGINES", "1")
+        .build();
+
+    assert_that!(
+        s.volta("fetch yarn@1.2.42"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+    assert!(s.yarn_inventory_integrity_exists("1.2.42"));
+}
+
+#[test]
+fn fetch_yarn_with_mismatched_package_manager_hash_leaves_inventory_unchanged() {
+    let s = sandbox()
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        .package_json(&package_json_with_package_manager(
+            "yarn@1.2.42+sha256.0000000000000000000000000000000000000000000000000000000000000000",
+        ))
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .env("VOLTA_FEATURE_ENGINES", "1")
+        .build();
+
+    assert_that!(
+        s.volta("fetch yarn@1.2.42"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]does not match its published checksum[..]")
+    );
+
+    assert!(!s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn install_yarn_without_published_integrity_fails() {
+    let s = sandbox()
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .env_remove("VOLTA_ALLOW_UNVERIFIED")
+        .build();
+
+    assert_that!(
+        s.volta("install yarn@1.2.42"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not find a published checksum for yarn@1.2.42[..]")
+    );
+
+    assert!(!s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn install_yarn_without_published_integrity_when_allowed() {
+    let s = sandbox()
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "warn")
+        .build();
+
+    assert_that!(
+        s.volta("install yarn@1.2.42"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]will not be verified[..]")
+    );
+
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+    assert!(!s.yarn_inventory_integrity_exists("1.2.42"));
+}
@@ -4,7 +4,7 @@ use std::{thread, time};
 use crate::support::events_helpers::{
     assert_events, match_args, match_end, match_error, match_start,
 };
-use crate::support::sandbox::sandbox;
+use crate::support::sandbox::{sandbox, DistroMetadata, NpmFixture};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use mockito::mock;
@@ -372,3 +372,124 @@ fn yarn_semver_with_hook_uses_old_format() {
             .with_stderr_contains("[..]Could not download yarn@3.9.2")
     );
 }
+
+fn publish_url_hooks_json() -> String {
+    format!(
+        r#"
+{{
+    "events": {{
+        "publish": {{
+            "url": "{}/events"
+        }}
+    }}
+}}"#,
+        mockito::server_url()
+    )
+}
+
+#[test]
+fn publishes_events_to_url() {
+    let events_mock = mock("POST", "/events")
+        .match_header("content-type", "application/json")
+        .match_body(mockito::Matcher::Regex(r#""name":"list""#.into()))
+        .with_status(200)
+        .create();
+    let s = sandbox().default_hooks(&publish_url_hooks_json()).build();
+
+    assert_that!(
+        s.volta("list"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    events_mock.assert();
+}
+
+#[test]
+fn publishes_start_and_end_events_to_url() {
+    let events_mock = mock("POST", "/events")
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::Regex(r#"^\[\{"timestamp":\d+,"schema_version":2,"#.into()),
+            mockito::Matcher::Regex(r#""event":"start""#.into()),
+            mockito::Matcher::Regex(r#""end":\{"exit_code":0"#.into()),
+        ]))
+        .with_status(200)
+        .expect(1)
+        .create();
+    let s = sandbox().default_hooks(&publish_url_hooks_json()).build();
+
+    assert_that!(
+        s.volta("list"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    events_mock.assert();
+}
+
+const NPM_VERSION_INFO: &str = r#"{
+    "name":"npm",
+    "dist-tags": { "latest":"8.1.5" },
+    "versions": {
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+const NPM_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "8.1.5",
+    compressed_size: 239,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+#[test]
+fn publishes_fetch_and_end_details() {
+    let events_mock = mock("POST", "/events")
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::Regex(r#""schema_version":2"#.into()),
+            mockito::Matcher::Regex(
+                r#""fetch":\{"tool":"npm@8.1.5","bytes":239,"duration_ms":\d+\}"#.into(),
+            ),
+            mockito::Matcher::Regex(r#""end":\{"exit_code":0,"duration_ms":\d+"#.into()),
+        ]))
+        .with_status(200)
+        .create();
+    let s = sandbox()
+        .default_hooks(&publish_url_hooks_json())
+        .npm_available_versions(NPM_VERSION_INFO)
+        .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch npm@8.1.5"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    events_mock.assert();
+}
+
+#[test]
+fn does_not_publish_events_offline() {
+    let events_mock = mock("POST", "/events").with_status(200).expect(0).create();
+    let s = sandbox()
+        .default_hooks(&publish_url_hooks_json())
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("list"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    events_mock.assert();
+}
+
+#[test]
+fn failed_publish_to_url_is_not_reported() {
+    let _mock = mock("POST", "/events").with_status(500).create();
+    let s = sandbox().default_hooks(&publish_url_hooks_json()).build();
+
+    assert_that!(
+        s.volta("list"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_does_not_contain("[..]publish[..]")
+    );
+}
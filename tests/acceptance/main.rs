@@ -5,18 +5,30 @@ cfg_if! {
         mod support;
 
         // test files
+        mod certificates;
+        mod concurrent_fetch;
         mod corrupted_download;
         mod direct_install;
         mod direct_uninstall;
         mod hooks;
         mod merged_platform;
         mod migrations;
+        mod npmrc;
+        mod offline_mode;
+        mod prefer_local;
+        mod resumed_download;
         mod run_shim_directly;
         mod verbose_errors;
         mod volta_bypass;
+        mod volta_config;
+        mod volta_export;
         mod volta_install;
+        mod volta_outdated;
         mod volta_pin;
+        mod volta_prune;
         mod volta_run;
+        mod volta_stats;
         mod volta_uninstall;
+        mod volta_upgrade;
     }
 }
@@ -31,6 +31,33 @@ const PACKAGE_JSON_WITH_YARN: &str = r#"{
     }
 }"#;
 
+const PACKAGE_JSON_NO_VOLTA: &str = r#"{
+    "name": "no-volta"
+}"#;
+
+const PACKAGE_JSON_WITH_ENGINES: &str = r#"{
+    "name": "with-engines",
+    "engines": {
+        "node": "^10.0.0"
+    }
+}"#;
+
+const PACKAGE_JSON_OUTSIDE_ENGINES: &str = r#"{
+    "name": "outside-engines",
+    "engines": {
+        "node": "<10"
+    },
+    "volta": {
+        "node": "10.99.1040"
+    }
+}"#;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
 const PLATFORM_NODE_ONLY: &str = r#"{
     "node":{
         "runtime":"9.27.6",
@@ -181,6 +208,166 @@ fn uses_project_npm_if_available() {
     );
 }
 
+#[test]
+fn uses_project_nvmrc_without_volta_node() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "v10.99.1040\n")
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from project configuration")
+    );
+}
+
+#[test]
+fn resolves_project_nvmrc_alias_from_index() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "lts/dubnium\n")
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Found newest node version (10.99.1040) in LTS line 'dubnium'[..]"
+            )
+            .with_stderr_contains("[..]Node: 10.99.1040 from project configuration")
+    );
+}
+
+#[test]
+fn resolves_project_nvmrc_lts_to_newest_over_fetched_older_lts() {
+    // The expired cache predates the newest LTS line, and an older LTS release is fetched
+    let stale_cache = format!(
+        "http://localhost/node-dist/index.json\n{}",
+        r#"[
+{"version":"v9.27.6","npm":"5.6.17","lts": "Carbon","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]"#
+    );
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "lts/*\n")
+        .file(".volta/tools/image/node/9.27.6/bin/node", "")
+        .node_cache(&stale_cache, true)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from project configuration")
+    );
+}
+
+#[test]
+fn prefers_volta_node_over_project_node_version_file() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_NODE_ONLY)
+        .project_file(".node-version", "9.27.6\n")
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from project configuration")
+    );
+}
+
+#[test]
+fn uses_engines_node_when_opted_in() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_WITH_ENGINES)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_FEATURE_ENGINES", "1")
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from project configuration")
+    );
+}
+
+#[test]
+fn ignores_engines_node_by_default() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_WITH_ENGINES)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 9.27.6 from default configuration")
+    );
+}
+
+#[test]
+fn warns_when_node_outside_engines() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_OUTSIDE_ENGINES)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]node@10.99.1040 does not satisfy the engines constraint \"<10\"[..]"
+            )
+    );
+}
+
+#[test]
+fn fails_when_node_outside_engines_in_strict_mode() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_OUTSIDE_ENGINES)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_STRICT_ENGINES", "1")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::EnginesMismatch as i32)
+            .with_stderr_contains(
+                "[..]node@10.99.1040 does not satisfy the engines constraint \"<10\""
+            )
+    );
+}
+
 #[test]
 fn uses_bundled_npm_in_project_without_npm() {
     let s = sandbox()
@@ -0,0 +1,86 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use mockito::mock;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "test-package",
+  "volta": {
+    "node": "1.2.3"
+  }
+}"#;
+
+const NPM_VERSION_INFO: &str = r#"
+{
+    "name":"npm",
+    "dist-tags": { "latest":"8.1.5" },
+    "versions": {
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"", "tarball":"" }}
+    }
+}
+"#;
+
+/// An `.npmrc` that points at a private registry on the mock server, authenticated with `secret`
+fn private_registry_npmrc() -> String {
+    let registry = format!("{}/private-registry/", mockito::server_url());
+    format!(
+        "registry={}\n{}:_authToken=secret\n",
+        registry,
+        registry.trim_start_matches("http:")
+    )
+}
+
+#[test]
+fn project_npmrc_registry_and_token() {
+    let index_mock = mock("GET", "/private-registry/npm")
+        .match_header("authorization", "Bearer secret")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(NPM_VERSION_INFO)
+        .create();
+
+    let s = sandbox()
+        .package_json(PACKAGE_JSON)
+        .project_file(".npmrc", &private_registry_npmrc())
+        .env("NPM_CONFIG_USERCONFIG", "does-not-exist")
+        .build();
+
+    // The tarball isn't mocked, so the download fails after the version is resolved
+    assert_that!(
+        s.volta("pin npm@8"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download npm@8.1.5")
+            .with_stderr_contains("[..]/private-registry/npm/-/npm-8.1.5.tgz")
+    );
+
+    index_mock.assert();
+}
+
+#[test]
+fn user_npmrc_registry_and_token() {
+    let index_mock = mock("GET", "/private-registry/npm")
+        .match_header("authorization", "Bearer secret")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(NPM_VERSION_INFO)
+        .create();
+
+    let s = sandbox()
+        .package_json(PACKAGE_JSON)
+        .project_file("user.npmrc", &private_registry_npmrc())
+        .env("NPM_CONFIG_USERCONFIG", "user.npmrc")
+        .build();
+
+    assert_that!(
+        s.volta("pin npm@8"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]/private-registry/npm/-/npm-8.1.5.tgz")
+    );
+
+    index_mock.assert();
+}
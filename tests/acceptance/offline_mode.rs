@@ -0,0 +1,57 @@
+use crate::support::sandbox::{sandbox, Sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const FETCHED_NODE_FILE: &str = ".volta/tools/image/node/10.99.1040/bin/node";
+
+#[test]
+fn install_node_semver_from_inventory() {
+    let s = sandbox()
+        .file(FETCHED_NODE_FILE, "contents don't matter")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .env("VOLTA_OFFLINE", "1")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]node@10.99.1040[..]")
+    );
+
+    assert!(Sandbox::read_default_platform().contains("10.99.1040"));
+}
+
+#[test]
+fn install_node_without_fetched_match_fails() {
+    let s = sandbox()
+        .file(FETCHED_NODE_FILE, "contents don't matter")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("install node@9"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains("[..]Could not find a fetched node version matching[..]")
+    );
+}
+
+#[test]
+fn offline_flag_prevents_download() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("--offline install node@9.27.6"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("because Volta is in offline mode.")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/9.27.6"));
+}
@@ -0,0 +1,67 @@
+use crate::support::sandbox::{sandbox, Sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const FETCHED_NODE_FILE: &str = ".volta/tools/image/node/10.99.1040/bin/node";
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+#[test]
+fn flag_resolves_range_from_inventory() {
+    // Without a mocked index, the install can only succeed by using the fetched version
+    let s = sandbox()
+        .file(FETCHED_NODE_FILE, "contents don't matter")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("--prefer-local install node@10"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]node@10.99.1040[..]")
+    );
+
+    assert!(Sandbox::read_default_platform().contains("10.99.1040"));
+}
+
+#[test]
+fn config_file_resolves_range_from_inventory() {
+    let s = sandbox()
+        .file(FETCHED_NODE_FILE, "contents don't matter")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .file(".volta/config.json", r#"{ "resolution": "prefer-local" }"#)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]node@10.99.1040[..]")
+    );
+}
+
+#[test]
+fn falls_back_to_index_without_fetched_match() {
+    // The distro isn't mocked, so the download fails after the version is resolved from the index
+    let s = sandbox()
+        .file(FETCHED_NODE_FILE, "contents don't matter")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .node_available_versions(NODE_VERSION_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("--prefer-local install node@9"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download node@9.27.6")
+    );
+}
@@ -0,0 +1,79 @@
+use std::fs;
+
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use mockito::mock;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+use volta_core::tool::Yarn;
+
+const YARN_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest": "1.2.42" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+const YARN_FIXTURE: &str = "tests/fixtures/yarn-1.2.42.tgz";
+
+#[test]
+fn resumes_partial_download() {
+    let fixture = fs::read(YARN_FIXTURE).unwrap();
+    let (downloaded, remaining) = fixture.split_at(100);
+
+    // Only the remaining bytes are served, so the fetch only succeeds if it resumes
+    let range_mock = mock("GET", "/yarn/-/yarn-1.2.42.tgz")
+        .match_header("Range", "bytes=100-")
+        .match_header("If-Range", "\"v1\"")
+        .with_status(206)
+        .with_header(
+            "Content-Range",
+            &format!("bytes 100-{}/{}", fixture.len() - 1, fixture.len()),
+        )
+        .with_body(remaining)
+        .create();
+
+    let s = sandbox().yarn_available_versions(YARN_VERSION_INFO).build();
+    let partial = format!("{}.partial", Yarn::archive_filename("1.2.42"));
+    s.tmp_file(&partial, downloaded);
+    s.tmp_file(&format!("{}.validator", partial), b"\"v1\"");
+
+    assert_that!(
+        s.volta("fetch yarn@1.2.42"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    range_mock.assert();
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn restarts_unsatisfiable_partial_download() {
+    let fixture = fs::read(YARN_FIXTURE).unwrap();
+
+    let range_mock = mock("GET", "/yarn/-/yarn-1.2.42.tgz")
+        .match_header("Range", mockito::Matcher::Any)
+        .with_status(416)
+        .create();
+    let file_mock = mock("GET", "/yarn/-/yarn-1.2.42.tgz")
+        .match_header("Range", mockito::Matcher::Missing)
+        .with_body(&fixture)
+        .create();
+
+    let s = sandbox().yarn_available_versions(YARN_VERSION_INFO).build();
+    let partial = format!("{}.partial", Yarn::archive_filename("1.2.42"));
+    s.tmp_file(&partial, b"left over from a different archive");
+    s.tmp_file(&format!("{}.validator", partial), b"\"v1\"");
+
+    assert_that!(
+        s.volta("fetch yarn@1.2.42"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    range_mock.assert();
+    file_mock.assert();
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+}
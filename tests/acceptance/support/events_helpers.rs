@@ -52,7 +52,7 @@ pub fn assert_events(sandbox: &Sandbox, matchers: Vec<(&str, EventKindMatcher)>)
             EventKindMatcher::End {
                 exit_code: expected_exit_code,
             } => {
-                if let EventKind::End { exit_code } = &events[i].event {
+                if let EventKind::End { exit_code, .. } = &events[i].event {
                     assert_that!(*exit_code, eq(expected_exit_code));
                 } else {
                     panic!(
@@ -81,7 +81,7 @@ pub fn assert_events(sandbox: &Sandbox, matchers: Vec<(&str, EventKindMatcher)>)
             EventKindMatcher::ToolEnd {
                 exit_code: expected_exit_code,
             } => {
-                if let EventKind::End { exit_code } = &events[i].event {
+                if let EventKind::End { exit_code, .. } = &events[i].event {
                     assert_that!(*exit_code, eq(expected_exit_code));
                 } else {
                     panic!(
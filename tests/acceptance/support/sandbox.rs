@@ -8,9 +8,10 @@ use std::time::{Duration, SystemTime};
 use hyperx::header::HttpDate;
 use mockito::{self, mock, Matcher};
 use semver::Version;
+use sha2::{Digest, Sha256};
 use test_support::{self, ok_or_panic, paths, paths::PathExt, process::ProcessBuilder};
 use volta_core::fs::{set_executable, symlink_file};
-use volta_core::tool::{Node, Yarn, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION, NODE_DISTRO_OS};
+use volta_core::tool::{Node, Npm, Yarn, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION, NODE_DISTRO_OS};
 
 // version cache for node and yarn
 #[derive(PartialEq, Clone)]
@@ -130,6 +131,11 @@ pub trait DistroFixture: From<DistroMetadata> {
     fn server_path(&self) -> String;
     fn fixture_path(&self) -> String;
     fn metadata(&self) -> &DistroMetadata;
+
+    /// The server path of the checksums file published alongside the distro, if any
+    fn checksums_path(&self) -> Option<String> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -143,6 +149,11 @@ pub struct NodeFixture {
     pub metadata: DistroMetadata,
 }
 
+/// A build of Node from the nightly channel, which is downloaded apart from the releases
+pub struct NodeNightlyFixture {
+    pub metadata: DistroMetadata,
+}
+
 pub struct NpmFixture {
     pub metadata: DistroMetadata,
 }
@@ -151,12 +162,23 @@ pub struct YarnFixture {
     pub metadata: DistroMetadata,
 }
 
+/// A release of Yarn 2 or later, published to the registry as `@yarnpkg/cli-dist`
+pub struct YarnBerryFixture {
+    pub metadata: DistroMetadata,
+}
+
 impl From<DistroMetadata> for NodeFixture {
     fn from(metadata: DistroMetadata) -> Self {
         Self { metadata }
     }
 }
 
+impl From<DistroMetadata> for NodeNightlyFixture {
+    fn from(metadata: DistroMetadata) -> Self {
+        Self { metadata }
+    }
+}
+
 impl From<DistroMetadata> for NpmFixture {
     fn from(metadata: DistroMetadata) -> Self {
         Self { metadata }
@@ -169,6 +191,12 @@ impl From<DistroMetadata> for YarnFixture {
     }
 }
 
+impl From<DistroMetadata> for YarnBerryFixture {
+    fn from(metadata: DistroMetadata) -> Self {
+        Self { metadata }
+    }
+}
+
 impl DistroFixture for NodeFixture {
     fn server_path(&self) -> String {
         let version = &self.metadata.version;
@@ -189,6 +217,39 @@ impl DistroFixture for NodeFixture {
     fn metadata(&self) -> &DistroMetadata {
         &self.metadata
     }
+
+    fn checksums_path(&self) -> Option<String> {
+        Some(format!("/v{}/SHASUMS256.txt", self.metadata.version))
+    }
+}
+
+impl DistroFixture for NodeNightlyFixture {
+    fn server_path(&self) -> String {
+        let version = &self.metadata.version;
+        format!(
+            "/download/nightly/v{}/node-v{}-{}-{}.{}",
+            version, version, NODE_DISTRO_OS, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION
+        )
+    }
+
+    fn fixture_path(&self) -> String {
+        let version = &self.metadata.version;
+        format!(
+            "tests/fixtures/node-v{}-{}-{}.{}",
+            version, NODE_DISTRO_OS, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION
+        )
+    }
+
+    fn metadata(&self) -> &DistroMetadata {
+        &self.metadata
+    }
+
+    fn checksums_path(&self) -> Option<String> {
+        Some(format!(
+            "/download/nightly/v{}/SHASUMS256.txt",
+            self.metadata.version
+        ))
+    }
 }
 
 impl DistroFixture for NpmFixture {
@@ -219,6 +280,23 @@ impl DistroFixture for YarnFixture {
     }
 }
 
+impl DistroFixture for YarnBerryFixture {
+    fn server_path(&self) -> String {
+        format!(
+            "/@yarnpkg/cli-dist/-/cli-dist-{}.tgz",
+            self.metadata.version
+        )
+    }
+
+    fn fixture_path(&self) -> String {
+        format!("tests/fixtures/cli-dist-{}.tgz", self.metadata.version)
+    }
+
+    fn metadata(&self) -> &DistroMetadata {
+        &self.metadata
+    }
+}
+
 impl SandboxBuilder {
     /// Root of the project, ex: `/path/to/cargo/target/integration_test/t0/foo`
     pub fn root(&self) -> PathBuf {
@@ -285,6 +363,12 @@ impl SandboxBuilder {
         self
     }
 
+    /// Unset an environment variable for the sandbox, including the defaults (chainable)
+    pub fn env_remove(mut self, name: &str) -> Self {
+        self.root.env_vars_remove.push(name.into());
+        self
+    }
+
     /// Setup mock to return the available node versions (chainable)
     pub fn node_available_versions(mut self, body: &str) -> Self {
         let mock = mock("GET", "/node-dist/index.json")
@@ -297,6 +381,18 @@ impl SandboxBuilder {
         self
     }
 
+    /// Setup mock to return the builds in a prerelease channel of Node, e.g. `nightly` (chainable)
+    pub fn node_channel_versions(mut self, channel: &str, body: &str) -> Self {
+        let mock = mock("GET", &format!("/download/{}/index.json", channel)[..])
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+
+        self
+    }
+
     /// Setup mock to return the available yarn versions (chainable)
     pub fn yarn_available_versions(mut self, body: &str) -> Self {
         let mock = mock("GET", "/yarn")
@@ -308,6 +404,17 @@ impl SandboxBuilder {
         self
     }
 
+    /// Setup mock to return the available versions of Yarn 2 and later (chainable)
+    pub fn yarn_berry_available_versions(mut self, body: &str) -> Self {
+        let mock = mock("GET", "/@yarnpkg/cli-dist")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+        self
+    }
+
     /// Setup mock to return the available npm versions (chainable)
     pub fn npm_available_versions(mut self, body: &str) -> Self {
         let mock = mock("GET", "/npm")
@@ -360,6 +467,41 @@ impl SandboxBuilder {
             .create();
         self.root.mocks.push(file_mock);
 
+        if let Some(checksums_path) = fx.checksums_path() {
+            let contents = ok_or_panic! { fs::read(&fixture_path) };
+            let file_name = server_path.rsplit('/').next().unwrap();
+            let checksums = format!(
+                "{}  {}\n",
+                hex::encode(Sha256::digest(&contents)),
+                file_name
+            );
+            let checksums_mock = mock("GET", &checksums_path[..])
+                .with_body(checksums)
+                .create();
+            self.root.mocks.push(checksums_mock);
+        }
+
+        self
+    }
+
+    /// Setup mock to serve the given `SHASUMS256.txt` contents for a Node version (chainable)
+    /// Note: This must be called after `distro_mocks` to replace the generated checksums
+    pub fn node_shasums(mut self, version: &str, body: &str) -> Self {
+        let mock = mock("GET", &format!("/v{}/SHASUMS256.txt", version)[..])
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+        self
+    }
+
+    /// Setup mock to respond with a 404 for the `SHASUMS256.txt` of a Node version, like a mirror
+    /// that doesn't publish checksums (chainable)
+    /// Note: This must be called after `distro_mocks` to replace the generated checksums
+    pub fn node_shasums_missing(mut self, version: &str) -> Self {
+        let mock = mock("GET", &format!("/v{}/SHASUMS256.txt", version)[..])
+            .with_status(404)
+            .create();
+        self.root.mocks.push(mock);
         self
     }
 
@@ -411,6 +553,13 @@ impl SandboxBuilder {
         self
     }
 
+    /// Set the contents of the usage history for the sandbox (chainable)
+    pub fn usage_history(mut self, contents: &str) -> Self {
+        let history_file = volta_log_dir().join("usage.jsonl");
+        self.files.push(FileBuilder::new(history_file, contents));
+        self
+    }
+
     /// Set a shim file for the sandbox (chainable)
     pub fn shim(mut self, name: &str) -> Self {
         let shim_file = shim_file(name);
@@ -524,6 +673,9 @@ fn image_dir() -> PathBuf {
 fn node_inventory_dir() -> PathBuf {
     inventory_dir().join("node")
 }
+fn npm_inventory_dir() -> PathBuf {
+    inventory_dir().join("npm")
+}
 fn yarn_inventory_dir() -> PathBuf {
     inventory_dir().join("yarn")
 }
@@ -605,6 +757,8 @@ impl Sandbox {
             .env("VOLTA_INSTALL_DIR", cargo_dir())
             .env("PATH", &self.path)
             .env("VOLTA_POSTSCRIPT", volta_postscript())
+            // most registry fixtures don't publish an integrity
+            .env("VOLTA_ALLOW_UNVERIFIED", "1")
             .env_remove("VOLTA_SHELL")
             .env_remove("MSYSTEM"); // assume cmd.exe everywhere on windows
 
@@ -659,10 +813,23 @@ impl Sandbox {
         fs::read_dir(volta_log_dir()).ok()
     }
 
+    pub fn read_usage_history(&self) -> String {
+        read_file_to_string(volta_log_dir().join("usage.jsonl"))
+    }
+
     pub fn remove_volta_home(&self) {
         volta_home().rm_rf();
     }
 
+    pub fn remove_tmp_dir(&self) {
+        volta_tmp_dir().rm_rf();
+    }
+
+    /// Write a file to the Volta tmp directory, such as a partial download
+    pub fn tmp_file(&self, name: &str, contents: &[u8]) {
+        ok_or_panic! { fs::write(volta_tmp_dir().join(name), contents) };
+    }
+
     // check that files in the sandbox exist
 
     pub fn node_inventory_archive_exists(&self, version: &Version) -> bool {
@@ -671,12 +838,28 @@ impl Sandbox {
             .exists()
     }
 
+    pub fn tmp_file_exists(&self, name: &str) -> bool {
+        volta_tmp_dir().join(name).exists()
+    }
+
+    pub fn npm_inventory_archive_exists(&self, version: &str) -> bool {
+        npm_inventory_dir()
+            .join(Npm::archive_filename(version))
+            .exists()
+    }
+
     pub fn yarn_inventory_archive_exists(&self, version: &str) -> bool {
         yarn_inventory_dir()
             .join(Yarn::archive_filename(version))
             .exists()
     }
 
+    pub fn yarn_inventory_integrity_exists(&self, version: &str) -> bool {
+        yarn_inventory_dir()
+            .join(format!("{}.integrity", Yarn::archive_filename(version)))
+            .exists()
+    }
+
     pub fn package_config_exists(name: &str) -> bool {
         package_config_file(name).exists()
     }
@@ -689,6 +872,9 @@ impl Sandbox {
     pub fn path_exists(path: &str) -> bool {
         sandbox_path(path).exists()
     }
+    pub fn read_file(path: &str) -> String {
+        read_file_to_string(sandbox_path(path))
+    }
     pub fn package_image_exists(name: &str, version: &str) -> bool {
         let package_img_dir = package_image_dir(name, version);
         package_img_dir.join("package.json").exists()
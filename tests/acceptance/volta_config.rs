@@ -0,0 +1,111 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const BASIC_PACKAGE_JSON: &str = r#"{
+  "name": "test-package"
+}"#;
+
+#[test]
+fn set_then_get() {
+    let s = sandbox().env("VOLTA_LOGLEVEL", "info").build();
+
+    assert_that!(
+        s.volta("config set registry https://registry.example.com"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]set registry to https://registry.example.com")
+    );
+
+    assert_that!(
+        s.volta("config get registry"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("https://registry.example.com")
+    );
+}
+
+#[test]
+fn set_rejects_invalid_value() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("config set offline sometimes"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Invalid value 'sometimes' for setting 'offline'")
+    );
+}
+
+#[test]
+fn get_rejects_unknown_key() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("config get colour"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Unknown setting 'colour'")
+    );
+}
+
+#[test]
+fn list_merges_project_settings() {
+    let s = sandbox()
+        .file(
+            ".volta/config.json",
+            r#"{ "registry": "https://registry.example.com", "offline": false }"#,
+        )
+        .package_json(BASIC_PACKAGE_JSON)
+        .project_file(".volta/config.json", r#"{ "offline": true }"#)
+        .build();
+
+    assert_that!(
+        s.volta("config list"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("registry = https://registry.example.com")
+            .with_stdout_contains("offline = true")
+    );
+}
+
+#[test]
+fn list_ignores_user_only_settings_from_project() {
+    let s = sandbox()
+        .package_json(BASIC_PACKAGE_JSON)
+        .project_file(
+            ".volta/config.json",
+            r#"{ "allowUnverified": true, "registry": "https://registry.example.com", "offline": true }"#,
+        )
+        .build();
+
+    assert_that!(
+        s.volta("config list"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("offline = true")
+            .with_stdout_does_not_contain("allowUnverified[..]")
+            .with_stdout_does_not_contain("registry[..]")
+            .with_stderr_contains(
+                "[..]Ignoring `registry`, `nodeMirror`, and `allowUnverified`[..]"
+            )
+    );
+}
+
+#[test]
+fn list_keeps_user_settings_with_malformed_package_json() {
+    let s = sandbox()
+        .file(".volta/config.json", r#"{ "offline": true }"#)
+        .package_json("{ not json")
+        .build();
+
+    assert_that!(
+        s.volta("config list"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("offline = true")
+    );
+}
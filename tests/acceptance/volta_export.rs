@@ -0,0 +1,221 @@
+use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, Sandbox, YarnFixture};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+  "node": {
+    "runtime": "14.17.0",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "14.17.0",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm"
+}"#;
+
+const EXPORT_MATCHING: &str = r#"{
+  "format": 1,
+  "platform": { "node": "14.17.0", "npm": null, "pnpm": null, "yarn": null },
+  "packages": [
+    {
+      "name": "cowsay",
+      "version": "1.4.0",
+      "platform": { "node": "14.17.0", "npm": null, "pnpm": null, "yarn": null },
+      "manager": "Npm"
+    }
+  ]
+}"#;
+
+const EXPORT_NEWER: &str = r#"{
+  "format": 1,
+  "platform": { "node": "16.13.0", "npm": null, "pnpm": null, "yarn": "1.22.19" },
+  "packages": [
+    {
+      "name": "typescript",
+      "version": "4.8.4",
+      "platform": { "node": "16.13.0", "npm": null, "pnpm": null, "yarn": null },
+      "manager": "Npm"
+    }
+  ]
+}"#;
+
+const EXPORT_FUTURE_FORMAT: &str = r#"{
+  "format": 99,
+  "platform": null,
+  "packages": []
+}"#;
+
+const EXPORT_PLATFORM_ONLY: &str = r#"{
+  "format": 1,
+  "platform": { "node": "10.99.1040", "npm": null, "pnpm": null, "yarn": "1.2.42" },
+  "packages": []
+}"#;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const NODE_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "10.99.1040",
+    compressed_size: 273,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+const YARN_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest": "1.2.42" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+const YARN_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "1.2.42",
+    compressed_size: 174,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
+
+#[test]
+fn export_writes_platform_and_packages() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .build();
+
+    assert_that!(
+        s.volta("export"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(r#"[..]"node": "14.17.0",[..]"#)
+            .with_stdout_contains(r#"[..]"name": "cowsay",[..]"#)
+            .with_stdout_contains(r#"[..]"manager": "Npm"[..]"#)
+    );
+}
+
+#[test]
+fn import_matching_toolchain_changes_nothing() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .file("toolchain.json", EXPORT_MATCHING)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]your toolchain already matches toolchain.json")
+    );
+}
+
+#[test]
+fn import_dry_run_reports_drift() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .file("toolchain.json", EXPORT_NEWER)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json --dry-run"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("    node: 14.17.0 -> 16.13.0")
+            .with_stdout_contains("    yarn: none -> 1.22.19")
+            .with_stdout_contains("    typescript: not installed -> 4.8.4 (node@16.13.0)")
+            .with_stdout_contains(
+                "    cowsay: 1.4.0 (node@14.17.0) (not in the exported toolchain)"
+            )
+    );
+
+    assert_eq!(Sandbox::read_default_platform(), PLATFORM_NODE_ONLY);
+}
+
+#[test]
+fn import_rejects_newer_format() {
+    let s = sandbox()
+        .file("toolchain.json", EXPORT_FUTURE_FORMAT)
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Could not parse exported toolchain")
+    );
+}
+
+#[test]
+fn import_installs_exported_platform() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .file("toolchain.json", EXPORT_PLATFORM_ONLY)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]node: 14.17.0 -> 10.99.1040")
+            .with_stdout_contains("[..]yarn: none -> 1.2.42")
+            .with_stdout_contains("[..]imported toolchain from toolchain.json")
+    );
+
+    let platform = Sandbox::read_default_platform();
+    assert!(platform.contains(r#""runtime": "10.99.1040""#));
+    assert!(platform.contains(r#""yarn": "1.2.42""#));
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn import_twice_changes_nothing_the_second_time() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .file("toolchain.json", EXPORT_PLATFORM_ONLY)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]imported toolchain from toolchain.json")
+    );
+    let imported = Sandbox::read_default_platform();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]your toolchain already matches toolchain.json")
+            .with_stdout_does_not_contain("[..]imported toolchain[..]")
+    );
+    assert_eq!(Sandbox::read_default_platform(), imported);
+}
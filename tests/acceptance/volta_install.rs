@@ -1,5 +1,6 @@
 use crate::support::sandbox::{
-    sandbox, DistroMetadata, NodeFixture, NpmFixture, Sandbox, YarnFixture,
+    sandbox, DistroMetadata, NodeFixture, NodeNightlyFixture, NpmFixture, Sandbox,
+    YarnBerryFixture, YarnFixture,
 };
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
@@ -41,6 +42,18 @@ const NODE_VERSION_INFO: &str = r#"[
 ]
 "#;
 
+const NODE_NIGHTLY_INFO: &str = r#"[
+{"version":"v20.0.0-nightly20230301e3b0c44298","npm":"9.5.1","lts": false,"files":["linux-x64","osx-x64-tar","osx-arm64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v20.0.0-nightly20230228a8d2b94a31","npm":"9.5.1","lts": false,"files":["linux-x64","osx-x64-tar","osx-arm64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const NODE_NIGHTLY_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "20.0.0-nightly20230301e3b0c44298",
+    compressed_size: 345,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "macos")] {
         const NODE_VERSION_FIXTURES: [DistroMetadata; 4] = [
@@ -124,6 +137,21 @@ const YARN_VERSION_INFO: &str = r#"[
 {"tag_name":"v1.12.99","assets":[{"name":"yarn-v1.12.99.tar.gz"}]}
 ]"#;
 
+const YARN_BERRY_VERSION_INFO: &str = r#"{
+    "name":"@yarnpkg/cli-dist",
+    "dist-tags": { "latest": "3.2.4" },
+    "versions": {
+        "2.4.3": { "version":"2.4.3", "dist": { "shasum":"", "tarball":"" }},
+        "3.2.4": { "version":"3.2.4", "dist": { "shasum":"", "tarball":"", "integrity":"sha512-HUZ4kkBtcRzyOFd0s28A8bH9byDsCLSb8Qfbla3V9w09lP+imdufDZ9/FMB322PkhTT0hlmB79+NQMMXQdDsFQ==" }}
+    }
+}"#;
+
+const YARN_BERRY_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "3.2.4",
+    compressed_size: 240,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
 const YARN_VERSION_FIXTURES: [DistroMetadata; 4] = [
     DistroMetadata {
         version: "1.12.99",
@@ -246,6 +274,55 @@ fn install_npm_bundled_reports_info() {
     );
 }
 
+#[test]
+fn install_node_nightly_resolves_from_channel() {
+    // Without a mock for the nightly archive, the download fails once the build is resolved
+    let s = sandbox()
+        .node_channel_versions("nightly", NODE_NIGHTLY_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("install node@nightly"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download node@20.0.0-nightly20230301e3b0c44298")
+    );
+}
+
+#[test]
+fn install_node_nightly_keeps_build_apart_from_releases() {
+    let s = sandbox()
+        .node_channel_versions("nightly", NODE_NIGHTLY_INFO)
+        .distro_mocks::<NodeNightlyFixture>(&NODE_NIGHTLY_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("install node@nightly"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(
+                "[..]installed and set node@20.0.0-nightly20230301e3b0c44298[..]"
+            )
+    );
+
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/node-channels/20.0.0-nightly20230301e3b0c44298"
+    ));
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/image/node/20.0.0-nightly20230301e3b0c44298"
+    ));
+    assert!(Sandbox::read_default_platform().contains("20.0.0-nightly20230301e3b0c44298"));
+
+    // The fetched Node versions don't include the nightly build, so it can't satisfy a range
+    assert_that!(
+        s.volta("--offline install node@20"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains("[..]Could not find a fetched node version matching[..]")
+    );
+}
+
 #[test]
 fn install_npm_without_node_errors() {
     let s = sandbox()
@@ -263,6 +340,32 @@ fn install_npm_without_node_errors() {
     );
 }
 
+#[test]
+fn install_yarn_berry_adds_launcher() {
+    // There is no mock for the index of Yarn 1, since a requirement for Yarn 3 can't match it
+    let s = sandbox()
+        .platform(&platform_with_node("10.99.1040"))
+        .yarn_berry_available_versions(YARN_BERRY_VERSION_INFO)
+        .distro_mocks::<YarnBerryFixture>(&YARN_BERRY_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "info")
+        .env_remove("VOLTA_ALLOW_UNVERIFIED")
+        .build();
+
+    assert_that!(
+        s.volta("install yarn@3"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set yarn@3.2.4 as default")
+    );
+
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/yarn/3.2.4/bin/yarn"
+    ));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/yarn/3.2.4/bin/yarn.js"
+    ));
+}
+
 #[test]
 fn install_yarn_without_node_errors() {
     let s = sandbox()
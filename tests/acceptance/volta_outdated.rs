@@ -0,0 +1,77 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+  "node": {
+    "runtime": "14.17.0",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v16.14.0","npm":"8.3.1","lts": "Gallium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v14.19.1","npm":"6.14.16","lts": "Fermium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v14.17.0","npm":"6.14.13","lts": "Fermium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const NODE_UP_TO_DATE_INFO: &str = r#"[
+{"version":"v14.17.0","npm":"6.14.13","lts": "Fermium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+#[test]
+fn outdated_reports_newer_node() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .node_available_versions(NODE_VERSION_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("outdated"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("Tool  Current  Wanted   Latest")
+            .with_stdout_contains("node  14.17.0  14.19.1  16.14.0")
+    );
+}
+
+#[test]
+fn outdated_json() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .node_available_versions(NODE_VERSION_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("outdated --format=json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(r#"  "schemaVersion": 1,"#)
+            .with_stdout_contains(r#"      "type": "runtime","#)
+            .with_stdout_contains(r#"      "wanted": "14.19.1","#)
+            .with_stdout_contains(r#"      "latest": "16.14.0""#)
+    );
+}
+
+#[test]
+fn outdated_when_up_to_date() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .node_available_versions(NODE_UP_TO_DATE_INFO)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("outdated"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]up to date")
+            .with_stdout_does_not_contain("[..]Current[..]")
+    );
+}
@@ -20,6 +20,13 @@ const PACKAGE_JSON_WITH_EXTENDS: &str = r#"{
   }
 }"#;
 
+const PACKAGE_JSON_WITH_ENGINES: &str = r#"{
+  "name": "test-package",
+  "engines": {
+    "node": ">=8"
+  }
+}"#;
+
 fn package_json_with_pinned_node(node: &str) -> String {
     format!(
         r#"{{
@@ -244,6 +251,54 @@ fn pin_node() {
     )
 }
 
+#[test]
+fn pin_node_outside_engines_fails() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_ENGINES)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin node@6"),
+        execs()
+            .with_status(ExitCode::EnginesMismatch as i32)
+            .with_stderr_contains("[..]Could not pin node@6.19.62[..]")
+            .with_stderr_contains("[..]engines constraint \">=8\"[..]")
+    );
+
+    assert_eq!(s.read_package_json(), PACKAGE_JSON_WITH_ENGINES);
+}
+
+#[test]
+fn pin_node_outside_engines_with_force() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_ENGINES)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin --force node@6"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Pinning node@6.19.62[..]engines constraint \">=8\"[..]")
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        r#"{
+  "name": "test-package",
+  "engines": {
+    "node": ">=8"
+  },
+  "volta": {
+    "node": "6.19.62"
+  }
+}"#,
+    );
+}
+
 #[test]
 fn pin_node_reports_info() {
     let s = sandbox()
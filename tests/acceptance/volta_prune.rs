@@ -0,0 +1,157 @@
+use crate::support::sandbox::{sandbox, Sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+  "node": {
+    "runtime": "14.17.0",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+const DEFAULT_NODE_FILE: &str = ".volta/tools/image/node/14.17.0/bin/node";
+const UNUSED_NODE_FILE: &str = ".volta/tools/image/node/10.24.1/bin/node";
+const UNUSED_YARN_FILE: &str = ".volta/tools/image/yarn/1.22.10/bin/yarn";
+
+const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
+
+#[test]
+fn prune_dry_run_keeps_everything() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .file(DEFAULT_NODE_FILE, "contents don't matter")
+        .file(UNUSED_NODE_FILE, "contents don't matter")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune --dry-run"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("    node@10.24.1 ([..])")
+            .with_stdout_does_not_contain("[..]node@14.17.0[..]")
+    );
+
+    assert!(Sandbox::path_exists(DEFAULT_NODE_FILE));
+    assert!(Sandbox::path_exists(UNUSED_NODE_FILE));
+}
+
+#[test]
+fn prune_without_terminal_keeps_everything() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .file(DEFAULT_NODE_FILE, "contents don't matter")
+        .file(UNUSED_NODE_FILE, "contents don't matter")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("    node@10.24.1 ([..])")
+            .with_stdout_contains("[..]Run `volta prune --yes` to remove the items above.")
+    );
+
+    assert!(Sandbox::path_exists(UNUSED_NODE_FILE));
+}
+
+#[test]
+fn prune_yes_removes_unused_versions() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .file(DEFAULT_NODE_FILE, "contents don't matter")
+        .file(UNUSED_NODE_FILE, "contents don't matter")
+        .file(UNUSED_YARN_FILE, "contents don't matter")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune --yes"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("    node@10.24.1 ([..])")
+            .with_stdout_contains("    yarn@1.22.10 ([..])")
+            .with_stdout_contains("[..]pruned [..]")
+    );
+
+    assert!(Sandbox::path_exists(DEFAULT_NODE_FILE));
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/10.24.1"));
+    assert!(!Sandbox::path_exists(".volta/tools/image/yarn/1.22.10"));
+}
+
+#[test]
+fn prune_without_tmp_dir() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .file(DEFAULT_NODE_FILE, "contents don't matter")
+        .file(UNUSED_NODE_FILE, "contents don't matter")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+    s.remove_tmp_dir();
+
+    assert_that!(
+        s.volta("prune --yes"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("    node@10.24.1 ([..])")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/10.24.1"));
+}
+
+const CACHED_NPM_TARBALL: &str =
+    ".volta/cache/packages/_cacache/content-v2/sha512/3a/f1/0c8e4b6f2a9d5e71";
+const CACHED_NPM_INDEX: &str = ".volta/cache/packages/_cacache/index-v5/1d/9c/4ab7e2";
+const CACHED_YARN_PACKAGE: &str =
+    ".volta/cache/packages/yarn/v6/npm-cowsay-1.5.0-0d2b1c6e-integrity/node_modules/cowsay/package.json";
+
+#[test]
+fn prune_trims_package_cache() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .file(DEFAULT_NODE_FILE, "contents don't matter")
+        .file(CACHED_NPM_TARBALL, "contents don't matter")
+        .file(CACHED_NPM_INDEX, "contents don't matter")
+        .file(CACHED_YARN_PACKAGE, "contents don't matter")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune --yes --max-cache-size 0B"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("    2 oldest entries of the package cache ([..])")
+            .with_stdout_contains("[..]pruned [..]")
+    );
+
+    assert!(!Sandbox::path_exists(CACHED_NPM_TARBALL));
+    assert!(!Sandbox::path_exists(
+        ".volta/cache/packages/yarn/v6/npm-cowsay-1.5.0-0d2b1c6e-integrity"
+    ));
+    // npm treats index entries for missing content as a cache miss, so they are left alone
+    assert!(Sandbox::path_exists(CACHED_NPM_INDEX));
+}
+
+#[test]
+fn prune_keeps_package_cache_within_limit() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .file(DEFAULT_NODE_FILE, "contents don't matter")
+        .file(CACHED_NPM_TARBALL, "contents don't matter")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune --yes --max-cache-size 1GiB"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]nothing to prune")
+    );
+
+    assert!(Sandbox::path_exists(CACHED_NPM_TARBALL));
+}
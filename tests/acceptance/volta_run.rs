@@ -318,6 +318,28 @@ fn inherited_yarn() {
     );
 }
 
+#[test]
+fn vendored_yarn_release() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .package_json(&package_json_with_pinned_node("10.99.1040"))
+        .project_file(
+            ".yarnrc.yml",
+            "nodeLinker: node-modules\nyarnPath: .yarn/releases/yarn-3.2.4.cjs\n",
+        )
+        .env(VOLTA_LOGLEVEL, "debug")
+        .build();
+
+    assert_that!(
+        s.volta("run yarn --version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Running the Yarn release at '[..]yarn-3.2.4.cjs'")
+            .with_stderr_contains("[..]Node: 10.99.1040 from project configuration")
+    );
+}
+
 #[test]
 fn force_no_yarn() {
     let s = sandbox()
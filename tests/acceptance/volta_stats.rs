@@ -0,0 +1,101 @@
+use crate::support::sandbox::{sandbox, shim_exe};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "14.17.0",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm"
+}"#;
+
+const PKG_CONFIG_TYPESCRIPT: &str = r#"{
+  "name": "typescript",
+  "version": "4.8.4",
+  "platform": {
+    "node": "14.17.0",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "tsc",
+    "tsserver"
+  ],
+  "manager": "Npm"
+}"#;
+
+const USAGE_HISTORY: &str = r#"{"timestamp":1665700000000,"tool":"tsc","node":{"version":"14.17.0","source":"project"},"project":"/projects/app","duration_ms":812,"exit_code":0}
+{"timestamp":1665700100000,"tool":"node","node":{"version":"14.17.0","source":"default"},"project":null,"duration_ms":40,"exit_code":0}
+{"timestamp":1665700200000,"tool":"node","node":{"version":"10.24.1","source":"project"},"project":"/projects/legacy","duration_ms":35,"exit_code":1}
+{"timestamp":1665700300000,"tool":"npm","node":{"version":"14.17.0","source":"proj
+"#;
+
+const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
+
+#[test]
+fn summarizes_usage_history() {
+    let s = sandbox()
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .package_config("typescript", PKG_CONFIG_TYPESCRIPT)
+        .usage_history(USAGE_HISTORY)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("stats"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("Usage in 3 runs since [..]")
+            .with_stdout_contains("14.17.0[..]2[..]/projects/app")
+            .with_stdout_contains("10.24.1[..]1[..]/projects/legacy")
+            .with_stdout_contains("typescript[..]1[..]/projects/app")
+            .with_stdout_contains("[..]these installed packages have not been used: cowsay")
+    );
+}
+
+#[test]
+fn reports_disabled_history() {
+    let s = sandbox().env(VOLTA_LOGLEVEL, "info").build();
+
+    assert_that!(
+        s.volta("stats"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]the usage history is disabled[..]")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn records_runs_of_shims() {
+    let s = sandbox()
+        .env("VOLTA_USAGE_HISTORY", "1")
+        .env("VOLTA_BYPASS", "1")
+        .env(
+            "VOLTA_INSTALL_DIR",
+            &shim_exe().parent().unwrap().to_string_lossy(),
+        )
+        .build();
+
+    assert_that!(
+        s.process(&shim_exe()),
+        execs().with_status(ExitCode::ExecutionFailure as i32)
+    );
+
+    let history = s.read_usage_history();
+    assert_that!(history.lines().count(), eq(1));
+    assert_that!(
+        history,
+        matches_regex(r#""tool":"volta-shim","node":null,.*"exit_code":126\}"#)
+    );
+}
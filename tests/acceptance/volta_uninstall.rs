@@ -3,6 +3,8 @@ use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use test_support::matchers::execs;
 
+use volta_core::error::ExitCode;
+
 const PKG_CONFIG_BASIC: &str = r#"{
   "name": "cowsay",
   "version": "1.4.0",
@@ -47,6 +49,21 @@ fn bin_config(name: &str) -> String {
     )
 }
 
+fn platform_with_node(node: &str) -> String {
+    format!(
+        r#"{{
+  "node": {{
+    "runtime": "{}",
+    "npm": null
+  }},
+  "yarn": null
+}}"#,
+        node
+    )
+}
+
+const NODE_IMAGE_FILE: &str = ".volta/tools/image/node/11.10.1/bin/node";
+
 const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
 
 #[test]
@@ -178,3 +195,115 @@ fn uninstall_package_orphaned_bins() {
     assert!(!Sandbox::shim_exists("cowsay"));
     assert!(!Sandbox::shim_exists("cowthink"));
 }
+
+#[test]
+fn uninstall_node_version() {
+    let s = sandbox()
+        .file(NODE_IMAGE_FILE, "contents don't matter")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]uninstalled node@11.10.1")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/11.10.1"));
+}
+
+#[test]
+fn uninstall_node_requires_version() {
+    let s = sandbox()
+        .file(NODE_IMAGE_FILE, "contents don't matter")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Please specify the version of node to uninstall.")
+    );
+
+    assert!(Sandbox::path_exists(NODE_IMAGE_FILE));
+}
+
+#[test]
+fn uninstall_default_node_requires_force() {
+    let s = sandbox()
+        .platform(&platform_with_node("11.10.1"))
+        .file(NODE_IMAGE_FILE, "contents don't matter")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]used by your default platform[..]")
+            .with_stderr_contains(
+                "Use `volta uninstall --force node@11.10.1` to remove it anyway."
+            )
+    );
+
+    assert!(Sandbox::path_exists(NODE_IMAGE_FILE));
+}
+
+#[test]
+fn uninstall_package_node_requires_force() {
+    let s = sandbox()
+        .package_config("cowsay", PKG_CONFIG_BASIC)
+        .file(NODE_IMAGE_FILE, "contents don't matter")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]used by the package 'cowsay'[..]")
+    );
+
+    assert!(Sandbox::path_exists(NODE_IMAGE_FILE));
+}
+
+#[test]
+fn uninstall_default_node_with_force() {
+    let s = sandbox()
+        .platform(&platform_with_node("11.10.1"))
+        .file(NODE_IMAGE_FILE, "contents don't matter")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall --force node@11.10.1"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]uninstalled node@11.10.1")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/11.10.1"));
+}
+
+#[test]
+fn uninstall_node_range_in_use_removes_nothing() {
+    let s = sandbox()
+        .platform(&platform_with_node("11.10.1"))
+        .file(NODE_IMAGE_FILE, "contents don't matter")
+        .file(
+            ".volta/tools/image/node/11.9.0/bin/node",
+            "contents don't matter",
+        )
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]used by your default platform[..]")
+    );
+
+    assert!(Sandbox::path_exists(NODE_IMAGE_FILE));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/node/11.9.0/bin/node"
+    ));
+}
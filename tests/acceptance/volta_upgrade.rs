@@ -0,0 +1,154 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use mockito::mock;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+#[test]
+fn upgrade_node_when_up_to_date() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .node_available_versions(NODE_VERSION_INFO)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("upgrade node"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]node is already up to date")
+            .with_stdout_contains("[..]nothing to upgrade")
+    );
+}
+
+#[test]
+fn upgrade_yarn_without_default_fails() {
+    let s = sandbox().platform(PLATFORM_NODE_ONLY).build();
+
+    assert_that!(
+        s.volta("upgrade yarn"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Yarn is not available.")
+    );
+}
+
+#[test]
+fn upgrade_missing_package_fails() {
+    let s = sandbox().platform(PLATFORM_NODE_ONLY).build();
+
+    assert_that!(
+        s.volta("upgrade cowsay"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Could not locate the package 'cowsay' to upgrade.")
+    );
+}
+
+const PKG_CONFIG_PRIVATE: &str = r#"{
+    "name": "private-tool",
+    "version": "1.0.0",
+    "platform": {
+      "node": "10.99.1040",
+      "npm": null,
+      "yarn": null
+    },
+    "bins": [
+      "private-tool"
+    ],
+    "manager": "Npm"
+  }"#;
+
+#[test]
+fn upgrade_all_skips_packages_without_registry_index() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_config("private-tool", PKG_CONFIG_PRIVATE)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("upgrade --all"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Skipping 'private-tool', which could not be checked for newer versions[..]"
+            )
+            .with_stdout_contains("[..]nothing to upgrade")
+    );
+}
+
+const PKG_CONFIG_YARN: &str = r#"{
+    "name": "cowsay",
+    "version": "1.4.0",
+    "platform": {
+      "node": "10.99.1040",
+      "npm": null,
+      "yarn": "1.12.99"
+    },
+    "bins": [
+      "cowsay"
+    ],
+    "manager": "Yarn"
+  }"#;
+
+const COWSAY_PACKAGE_INDEX: &str = r#"{
+    "name": "cowsay",
+    "dist-tags": { "latest": "1.4.0" },
+    "versions": {
+        "1.4.0": { "version": "1.4.0", "dist": { "shasum": "", "tarball": "" }}
+    }
+}"#;
+
+#[test]
+fn upgrade_all_checks_packages_installed_with_yarn() {
+    let _index = mock("GET", "/cowsay")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(COWSAY_PACKAGE_INDEX)
+        .create();
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_config("cowsay", PKG_CONFIG_YARN)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("upgrade --all"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_does_not_contain("[..]Skipping 'cowsay'[..]")
+            .with_stdout_contains("[..]nothing to upgrade")
+    );
+}
+
+#[test]
+fn upgrade_all_without_packages() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("upgrade --all"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]nothing to upgrade")
+    );
+}
@@ -22,5 +22,6 @@ cfg_if::cfg_if! {
         mod volta_fetch;
         mod volta_install;
         mod volta_run;
+        mod volta_upgrade;
     }
 }
@@ -229,6 +229,11 @@ fn package_image_dir(name: &str, root: PathBuf) -> PathBuf {
 fn default_platform_file(root: PathBuf) -> PathBuf {
     default_toolchain_dir(root).join("platform.json")
 }
+fn package_config_file(name: &str, root: PathBuf) -> PathBuf {
+    default_toolchain_dir(root)
+        .join("packages")
+        .join(format!("{}.json", name))
+}
 pub fn node_distro_file_name(version: &str) -> String {
     format!(
         "node-v{}-{}-{}.tar.gz",
@@ -398,6 +403,13 @@ impl TempProject {
         install_dir.exists()
     }
 
+    /// Read the config file that records how the input package was installed
+    pub fn package_config(&self, name: &str) -> serde_json::Value {
+        let config_file = package_config_file(name, self.root());
+        let config_contents = read_file_to_string(config_file);
+        serde_json::from_str(&config_contents).expect("could not parse package config")
+    }
+
     /// Verify that the input package version has been fetched.
     pub fn shim_exists(&self, name: &str) -> bool {
         shim_file(name, self.root()).exists()
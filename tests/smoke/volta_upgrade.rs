@@ -0,0 +1,49 @@
+use crate::support::temp_project::temp_project;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+#[test]
+fn upgrade_package_keeps_its_platform() {
+    let p = temp_project().build();
+
+    assert_that!(
+        p.volta("install node@14.16.1 cowsay@1.4.0"),
+        execs().with_status(0)
+    );
+    // Change the default Node, which the upgraded package shouldn't pick up
+    assert_that!(p.volta("install node@14.17.0"), execs().with_status(0));
+
+    assert_that!(p.volta("upgrade cowsay"), execs().with_status(0));
+
+    let config = p.package_config("cowsay");
+    assert_ne!(config["version"], "1.4.0");
+    assert_eq!(config["platform"]["node"], "14.16.1");
+    assert_that!(p.exec_shim("cowsay", "moo"), execs().with_status(0));
+}
+
+#[test]
+fn upgrade_package_with_repin() {
+    let p = temp_project().build();
+
+    assert_that!(
+        p.volta("install node@14.16.0 typescript@2.8.4"),
+        execs().with_status(0)
+    );
+    assert_that!(p.volta("install node@14.17.1"), execs().with_status(0));
+
+    assert_that!(
+        p.volta("upgrade --repin typescript"),
+        execs().with_status(0)
+    );
+
+    let config = p.package_config("typescript");
+    assert_ne!(config["version"], "2.8.4");
+    assert_eq!(config["platform"]["node"], "14.17.1");
+    assert_that!(
+        p.exec_shim("tsc", "--version"),
+        execs()
+            .with_status(0)
+            .with_stdout_does_not_contain("Version 2.8.4")
+    );
+}